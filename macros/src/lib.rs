@@ -0,0 +1,106 @@
+//! Procedural macros for `memoiter`. These are re-exported by `memoiter` when
+//!     its `macros` feature is enabled, and should be used from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Error, FnArg, Ident, ItemFn, Pat,
+    ReturnType, Type,
+};
+
+
+/// Memoize a function of a single `usize` index. See the documentation of
+///     `memoiter::memoized` for details.
+#[proc_macro_attribute]
+pub fn memoized(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr: proc_macro2::TokenStream = attr.into();
+
+        return Error::new(attr.span(), "`#[memoized]` takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let func: ItemFn = parse_macro_input!(item as ItemFn);
+
+    match expand(func) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+
+fn expand(func: ItemFn) -> Result<proc_macro2::TokenStream, Error> {
+    let ItemFn { attrs, vis, sig, block } = func;
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new(asyncness.span(), "`#[memoized]` cannot be used on an `async fn`"));
+    }
+
+    if let Some(constness) = &sig.constness {
+        return Err(Error::new(constness.span(), "`#[memoized]` cannot be used on a `const fn`"));
+    }
+
+    if let Some(unsafety) = &sig.unsafety {
+        return Err(Error::new(unsafety.span(), "`#[memoized]` cannot be used on an `unsafe fn`"));
+    }
+
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(Error::new(sig.generics.span(), "`#[memoized]` cannot be used on a generic function"));
+    }
+
+    let output: Type = match &sig.output {
+        ReturnType::Type(_, ty) => (**ty).clone(),
+        ReturnType::Default => {
+            return Err(Error::new(sig.span(), "`#[memoized]` requires a return type"));
+        }
+    };
+
+    let param = match (sig.inputs.len(), sig.inputs.first()) {
+        (1, Some(FnArg::Typed(param))) if is_usize(&param.ty) => param.clone(),
+        _ => {
+            return Err(Error::new(
+                sig.inputs.span(),
+                "`#[memoized]` requires a single parameter of type `usize`",
+            ));
+        }
+    };
+
+    //  The outer function keeps the name of a plain parameter, for the sake of
+    //      its documentation, but any other pattern is left to the inner one.
+    let index: Ident = match &*param.pat {
+        Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => pat.ident.clone(),
+        _ => Ident::new("__memoized_index", Span::call_site()),
+    };
+
+    let mut outer = sig.clone();
+    outer.inputs = syn::parse_quote!(#index: usize);
+
+    let mut inner = sig;
+    inner.ident = Ident::new("__memoized_inner", Span::call_site());
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #outer {
+            #inner #block
+
+            ::std::thread_local! {
+                static MEMO: ::memoiter::macro_support::Memo<#output> =
+                    ::memoiter::macro_support::Memo::new();
+            }
+
+            ::memoiter::macro_support::get(&MEMO, #index, __memoized_inner)
+        }
+    })
+}
+
+
+fn is_usize(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident("usize"),
+        Type::Group(group) => is_usize(&group.elem),
+        Type::Paren(paren) => is_usize(&paren.elem),
+        _ => false,
+    }
+}
@@ -0,0 +1,211 @@
+//! Memoization into storage which changes its layout as it grows.
+
+use std::mem::take;
+
+
+/// The layout of the items of an `AdaptiveMemoIter`.
+#[derive(Debug)]
+enum Storage<T> {
+    /// A single Vector, holding fewer items than the threshold.
+    Flat(Vec<T>),
+    /// Vectors of exactly `threshold` items each, except for the last, which
+    ///     is allocated at that capacity and never reallocated.
+    Chunked(Vec<Vec<T>>),
+}
+
+
+/// A Memoized Iterator which stores its items in a single Vector while there
+///     are few of them, and in fixed-size chunks once there are many.
+///
+/// A small sequence is kept contiguous, which is as friendly to the cache as
+///     storage can be. But every time a Vector outgrows its allocation, all of
+///     its items are copied, which becomes costly for very long sequences. So
+///     when the number of items reaches a threshold, the Vector becomes the
+///     first chunk, without any copying, and each later chunk is allocated
+///     with room for exactly `threshold` items. From then on, no item is ever
+///     moved again.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::adaptive::AdaptiveMemoIter;
+///
+/// let mut memo = AdaptiveMemoIter::new((0u64..).map(|n| n * n), 1000);
+///
+/// assert_eq!(memo.get(999), Some(&998001));
+/// assert!(memo.is_chunked());
+///
+/// assert_eq!(memo.get(2500), Some(&6250000));
+/// assert_eq!(memo.chunks().count(), 3);
+/// assert_eq!(memo.recall(1000), Some(&1000000));
+/// ```
+#[derive(Debug)]
+pub struct AdaptiveMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    exhausted: bool,
+    iterator: I,
+    len: usize,
+    storage: Storage<T>,
+    threshold: usize,
+}
+
+
+impl<I, T> AdaptiveMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `AdaptiveMemoIter` wrapping a given Iterator, which will
+    ///     switch to chunked storage when it holds `threshold` items.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `threshold` is zero.
+    pub fn new(iterator: I, threshold: usize) -> Self {
+        assert_ne!(threshold, 0, "threshold must not be zero");
+
+        Self {
+            exhausted: false,
+            iterator,
+            len: 0,
+            storage: Storage::Flat(Vec::new()),
+            threshold,
+        }
+    }
+
+    /// Return the number of items at which storage switches to chunks, which
+    ///     is also the size of each chunk.
+    #[inline]
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Return `true` if the items are stored in chunks.
+    #[inline]
+    pub fn is_chunked(&self) -> bool {
+        matches!(self.storage, Storage::Chunked(_))
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn push(&mut self, item: T) {
+        match &mut self.storage {
+            Storage::Flat(vec) => {
+                vec.push(item);
+
+                if vec.len() == self.threshold {
+                    //  NOTE: The Vector is moved, not copied, into the first
+                    //      chunk.
+                    self.storage = Storage::Chunked(vec![take(vec)]);
+                }
+            }
+            Storage::Chunked(chunks) => {
+                let threshold: usize = self.threshold;
+
+                match chunks.last_mut() {
+                    Some(last) if last.len() < threshold => last.push(item),
+                    _ => {
+                        let mut chunk: Vec<T> = Vec::with_capacity(threshold);
+                        chunk.push(item);
+                        chunks.push(chunk);
+                    }
+                }
+            }
+        }
+
+        self.len += 1;
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.len <= idx {
+            match self.iterator.next() {
+                Some(item) => self.push(item),
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        match &self.storage {
+            Storage::Flat(vec) => vec.get(idx),
+            Storage::Chunked(chunks) => {
+                chunks.get(idx / self.threshold)?.get(idx % self.threshold)
+            }
+        }
+    }
+
+    /// Return an Iterator over the contiguous runs of items evaluated so far:
+    ///     a single slice before the switch to chunks, or one per chunk after.
+    pub fn chunks(&self) -> impl Iterator<Item=&[T]> + '_ {
+        let chunks: &[Vec<T>] = match &self.storage {
+            Storage::Flat(vec) => std::slice::from_ref(vec),
+            Storage::Chunked(chunks) => chunks,
+        };
+
+        chunks.iter().map(Vec::as_slice).filter(|chunk| !chunk.is_empty())
+    }
+
+    /// Return an Iterator over references to the values evaluated so far.
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.chunks().flatten()
+    }
+
+    /// Consume self, returning a Vector of the values evaluated so far, and the
+    ///     internal Iterator. If the items are stored in chunks, they are
+    ///     copied into one Vector.
+    pub fn consume(self) -> (Vec<T>, I) {
+        let sequence: Vec<T> = match self.storage {
+            Storage::Flat(vec) => vec,
+            Storage::Chunked(chunks) => chunks.into_iter().flatten().collect(),
+        };
+
+        (sequence, self.iterator)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch() {
+        let mut memo = AdaptiveMemoIter::new(0..25, 10);
+
+        assert_eq!(memo.get(8), Some(&8));
+        assert!(!memo.is_chunked());
+
+        assert_eq!(memo.get(9), Some(&9));
+        assert!(memo.is_chunked());
+        let first: *const i32 = memo.recall(0).unwrap();
+
+        assert_eq!(memo.get(30), None);
+        assert_eq!(memo.recall(0).unwrap() as *const i32, first);
+
+        let lengths: Vec<usize> = memo.chunks().map(<[i32]>::len).collect();
+        assert_eq!(lengths, [10, 10, 5]);
+        assert!(memo.iter().copied().eq(0..25));
+
+        let (sequence, _) = memo.consume();
+        assert_eq!(sequence, (0..25).collect::<Vec<_>>());
+    }
+}
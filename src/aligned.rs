@@ -0,0 +1,264 @@
+//! Memoization into storage with a guaranteed alignment, for SIMD consumers.
+
+use std::{
+    alloc::{self, Layout},
+    fmt::{self, Debug, Formatter},
+    mem::{align_of, size_of, ManuallyDrop},
+    ptr::{self, NonNull},
+    slice,
+};
+
+
+/// A Memoized Iterator whose stored items begin at an address aligned to a
+///     given boundary, such as 32 or 64 bytes. The evaluated prefix of a
+///     numeric sequence can then be passed straight to SIMD kernels which
+///     require aligned input, without first being copied into an aligned
+///     buffer.
+///
+/// The alignment holds for the start of the storage, and so for every item
+///     whose offset is a multiple of it; it is kept when the storage grows.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::aligned::AlignedMemoIter;
+///
+/// let mut memo = AlignedMemoIter::new((0..).map(|n| n as f32 * 0.5), 64);
+///
+/// assert_eq!(memo.get(99), Some(&49.5));
+/// assert_eq!(memo.as_slice().as_ptr() as usize % 64, 0);
+/// assert_eq!(memo.as_slice().len(), 100);
+/// ```
+pub struct AlignedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    align: usize,
+    buffer: NonNull<T>,
+    capacity: usize,
+    exhausted: bool,
+    iterator: I,
+    len: usize,
+}
+
+
+//  SAFETY: The buffer is uniquely owned, like the buffer of a `Vec<T>`.
+unsafe impl<I, T> Send for AlignedMemoIter<I, T> where
+    I: Iterator<Item=T> + Send,
+    T: Send,
+{}
+
+
+//  SAFETY: Shared access only ever reads the buffer.
+unsafe impl<I, T> Sync for AlignedMemoIter<I, T> where
+    I: Iterator<Item=T> + Sync,
+    T: Sync,
+{}
+
+
+impl<I, T> AlignedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `AlignedMemoIter` wrapping a given Iterator, which will
+    ///     store its items at an address aligned to `align` bytes, or to the
+    ///     alignment of `T` if that is greater.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `align` is not a power of two.
+    pub fn new(iterator: I, align: usize) -> Self {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+
+        Self {
+            align: align.max(align_of::<T>()),
+            buffer: NonNull::dangling(),
+            capacity: if size_of::<T>() == 0 { usize::MAX } else { 0 },
+            exhausted: false,
+            iterator,
+            len: 0,
+        }
+    }
+
+    /// Return the alignment of the storage, in bytes.
+    #[inline]
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn layout(&self, capacity: usize) -> Layout {
+        let size: usize = size_of::<T>().checked_mul(capacity).expect("capacity overflow");
+        Layout::from_size_align(size, self.align).expect("capacity overflow")
+    }
+
+    fn grow(&mut self) {
+        let capacity: usize = self.capacity.saturating_mul(2).max(8);
+        let new: Layout = self.layout(capacity);
+
+        //  SAFETY: The new layout is not zero-sized, because `T` is not, and
+        //      the old layout is the one the buffer was allocated with.
+        let pointer: *mut u8 = unsafe {
+            if self.capacity == 0 {
+                alloc::alloc(new)
+            } else {
+                alloc::realloc(self.buffer.as_ptr().cast(), self.layout(self.capacity), new.size())
+            }
+        };
+
+        self.buffer = match NonNull::new(pointer.cast()) {
+            Some(buffer) => buffer,
+            None => alloc::handle_alloc_error(new),
+        };
+        self.capacity = capacity;
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.len <= idx {
+            match self.iterator.next() {
+                Some(item) => {
+                    if self.len == self.capacity {
+                        self.grow();
+                    }
+
+                    //  SAFETY: The buffer has room for `capacity` items, and
+                    //      `len` is less than it.
+                    unsafe { self.buffer.as_ptr().add(self.len).write(item); }
+                    self.len += 1;
+                }
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.as_slice().get(idx)
+    }
+
+    /// Return a slice of the items evaluated so far. Unless it is empty, the
+    ///     slice starts at an address aligned as requested.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        //  SAFETY: The first `len` items of the buffer are initialized.
+        unsafe { slice::from_raw_parts(self.buffer.as_ptr(), self.len) }
+    }
+
+    /// Return a mutable slice of the items evaluated so far, for kernels which
+    ///     work in place.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        //  SAFETY: As in `as_slice()`, and the buffer is borrowed uniquely.
+        unsafe { slice::from_raw_parts_mut(self.buffer.as_ptr(), self.len) }
+    }
+
+    /// Consume self, returning a Vector of the values evaluated so far, and the
+    ///     internal Iterator. The values are moved into a new allocation, with
+    ///     the ordinary alignment of `T`.
+    pub fn consume(self) -> (Vec<T>, I) {
+        let mut this: ManuallyDrop<Self> = ManuallyDrop::new(self);
+        let mut sequence: Vec<T> = Vec::with_capacity(this.len);
+
+        //  SAFETY: The items are moved out exactly once, and the buffer is then
+        //      freed without dropping them again. The Iterator is moved out of
+        //      `this`, which is never used or dropped afterwards.
+        unsafe {
+            ptr::copy_nonoverlapping(this.buffer.as_ptr(), sequence.as_mut_ptr(), this.len);
+            sequence.set_len(this.len);
+            this.len = 0;
+            this.free();
+
+            (sequence, ptr::read(&this.iterator))
+        }
+    }
+
+    /// Drop the stored items and free the buffer.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must not be used again.
+    unsafe fn free(&mut self) {
+        ptr::drop_in_place(self.as_mut_slice());
+
+        if size_of::<T>() != 0 && self.capacity > 0 {
+            alloc::dealloc(self.buffer.as_ptr().cast(), self.layout(self.capacity));
+        }
+    }
+}
+
+
+impl<I, T> Drop for AlignedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    fn drop(&mut self) {
+        //  SAFETY: The buffer is not used after being dropped.
+        unsafe { self.free(); }
+    }
+}
+
+
+impl<I, T> Debug for AlignedMemoIter<I, T> where
+    I: Debug + Iterator<Item=T>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlignedMemoIter")
+            .field("align", &self.align)
+            .field("exhausted", &self.exhausted)
+            .field("iterator", &self.iterator)
+            .field("sequence", &self.as_slice())
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use super::*;
+
+    #[test]
+    fn test_aligned() {
+        let marker: Rc<()> = Rc::new(());
+        let source = (0..100).map(|n| (n, marker.clone()));
+        let mut memo = AlignedMemoIter::new(source, 256);
+
+        for idx in [0, 7, 8, 50, 99] {
+            assert_eq!(memo.get(idx).map(|(n, _)| *n), Some(idx));
+            assert_eq!(memo.as_slice().as_ptr() as usize % 256, 0);
+        }
+
+        assert_eq!(Rc::strong_count(&marker), 101);
+        drop(memo);
+        assert_eq!(Rc::strong_count(&marker), 1);
+
+        let mut memo = AlignedMemoIter::new((0..10).map(|n| vec![n]), 32);
+        memo.get(4);
+        let (sequence, mut rest) = memo.consume();
+        assert_eq!(sequence, [[0], [1], [2], [3], [4]]);
+        assert_eq!(rest.next(), Some(vec![5]));
+
+        let mut units = AlignedMemoIter::new(std::iter::repeat(()).take(5), 64);
+        assert_eq!(units.get(4), Some(&()));
+        assert_eq!(units.get(5), None);
+    }
+}
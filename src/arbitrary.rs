@@ -0,0 +1,64 @@
+//! Implementation of `arbitrary::Arbitrary` for `MemoIter`, allowing fuzz
+//!     targets to generate memoized state directly.
+
+use ::arbitrary::{Arbitrary, Result, size_hint, Unstructured};
+use std::vec::IntoIter;
+use crate::MemoIter;
+
+
+/// Generate a `MemoIter` over a Vector-backed source. The full sequence is
+///     generated first, and then split at a random point into an evaluated
+///     prefix and an unevaluated remainder. An exhausted `MemoIter` will have
+///     evaluated its entire sequence.
+impl<'a, T> Arbitrary<'a> for MemoIter<IntoIter<T>, T> where
+    T: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut sequence: Vec<T> = u.arbitrary()?;
+        let exhausted: bool = u.arbitrary()?;
+
+        let evaluated: usize = if exhausted {
+            sequence.len()
+        } else {
+            u.int_in_range(0..=sequence.len())?
+        };
+
+        let remainder: Vec<T> = sequence.split_off(evaluated);
+
+        let mut memo = Self::with_vec(remainder.into_iter(), sequence);
+        memo.exhausted = exhausted;
+        Ok(memo)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and_all(&[
+            <Vec<T> as Arbitrary>::size_hint(depth),
+            <bool as Arbitrary>::size_hint(depth),
+            <usize as Arbitrary>::size_hint(depth),
+        ])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary() {
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let mut memo: MemoIter<_, u8> = u.arbitrary().unwrap();
+            let evaluated: usize = memo.evaluated();
+            let total: usize = memo.len();
+
+            assert!(evaluated <= total);
+            assert!(!memo.is_exhausted() || evaluated == total);
+
+            memo.get_slice(..total);
+            assert_eq!(memo.evaluated(), total);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Sequences whose first items are computed ahead of time, such as by a build
+//!     script, and embedded in the program as a constant array.
+
+use std::{
+    fmt::{Debug, Write},
+    iter::{Chain, FusedIterator},
+    slice::Iter,
+};
+use crate::MemoIter;
+
+
+/// A Memoized Iterator whose first items are held in a `'static` slice, and
+///     are served from it without allocation. Items beyond the end of the slice
+///     are evaluated at runtime, from an Iterator which continues the sequence
+///     where the slice ends.
+///
+/// The slice will usually be a constant array generated by `MemoIter::bake()`
+///     in a build script.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+/// use std::iter::successors;
+///
+/// //  Generated at build time by `MemoIter::bake()`.
+/// const FACTORIALS: [u64; 6] = [1, 1, 2, 6, 24, 120];
+///
+/// let mut memo = MemoIter::with_baked(&FACTORIALS, successors(
+///     Some((6, 720u64)),
+///     |&(n, f)| Some((n + 1, f * (n + 1))),
+/// ).map(|(_, f)| f));
+///
+/// assert_eq!(memo.get(4), Some(&24));
+/// assert_eq!(memo.evaluated(), 6);
+///
+/// assert_eq!(memo.get(8), Some(&40320));
+/// assert_eq!(memo.evaluated(), 9);
+/// assert_eq!(memo.tail().evaluated(), 3);
+/// ```
+#[derive(Debug)]
+pub struct BakedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: 'static,
+{
+    baked: &'static [T],
+    tail: MemoIter<I, T>,
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: 'static,
+{
+    /// Create a `BakedMemoIter` which serves the items of a `'static` slice,
+    ///     and then the items of an Iterator. The Iterator should produce the
+    ///     items which follow the slice, starting at index `baked.len()`.
+    pub fn with_baked(baked: &'static [T], iterator: I) -> BakedMemoIter<I, T> {
+        BakedMemoIter {
+            baked,
+            tail: MemoIter::new(iterator),
+        }
+    }
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Evaluate the first `len` items of the sequence, and return Rust source
+    ///     code declaring them as a public constant array, named `name`, with
+    ///     items of the type named `ty`. If the sequence ends before `len`
+    ///     items, the array holds every item.
+    ///
+    /// Items are written with their `Debug` representations, which must be
+    ///     valid expressions of the given type. This is true of integers, of
+    ///     finite floats, and of many simple types with derived
+    ///     implementations.
+    ///
+    /// This is intended for use in build scripts, with the code written to a
+    ///     file in `OUT_DIR` and included by the crate, so that the array can
+    ///     be given to `MemoIter::with_baked()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut squares = MemoIter::new((0u32..).map(|n| n * n));
+    ///
+    /// assert_eq!(
+    ///     squares.bake(5, "SQUARES", "u32"),
+    ///     "pub const SQUARES: [u32; 5] = [0, 1, 4, 9, 16];\n",
+    /// );
+    /// ```
+    pub fn bake(&mut self, len: usize, name: &str, ty: &str) -> String where
+        T: Debug,
+    {
+        let items: &[T] = self.get_slice(..len);
+        let mut code: String = format!("pub const {}: [{}; {}] = [", name, ty, items.len());
+
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                code.push_str(", ");
+            }
+
+            write!(code, "{:?}", item).unwrap();
+        }
+
+        code.push_str("];\n");
+        code
+    }
+}
+
+
+impl<I, T> BakedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: 'static,
+{
+    /// Return the items computed ahead of time.
+    #[inline]
+    pub fn baked(&self) -> &'static [T] {
+        self.baked
+    }
+
+    /// Return the `MemoIter` holding the items evaluated at runtime. Its
+    ///     indices are offset by the length of the baked slice.
+    #[inline]
+    pub fn tail(&self) -> &MemoIter<I, T> {
+        &self.tail
+    }
+
+    /// Return the number of items available, including the baked items.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.baked.len() + self.tail.evaluated()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.tail.is_exhausted()
+    }
+
+    /// Retrieve, by its index, an item of the sequence. If the item is not
+    ///     baked and has not yet been evaluated, the Iterator will be run until
+    ///     it has.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        match idx.checked_sub(self.baked.len()) {
+            None => self.baked.get(idx),
+            Some(offset) => self.tail.get(offset),
+        }
+    }
+
+    /// Retrieve, by its index, an item of the sequence. If the item is not
+    ///     baked and has not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        match idx.checked_sub(self.baked.len()) {
+            None => self.baked.get(idx),
+            Some(offset) => self.tail.as_ref().get(offset),
+        }
+    }
+
+    /// Return an Iterator over every item available, without evaluating any.
+    pub fn iter(&self) -> Chain<Iter<'_, T>, Iter<'_, T>> {
+        self.baked.iter().chain(self.tail.as_ref())
+    }
+
+    /// Consume self, returning a Vector of the items evaluated at runtime, and
+    ///     the internal Iterator.
+    pub fn consume(self) -> (Vec<T>, I) {
+        self.tail.consume()
+    }
+}
+
+
+impl<I, T> Iterator for BakedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Clone + 'static,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx: usize = self.evaluated();
+        self.get(idx).cloned()
+    }
+}
+
+
+impl<I, T> FusedIterator for BakedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Clone + 'static,
+{}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baked() {
+        let code: String = MemoIter::new((0i8..).step_by(3)).bake(4, "TRIPLES", "i8");
+        assert_eq!(code, "pub const TRIPLES: [i8; 4] = [0, 3, 6, 9];\n");
+
+        const TRIPLES: [i8; 4] = [0, 3, 6, 9];
+        let mut memo = MemoIter::with_baked(&TRIPLES, (12i8..=21).step_by(3));
+
+        assert_eq!(memo.recall(3), Some(&9));
+        assert_eq!(memo.recall(4), None);
+        assert_eq!(memo.get(5), Some(&15));
+        assert_eq!(memo.iter().copied().collect::<Vec<_>>(), [0, 3, 6, 9, 12, 15]);
+
+        assert_eq!(memo.by_ref().collect::<Vec<_>>(), [18, 21]);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.get(8), None);
+        assert_eq!(memo.consume().0, [12, 15, 18, 21]);
+    }
+}
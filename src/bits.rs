@@ -0,0 +1,200 @@
+//! Memoization of `bool` sequences in packed bitsets.
+
+use std::iter::FusedIterator;
+
+
+/// The number of bits in each word of storage.
+const WORD: usize = u64::BITS as usize;
+
+
+/// A Memoized Iterator over `bool`s, which stores each item as a single bit.
+///     This uses an eighth of the memory of a `MemoIter` over the same source,
+///     making it suitable for very long sequences of flags, such as sieves or
+///     predicates over indices.
+///
+/// Its methods mirror those of `MemoIter`, returning references to `bool`
+///     where `MemoIter` would, so that one may be swapped for the other. The
+///     exception is that slices cannot be taken, because the items are not
+///     stored as individual bytes.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::bits::BitMemoIter;
+///
+/// let mut prime = BitMemoIter::new((0u64..).map(|n| {
+///     n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+/// }));
+///
+/// assert_eq!(prime.get(97), Some(&true));
+/// assert_eq!(prime.get(91), Some(&false));
+/// assert_eq!(prime.count_ones(), 25);
+/// ```
+#[derive(Debug)]
+pub struct BitMemoIter<I> where
+    I: Iterator<Item=bool>,
+{
+    exhausted: bool,
+    iterator: I,
+    len: usize,
+    words: Vec<u64>,
+}
+
+
+impl<I> BitMemoIter<I> where
+    I: Iterator<Item=bool>,
+{
+    /// Create an empty `BitMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            exhausted: false,
+            iterator,
+            len: 0,
+            words: Vec::new(),
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn push(&mut self, bit: bool) {
+        let offset: usize = self.len % WORD;
+
+        if offset == 0 {
+            self.words.push(0);
+        }
+
+        if bit {
+            if let Some(word) = self.words.last_mut() {
+                *word |= 1 << offset;
+            }
+        }
+
+        self.len += 1;
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.len <= idx {
+            match self.iterator.next() {
+                Some(bit) => self.push(bit),
+                None => {
+                    self.exhausted = true;
+                    self.words.shrink_to_fit();
+                }
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<&bool> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<&bool> {
+        if idx < self.len {
+            let bit: bool = self.words[idx / WORD] >> (idx % WORD) & 1 == 1;
+            Some(if bit { &true } else { &false })
+        } else {
+            None
+        }
+    }
+
+    /// Evaluate the internal Iterator until it ends, returning the number of
+    ///     items evaluated in total.
+    ///
+    /// This method will not return if the Iterator is infinite.
+    pub fn exhaust(&mut self) -> usize {
+        self.expand_to_contain(usize::MAX);
+        self.len
+    }
+
+    /// Return the number of `true` values evaluated so far.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Return the packed words of the bitset. Item `i` is bit `i % 64` of word
+    ///     `i / 64`, and any bits beyond the evaluated items are zero.
+    #[inline]
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Return an Iterator over the values evaluated so far.
+    pub fn iter(&self) -> impl Iterator<Item=bool> + '_ {
+        (0..self.len).map(move |idx| self.words[idx / WORD] >> (idx % WORD) & 1 == 1)
+    }
+
+    /// Consume self, returning a Vector of the values evaluated so far, and the
+    ///     internal Iterator.
+    pub fn consume(self) -> (Vec<bool>, I) {
+        let bits: Vec<bool> = self.iter().collect();
+        (bits, self.iterator)
+    }
+}
+
+
+impl<I> Iterator for BitMemoIter<I> where
+    I: Iterator<Item=bool>,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let idx: usize = self.len;
+        self.get(idx).copied()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.exhausted {
+            (0, Some(0))
+        } else {
+            self.iterator.size_hint()
+        }
+    }
+}
+
+
+impl<I: Iterator<Item=bool>> FusedIterator for BitMemoIter<I> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits() {
+        let pattern = |n: usize| n % 3 == 0 || n % 7 == 0;
+        let mut memo = BitMemoIter::new((0..200).map(pattern));
+
+        assert_eq!(memo.get(126), Some(&true));
+        assert_eq!(memo.recall(127), None);
+        assert_eq!(memo.as_words().len(), 2);
+
+        assert_eq!(memo.exhaust(), 200);
+        assert_eq!(memo.get(200), None);
+        assert_eq!(memo.as_words().len(), 4);
+        assert_eq!(memo.count_ones(), (0..200).filter(|&n| pattern(n)).count());
+
+        let (bits, _) = memo.consume();
+        assert!(bits.iter().enumerate().all(|(n, &b)| b == pattern(n)));
+    }
+}
@@ -0,0 +1,156 @@
+//! Branded indices, which prove that an item has been evaluated, so that it can
+//!     be accessed repeatedly without bounds checks.
+
+use std::marker::PhantomData;
+use crate::MemoIter;
+
+
+/// A marker tying tokens to one call of `MemoIter::branded()`. The lifetime is
+///     invariant, so tokens from one call cannot be used in another.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+
+/// An index which is known to be within the evaluated items of the `MemoIter`
+///     that issued it. It can only be obtained from a `BrandedMemo`, and only
+///     used with the same one.
+///
+/// ```compile_fail
+/// use memoiter::MemoIter;
+///
+/// let mut short = MemoIter::new(0..2);
+/// let mut long = MemoIter::new(0..100);
+///
+/// long.branded(|mut outer| {
+///     let idx = outer.evaluate(50).unwrap();
+///
+///     //  A token from one view cannot be used with another.
+///     short.branded(|inner| *inner.get(idx))
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BrandedIndex<'id> {
+    idx: usize,
+    _brand: Brand<'id>,
+}
+
+
+impl BrandedIndex<'_> {
+    /// Return the plain index.
+    #[inline]
+    pub fn get(self) -> usize {
+        self.idx
+    }
+}
+
+
+/// A view of a `MemoIter` within `MemoIter::branded()`, which issues
+///     `BrandedIndex` tokens, and accepts them for access without bounds
+///     checks.
+///
+/// Items can still be evaluated through this view, but none can be removed, so
+///     every token stays valid for as long as the view exists.
+#[derive(Debug)]
+pub struct BrandedMemo<'id, 'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    memo: &'a mut MemoIter<I, T>,
+    _brand: Brand<'id>,
+}
+
+
+impl<'id, I, T> BrandedMemo<'id, '_, I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.memo.sequence.len()
+    }
+
+    /// Return a token for an index which has already been evaluated, or
+    ///     `None` if it has not.
+    #[inline]
+    pub fn check(&self, idx: usize) -> Option<BrandedIndex<'id>> {
+        if idx < self.memo.sequence.len() {
+            Some(BrandedIndex { idx, _brand: PhantomData })
+        } else {
+            None
+        }
+    }
+
+    /// Evaluate the item at an index if needed, and return a token for it.
+    ///     Returns `None` if the Iterator ends first.
+    pub fn evaluate(&mut self, idx: usize) -> Option<BrandedIndex<'id>> {
+        self.memo.expand_to_contain(idx);
+        self.check(idx)
+    }
+
+    /// Return an Iterator over tokens for every index evaluated so far.
+    pub fn indices(&self) -> impl Iterator<Item=BrandedIndex<'id>> {
+        (0..self.memo.sequence.len()).map(|idx| BrandedIndex { idx, _brand: PhantomData })
+    }
+
+    /// Retrieve the item at a branded index, without a bounds check.
+    #[inline]
+    pub fn get(&self, idx: BrandedIndex<'id>) -> &T {
+        //  SAFETY: A token is only issued for an index below the length of the
+        //      sequence, and the sequence cannot shrink while this view, and
+        //      therefore its brand, exists.
+        unsafe { self.memo.get_unchecked(idx.idx) }
+    }
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Call a function with a branded view of this `MemoIter`, through which
+    ///     indices may be checked once and then used any number of times
+    ///     without bounds checks, and without any `unsafe` at the call site.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((1u64..).map(|n| n * n));
+    ///
+    /// let total: u64 = memo.branded(|mut view| {
+    ///     let last = view.evaluate(99).unwrap();
+    ///     let first = view.check(0).unwrap();
+    ///
+    ///     (0..1000).map(|_| view.get(first) + view.get(last)).sum()
+    /// });
+    ///
+    /// assert_eq!(total, 1000 * (1 + 10000));
+    /// ```
+    pub fn branded<F, R>(&mut self, f: F) -> R where
+        F: for<'id> FnOnce(BrandedMemo<'id, '_, I, T>) -> R,
+    {
+        f(BrandedMemo { memo: self, _brand: PhantomData })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branded() {
+        let mut memo = MemoIter::new(0..10);
+
+        let sum: i32 = memo.branded(|mut view| {
+            assert!(view.check(0).is_none());
+            let fifth = view.evaluate(4).unwrap();
+
+            assert!(view.evaluate(10).is_none());
+            assert_eq!(view.evaluated(), 10);
+            assert_eq!(*view.get(fifth), 4);
+
+            view.indices().map(|idx| view.get(idx)).sum()
+        });
+
+        assert_eq!(sum, 45);
+    }
+}
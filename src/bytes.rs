@@ -0,0 +1,241 @@
+//! Memoization of byte sequences into `bytes` buffers, which can be shared
+//!     without copying.
+
+use ::bytes::{Bytes, BytesMut};
+use std::{collections::Bound, ops::RangeBounds};
+
+
+/// A Memoized Iterator over bytes, which stores its items in `bytes` buffers.
+///     Evaluated ranges can be returned as `Bytes`, which share the memo's
+///     storage rather than copying it, so that cached data can be forwarded to
+///     network code directly.
+///
+/// Evaluated bytes are first written to a mutable tail buffer. When a range
+///     of them is requested as `Bytes`, the tail is frozen into a new segment,
+///     which can then be shared. A range lying within a single segment is
+///     returned without copying; a range spanning several segments must be
+///     copied into one buffer. All segments can be retrieved at once with
+///     `segments()`, for use in vectored writes.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::bytes::ByteMemo;
+///
+/// let mut memo = ByteMemo::new(b"GET / HTTP/1.1\r\n".iter().copied());
+///
+/// let method = memo.get_bytes(..3);
+/// let version = memo.get_bytes(6..14);
+///
+/// assert_eq!(&method[..], b"GET");
+/// assert_eq!(&version[..], b"HTTP/1.1");
+/// assert_eq!(memo.segments().len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct ByteMemo<I> where
+    I: Iterator<Item=u8>,
+{
+    exhausted: bool,
+    frozen: usize,
+    iterator: I,
+    segments: Vec<Bytes>,
+    starts: Vec<usize>,
+    tail: BytesMut,
+}
+
+
+impl<I> ByteMemo<I> where
+    I: Iterator<Item=u8>,
+{
+    /// Create an empty `ByteMemo` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            exhausted: false,
+            frozen: 0,
+            iterator,
+            segments: Vec::new(),
+            starts: Vec::new(),
+            tail: BytesMut::new(),
+        }
+    }
+
+    /// Return the number of bytes evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.frozen + self.tail.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        let len: usize = self.evaluated();
+
+        if !self.exhausted && idx >= len {
+            let needed: usize = (idx - len).saturating_add(1);
+
+            self.tail.extend(self.iterator.by_ref().take(needed));
+
+            if self.evaluated() <= idx {
+                self.exhausted = true;
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a byte returned by the Iterator. If the byte has
+    ///     not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<u8> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a byte returned by the Iterator. If the byte has
+    ///     not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<u8> {
+        if idx >= self.frozen {
+            self.tail.get(idx - self.frozen).copied()
+        } else {
+            let seg: usize = self.starts.partition_point(|&s| s <= idx) - 1;
+            Some(self.segments[seg][idx - self.starts[seg]])
+        }
+    }
+
+    /// Freeze every evaluated byte which is not yet part of a segment into a
+    ///     new segment, returning it. If there are no such bytes, an empty
+    ///     `Bytes` is returned, and no segment is added.
+    pub fn freeze(&mut self) -> Bytes {
+        if self.tail.is_empty() {
+            return Bytes::new();
+        }
+
+        let segment: Bytes = self.tail.split().freeze();
+
+        self.starts.push(self.frozen);
+        self.frozen += segment.len();
+        self.segments.push(segment.clone());
+
+        segment
+    }
+
+    /// Return the frozen segments, which together hold the first
+    ///     `frozen_len()` evaluated bytes, in order.
+    #[inline]
+    pub fn segments(&self) -> &[Bytes] {
+        &self.segments
+    }
+
+    /// Return the number of evaluated bytes which have been frozen into
+    ///     segments.
+    #[inline]
+    pub fn frozen_len(&self) -> usize {
+        self.frozen
+    }
+
+    /// Retrieve a range of bytes returned by the Iterator as `Bytes`, evaluating
+    ///     them and freezing them into a segment if needed. The result shares
+    ///     the memo's storage if the range lies within one segment, and is
+    ///     copied otherwise.
+    ///
+    /// As with `MemoIter::get_slice()`, a range with an unbounded end does no
+    ///     new evaluation, and a range extending past the end of the sequence
+    ///     is shortened rather than causing a panic.
+    pub fn get_bytes<R>(&mut self, range: R) -> Bytes where
+        R: RangeBounds<usize>,
+    {
+        let first: usize = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.saturating_add(1),
+        };
+
+        let end: usize = match range.end_bound() {
+            Bound::Unbounded => self.evaluated(),
+            Bound::Included(&i) => {
+                self.expand_to_contain(i);
+                self.evaluated().min(i.saturating_add(1))
+            }
+            Bound::Excluded(&i) => {
+                if let Some(last) = i.checked_sub(1) {
+                    self.expand_to_contain(last);
+                }
+
+                self.evaluated().min(i)
+            }
+        };
+
+        if first >= end {
+            return Bytes::new();
+        }
+
+        if end > self.frozen {
+            self.freeze();
+        }
+
+        let seg: usize = self.starts.partition_point(|&s| s <= first) - 1;
+        let start: usize = self.starts[seg];
+        let segment: &Bytes = &self.segments[seg];
+
+        if end <= start + segment.len() {
+            segment.slice(first - start..end - start)
+        } else {
+            let mut joined: BytesMut = BytesMut::with_capacity(end - first);
+            let mut pos: usize = first;
+
+            for (segment, &start) in self.segments[seg..].iter().zip(&self.starts[seg..]) {
+                let stop: usize = (start + segment.len()).min(end);
+                joined.extend_from_slice(&segment[pos - start..stop - start]);
+                pos = stop;
+
+                if pos == end {
+                    break;
+                }
+            }
+
+            joined.freeze()
+        }
+    }
+
+    /// Consume self, returning every evaluated byte as a single `Bytes`, and
+    ///     the internal Iterator. This is done without copying if at most one
+    ///     segment has been frozen.
+    pub fn consume(mut self) -> (Bytes, I) {
+        let bytes: Bytes = self.get_bytes(..);
+        (bytes, self.iterator)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        let mut memo = ByteMemo::new(0..=255u8);
+
+        let head: Bytes = memo.get_bytes(..10);
+        assert_eq!(memo.frozen_len(), 10);
+        assert_eq!(memo.get(20), Some(20));
+        assert_eq!(memo.frozen_len(), 10);
+
+        let inner: Bytes = memo.get_bytes(2..5);
+        assert_eq!(inner.as_ptr(), head[2..].as_ptr());
+
+        let spanning: Bytes = memo.get_bytes(8..=12);
+        assert_eq!(&spanning[..], [8, 9, 10, 11, 12]);
+        assert_eq!(memo.segments().len(), 2);
+        assert_eq!(memo.recall(15), Some(15));
+        assert_eq!(memo.recall(21), None);
+
+        assert_eq!(memo.get_bytes(250..300).len(), 6);
+        assert!(memo.is_exhausted());
+
+        let (all, _) = memo.consume();
+        assert_eq!(all.len(), 256);
+        assert!(all.iter().enumerate().all(|(i, &b)| i == b as usize));
+    }
+}
@@ -0,0 +1,216 @@
+//! Memoization of the messages received over a channel, without blocking
+//!     indefinitely.
+
+use std::{
+    ops::Deref,
+    sync::mpsc::{IntoIter, Receiver, RecvTimeoutError, TryRecvError},
+    time::{Duration, Instant},
+};
+use crate::MemoIter;
+
+
+/// A Memoized channel. Wraps an `mpsc::Receiver`, storing every message it
+///     receives so that past messages can be retrieved by index.
+///
+/// A `MemoIter` can be built directly from a `Receiver`, but retrieving an item
+///     which has not yet been sent would block until it is. This type instead
+///     offers retrieval with a timeout, or without blocking at all, reporting
+///     whether an item is not yet available or will never arrive.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::channel::ChannelMemo;
+/// use std::{sync::mpsc::{channel, RecvTimeoutError}, time::Duration};
+///
+/// let (tx, rx) = channel();
+/// let mut memo = ChannelMemo::new(rx);
+///
+/// tx.send("first").unwrap();
+/// assert_eq!(memo.get_timeout(0, Duration::from_millis(10)), Ok(&"first"));
+/// assert_eq!(memo.get_timeout(1, Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+///
+/// drop(tx);
+/// assert_eq!(memo.get_timeout(1, Duration::from_millis(10)), Err(RecvTimeoutError::Disconnected));
+/// assert_eq!(&memo[..], ["first"]);
+/// ```
+#[derive(Debug)]
+pub struct ChannelMemo<T> {
+    disconnected: bool,
+    receiver: Receiver<T>,
+    sequence: Vec<T>,
+}
+
+
+impl<T> ChannelMemo<T> {
+    /// Create an empty `ChannelMemo` wrapping a given Receiver.
+    pub fn new(receiver: Receiver<T>) -> Self {
+        Self {
+            disconnected: false,
+            receiver,
+            sequence: Vec::new(),
+        }
+    }
+
+    /// Return the number of messages received.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Return `true` if every Sender has been dropped and all messages have
+    ///     been received.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Retrieve, by its index, a message received over the channel. If it has
+    ///     not yet been received, this will block until it is. Returns `None`
+    ///     if the channel disconnects before reaching the given index.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        while !self.disconnected && self.sequence.len() <= idx {
+            match self.receiver.recv() {
+                Ok(msg) => self.sequence.push(msg),
+                Err(_) => self.disconnected = true,
+            }
+        }
+
+        self.sequence.get(idx)
+    }
+
+    /// Retrieve, by its index, a message received over the channel. If it has
+    ///     not yet been received, this will block until it is, but for no
+    ///     longer than the given timeout in total.
+    ///
+    /// Returns `RecvTimeoutError::Timeout` if the message is not yet available,
+    ///     or `RecvTimeoutError::Disconnected` if it never will be.
+    pub fn get_timeout(&mut self, idx: usize, timeout: Duration)
+        -> Result<&T, RecvTimeoutError>
+    {
+        let deadline: Option<Instant> = Instant::now().checked_add(timeout);
+
+        while self.sequence.len() <= idx {
+            if self.disconnected {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+
+            let result = match deadline {
+                Some(deadline) => self.receiver.recv_timeout(
+                    deadline.saturating_duration_since(Instant::now()),
+                ),
+                None => self.receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match result {
+                Ok(msg) => self.sequence.push(msg),
+                Err(RecvTimeoutError::Disconnected) => self.disconnected = true,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(&self.sequence[idx])
+    }
+
+    /// Retrieve, by its index, a message received over the channel, taking
+    ///     any messages which are already waiting, but without blocking.
+    ///
+    /// Returns `TryRecvError::Empty` if the message is not yet available, or
+    ///     `TryRecvError::Disconnected` if it never will be.
+    pub fn try_get(&mut self, idx: usize) -> Result<&T, TryRecvError> {
+        while self.sequence.len() <= idx {
+            if self.disconnected {
+                return Err(TryRecvError::Disconnected);
+            }
+
+            match self.receiver.try_recv() {
+                Ok(msg) => self.sequence.push(msg),
+                Err(TryRecvError::Disconnected) => self.disconnected = true,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(&self.sequence[idx])
+    }
+
+    /// Receive every message which is already waiting, without blocking.
+    ///     Returns the number of messages received.
+    pub fn poll(&mut self) -> usize {
+        let before: usize = self.sequence.len();
+
+        loop {
+            match self.receiver.try_recv() {
+                Ok(msg) => self.sequence.push(msg),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        self.sequence.len() - before
+    }
+
+    /// Retrieve, by its index, a message received over the channel. If it has
+    ///     not yet been received, it will **NOT** be waited for, and this
+    ///     method will return `None`.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.sequence.get(idx)
+    }
+
+    /// Consume self, returning a `MemoIter` holding the messages received so
+    ///     far, which will block to receive further messages.
+    pub fn into_memo(self) -> MemoIter<IntoIter<T>, T> {
+        let mut memo = MemoIter::with_vec(self.receiver.into_iter(), self.sequence);
+        memo.exhausted = self.disconnected;
+        memo
+    }
+}
+
+
+impl<T> Deref for ChannelMemo<T> {
+    type Target = [T];
+
+    /// A ChannelMemo dereferences to the slice of its received messages.
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.sequence
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc::channel, thread};
+    use super::*;
+
+    #[test]
+    fn test_channel() {
+        let (tx, rx) = channel();
+        let mut memo = ChannelMemo::new(rx);
+
+        assert_eq!(memo.try_get(0), Err(TryRecvError::Empty));
+
+        let sender = thread::spawn(move || {
+            for n in 0..5 {
+                tx.send(n).unwrap();
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+
+        assert_eq!(memo.get_timeout(2, Duration::from_secs(5)), Ok(&2));
+        assert_eq!(memo.get(4), Some(&4));
+
+        sender.join().unwrap();
+        assert_eq!(memo.poll(), 0);
+        assert!(memo.is_disconnected());
+        assert_eq!(memo.try_get(5), Err(TryRecvError::Disconnected));
+
+        let mut memo = memo.into_memo();
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.get(5), None);
+        assert_eq!(memo.get(4), Some(&4));
+    }
+}
@@ -0,0 +1,267 @@
+//! A wrapper for `MemoIter` whose methods never panic, for use behind FFI
+//!     boundaries and in code which must not unwind.
+
+use std::{
+    collections::Bound,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::RangeBounds,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+use crate::MemoIter;
+
+
+/// An error returned by the methods of `CheckedMemoIter`, in place of a panic.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AccessError {
+    /// The sequence ends before the requested index.
+    OutOfRange {
+        /// The first index which was requested but does not exist.
+        index: usize,
+        /// The length of the sequence.
+        len: usize,
+    },
+    /// The start of a range is after its end.
+    InvalidRange {
+        /// The first index of the range.
+        start: usize,
+        /// The index after the last of the range.
+        end: usize,
+    },
+    /// A bound of a range cannot be represented, such as the end of the range
+    ///     `..=usize::MAX`.
+    Overflow,
+    /// The Iterator panicked during an earlier evaluation, and will not be
+    ///     advanced again.
+    Poisoned,
+}
+
+
+impl Display for AccessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange { index, len } => {
+                write!(f, "index {} is out of range for a sequence of {} items", index, len)
+            }
+            Self::InvalidRange { start, end } => {
+                write!(f, "range starts at {} but ends at {}", start, end)
+            }
+            Self::Overflow => f.write_str("range bound overflows usize"),
+            Self::Poisoned => f.write_str("iterator panicked during evaluation"),
+        }
+    }
+}
+
+
+impl Error for AccessError {}
+
+
+/// Resolve the bounds of a range into a starting index and, if it is bounded,
+///     an exclusive ending index, without overflowing.
+pub(crate) fn resolve<R>(range: &R) -> Result<(usize, Option<usize>), AccessError> where
+    R: RangeBounds<usize>,
+{
+    let start: usize = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.checked_add(1).ok_or(AccessError::Overflow)?,
+    };
+
+    let end: Option<usize> = match range.end_bound() {
+        Bound::Unbounded => None,
+        Bound::Included(&i) => Some(i.checked_add(1).ok_or(AccessError::Overflow)?),
+        Bound::Excluded(&i) => Some(i),
+    };
+
+    match end {
+        Some(end) if start > end => Err(AccessError::InvalidRange { start, end }),
+        _ => Ok((start, end)),
+    }
+}
+
+
+/// A wrapper for a `MemoIter` which reports every failure as an `AccessError`,
+///     and never panics.
+///
+/// Indexing out of range, and malformed or overflowing ranges, return errors.
+///     A panic raised by the Iterator during evaluation is caught, after which
+///     the wrapper is poisoned: the items already stored remain available, but
+///     the Iterator is not advanced again.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, checked::{AccessError, CheckedMemoIter}};
+///
+/// let mut memo = CheckedMemoIter::new(MemoIter::new(0..5));
+///
+/// assert_eq!(memo.get(2), Ok(&2));
+/// assert_eq!(memo.get(9), Err(AccessError::OutOfRange { index: 9, len: 5 }));
+/// assert_eq!(memo.get_slice(1..3), Ok(&[1, 2][..]));
+/// assert_eq!(memo.get_slice(3..1), Err(AccessError::InvalidRange { start: 3, end: 1 }));
+/// assert_eq!(memo.get_slice(..=usize::MAX), Err(AccessError::Overflow));
+/// ```
+#[derive(Debug)]
+pub struct CheckedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    memo: MemoIter<I, T>,
+    poisoned: bool,
+}
+
+
+impl<I, T> CheckedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Wrap a `MemoIter`.
+    pub fn new(memo: MemoIter<I, T>) -> Self {
+        Self { memo, poisoned: false }
+    }
+
+    /// Return a reference to the wrapped `MemoIter`.
+    #[inline]
+    pub fn memo(&self) -> &MemoIter<I, T> {
+        &self.memo
+    }
+
+    /// Consume self, returning the wrapped `MemoIter`.
+    #[inline]
+    pub fn into_inner(self) -> MemoIter<I, T> {
+        self.memo
+    }
+
+    /// Return `true` if the Iterator has panicked, and will not be advanced
+    ///     again.
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Evaluate items until `end` are stored, catching any panic.
+    fn expand_to_len(&mut self, end: usize) -> Result<(), AccessError> {
+        if end > self.memo.sequence.len() && !self.memo.exhausted {
+            if self.poisoned {
+                return Err(AccessError::Poisoned);
+            }
+
+            let memo: &mut MemoIter<I, T> = &mut self.memo;
+
+            if catch_unwind(AssertUnwindSafe(|| memo.expand_to_contain(end - 1))).is_err() {
+                self.poisoned = true;
+                return Err(AccessError::Poisoned);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator, evaluating it
+    ///     if needed.
+    pub fn get(&mut self, idx: usize) -> Result<&T, AccessError> {
+        let end: usize = idx.checked_add(1).ok_or(AccessError::Overflow)?;
+        self.expand_to_len(end)?;
+
+        let len: usize = self.memo.sequence.len();
+        self.memo.sequence.get(idx).ok_or(AccessError::OutOfRange { index: idx, len })
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.memo.sequence.get(idx)
+    }
+
+    /// Retrieve a slice of values returned by the Iterator, evaluating them if
+    ///     needed. As with `MemoIter::get_slice()`, a range without an end is
+    ///     limited to the items already evaluated.
+    ///
+    /// Returns an error if the sequence ends before the end of the range,
+    ///     rather than a shorter slice.
+    pub fn get_slice<R>(&mut self, range: R) -> Result<&[T], AccessError> where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve(&range)?;
+        let len: usize = self.memo.sequence.len();
+        let end: usize = match end {
+            Some(end) => end,
+            None if start > len => return Err(AccessError::OutOfRange { index: start, len }),
+            None => len,
+        };
+
+        self.expand_to_len(end)?;
+        let len: usize = self.memo.sequence.len();
+
+        match self.memo.sequence.get(start..end) {
+            Some(slice) => Ok(slice),
+            None => Err(AccessError::OutOfRange { index: len.max(start), len }),
+        }
+    }
+
+    /// Evaluate the Iterator until it ends, returning the entire sequence.
+    pub fn exhaust(&mut self) -> Result<&[T], AccessError> {
+        if !self.memo.exhausted {
+            if self.poisoned {
+                return Err(AccessError::Poisoned);
+            }
+
+            let memo: &mut MemoIter<I, T> = &mut self.memo;
+
+            if catch_unwind(AssertUnwindSafe(|| { memo.exhaust(); })).is_err() {
+                self.poisoned = true;
+                return Err(AccessError::Poisoned);
+            }
+        }
+
+        Ok(&self.memo.sequence)
+    }
+}
+
+
+impl<I, T> From<MemoIter<I, T>> for CheckedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    fn from(memo: MemoIter<I, T>) -> Self {
+        Self::new(memo)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_poisoned() {
+        let source = (0..10).map(|n| if n == 6 { panic!("bad item") } else { n });
+        let mut memo = CheckedMemoIter::new(MemoIter::new(source));
+
+        assert_eq!(memo.get_slice(2..5), Ok(&[2, 3, 4][..]));
+        assert_eq!(memo.get(8), Err(AccessError::Poisoned));
+        assert!(memo.is_poisoned());
+
+        assert_eq!(memo.get(5), Ok(&5));
+        assert_eq!(memo.get_slice(..), Ok(&[0, 1, 2, 3, 4, 5][..]));
+        assert_eq!(memo.exhaust(), Err(AccessError::Poisoned));
+        assert_eq!(memo.get_slice(8..), Err(AccessError::OutOfRange { index: 8, len: 6 }));
+
+        //  An index too large to reserve space for is only out of range.
+        let mut inner = MemoIter::new(0..5);
+        inner.set_policy(|_| std::ops::ControlFlow::Continue(()));
+        let mut memo = CheckedMemoIter::new(inner);
+
+        assert_eq!(memo.get(usize::MAX - 1), Err(AccessError::OutOfRange { index: usize::MAX - 1, len: 5 }));
+        assert!(!memo.is_poisoned());
+
+        //  A range without an end never evaluates anything new.
+        let source = CountingIter::new(0..);
+        let counter = source.counter();
+        let mut memo = CheckedMemoIter::new(MemoIter::new(source));
+        let expected = Err(AccessError::OutOfRange { index: 1_000_000, len: 0 });
+
+        assert_eq!(memo.get_slice(1_000_000..), expected);
+        assert_eq!(memo.into_inner().get_slice_checked(1_000_000..), expected);
+        assert_eq!(counter.times_advanced(), 0);
+    }
+}
@@ -0,0 +1,213 @@
+//! A two-dimensional counterpart to `MemoIter`, for procedurally generated
+//!     worlds divided into chunks.
+
+use std::collections::HashMap;
+use crate::lru::Recency;
+
+
+/// The coordinates of a chunk.
+pub type ChunkCoord = (i32, i32);
+
+
+/// A cache of chunks, keyed by their coordinates, which are generated on demand
+///     by a user-provided function.
+///
+/// Optionally, a budget may be set on the number of chunks kept in memory. When
+///     a new chunk would exceed the budget, the least recently used chunk is
+///     evicted, and will be regenerated if it is requested again. For this to
+///     be transparent, the generator should be deterministic.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::chunk::ChunkMemo;
+///
+/// let mut world = ChunkMemo::with_budget(2, |(x, y)| x * 100 + y);
+///
+/// assert_eq!(world.get((1, 2)), &102);
+/// assert_eq!(world.get((3, 4)), &304);
+/// assert_eq!(world.get((1, 2)), &102);
+/// assert_eq!(world.generated(), 2);
+///
+/// //  This exceeds the budget, evicting the least recently used chunk.
+/// assert_eq!(world.get((5, 6)), &506);
+/// assert!(!world.contains((3, 4)));
+///
+/// //  The evicted chunk is regenerated when requested again.
+/// assert_eq!(world.get((3, 4)), &304);
+/// assert_eq!(world.generated(), 4);
+/// ```
+#[derive(Debug)]
+pub struct ChunkMemo<F, C> where
+    F: FnMut(ChunkCoord) -> C,
+{
+    budget: Option<usize>,
+    chunks: HashMap<ChunkCoord, C>,
+    generated: usize,
+    generator: F,
+    recency: Recency<ChunkCoord>,
+}
+
+
+impl<F, C> ChunkMemo<F, C> where
+    F: FnMut(ChunkCoord) -> C,
+{
+    /// Create an empty `ChunkMemo` using a given generator function. Chunks
+    ///     will never be evicted.
+    pub fn new(generator: F) -> Self {
+        Self {
+            budget: None,
+            chunks: HashMap::new(),
+            generated: 0,
+            generator,
+            recency: Recency::new(),
+        }
+    }
+
+    /// Create an empty `ChunkMemo` using a given generator function, which will
+    ///     keep no more than `budget` chunks in memory at once. A budget of
+    ///     zero is treated as a budget of one.
+    pub fn with_budget(budget: usize, generator: F) -> Self {
+        let mut new = Self::new(generator);
+        new.budget = Some(budget.max(1));
+        new
+    }
+}
+
+
+impl<F, C> ChunkMemo<F, C> where
+    F: FnMut(ChunkCoord) -> C,
+{
+    /// Return the maximum number of chunks kept in memory, if there is one.
+    #[inline]
+    pub fn budget(&self) -> Option<usize> {
+        self.budget
+    }
+
+    /// Change the maximum number of chunks kept in memory. If the new budget is
+    ///     lower than the current number of chunks, the least recently used
+    ///     chunks are evicted immediately.
+    pub fn set_budget(&mut self, budget: Option<usize>) {
+        self.budget = budget.map(|b| b.max(1));
+        self.enforce_budget();
+    }
+
+    /// Return `true` if the chunk at the given coordinates is currently held in
+    ///     memory.
+    #[inline]
+    pub fn contains(&self, coord: ChunkCoord) -> bool {
+        self.chunks.contains_key(&coord)
+    }
+
+    /// Remove the chunk at the given coordinates from memory, returning it if
+    ///     it was present.
+    pub fn evict(&mut self, coord: ChunkCoord) -> Option<C> {
+        self.recency.forget(&coord);
+        self.chunks.remove(&coord)
+    }
+
+    /// Remove all chunks from memory.
+    pub fn clear(&mut self) {
+        self.recency.clear();
+        self.chunks.clear();
+    }
+
+    /// Return the number of times the generator function has been called. This
+    ///     includes regenerations of evicted chunks.
+    #[inline]
+    pub fn generated(&self) -> usize {
+        self.generated
+    }
+
+    /// Retrieve the chunk at the given coordinates. If it is not currently held
+    ///     in memory, it will be generated, possibly evicting another chunk.
+    pub fn get(&mut self, coord: ChunkCoord) -> &C {
+        if !self.chunks.contains_key(&coord) {
+            let chunk: C = (self.generator)(coord);
+
+            self.generated += 1;
+            self.chunks.insert(coord, chunk);
+        }
+
+        self.recency.touch(&coord);
+        self.enforce_budget();
+        &self.chunks[&coord]
+    }
+
+    /// Return the number of chunks currently held in memory.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Return `true` if no chunks are currently held in memory.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Retrieve the chunk at the given coordinates, only if it is currently
+    ///     held in memory. This does **not** count as a use of the chunk for
+    ///     the purposes of eviction.
+    pub fn peek(&self, coord: ChunkCoord) -> Option<&C> {
+        self.chunks.get(&coord)
+    }
+
+    fn enforce_budget(&mut self) {
+        if let Some(budget) = self.budget {
+            while self.chunks.len() > budget {
+                match self.recency.pop_oldest() {
+                    Some(oldest) => { self.chunks.remove(&oldest); }
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction() {
+        let mut chunks = ChunkMemo::with_budget(3, |(x, y)| vec![x; y as usize]);
+
+        for x in 0..3 {
+            assert_eq!(chunks.get((x, 2)), &[x, x]);
+        }
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.generated(), 3);
+
+        //  Use the oldest chunk, so that the second becomes the oldest.
+        chunks.get((0, 2));
+        chunks.get((9, 1));
+
+        assert!(chunks.contains((0, 2)));
+        assert!(!chunks.contains((1, 2)));
+        assert_eq!(chunks.peek((9, 1)), Some(&vec![9]));
+
+        chunks.set_budget(Some(1));
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks.contains((9, 1)));
+
+        assert_eq!(chunks.evict((9, 1)), Some(vec![9]));
+        assert!(chunks.is_empty());
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let mut chunks = ChunkMemo::new(|(x, y)| x + y);
+
+        for x in -10..10 {
+            for y in -10..10 {
+                assert_eq!(*chunks.get((x, y)), x + y);
+            }
+        }
+
+        assert_eq!(chunks.len(), 400);
+        assert_eq!(chunks.budget(), None);
+    }
+}
@@ -0,0 +1,779 @@
+//! Companion structures, which maintain derived data about the items of a
+//!     `MemoIter` alongside it.
+//!
+//! A companion does not own its `MemoIter`. Instead, it is brought up to date
+//!     with the items evaluated so far by passing it the memoized sequence,
+//!     either explicitly with `sync()`, or implicitly by its query methods,
+//!     which evaluate the `MemoIter` as needed. Each companion assumes that it
+//!     is only ever given the same sequence, which may grow between calls but
+//!     must not otherwise change. The exception is `RangeSums::set()`, which
+//!     changes an item of the sequence and its companion together.
+
+use crate::MemoIter;
+use std::{
+    collections::{HashMap, VecDeque, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+    ops::{Add, Range, Sub},
+};
+
+
+/// A companion recording the running maximum and minimum of a sequence, so that
+///     the largest or smallest item up to any index can be found in constant
+///     time, rather than by scanning.
+///
+/// The index of the extreme item is stored for each position, rather than the
+///     item itself, so no cloning is needed. Of several equal extremes, the
+///     first is reported.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, companion::RunningExtrema};
+///
+/// let mut prices = MemoIter::new(vec![5, 3, 8, 1, 9, 2].into_iter());
+/// let mut extrema = RunningExtrema::new();
+///
+/// assert_eq!(extrema.max_to(&mut prices, 3), Some(&8));
+/// assert_eq!(extrema.min_to(&mut prices, 3), Some(&1));
+/// assert_eq!(extrema.argmax(2), Some(2));
+///
+/// assert_eq!(extrema.max_to(&mut prices, 5), Some(&9));
+/// assert_eq!(extrema.max_to(&mut prices, 6), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RunningExtrema {
+    argmax: Vec<usize>,
+    argmin: Vec<usize>,
+}
+
+
+impl RunningExtrema {
+    /// Create an empty `RunningExtrema`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of items of the sequence which have been recorded.
+    #[inline]
+    pub fn synced(&self) -> usize {
+        self.argmax.len()
+    }
+
+    /// Record any items of the sequence which are not yet recorded.
+    pub fn sync<T: Ord>(&mut self, items: &[T]) {
+        for idx in self.argmax.len()..items.len() {
+            let (max, min) = match (self.argmax.last(), self.argmin.last()) {
+                (Some(&max), Some(&min)) => (
+                    if items[idx] > items[max] { idx } else { max },
+                    if items[idx] < items[min] { idx } else { min },
+                ),
+                _ => (idx, idx),
+            };
+
+            self.argmax.push(max);
+            self.argmin.push(min);
+        }
+    }
+
+    /// Return the index of the largest recorded item at or before `idx`.
+    #[inline]
+    pub fn argmax(&self, idx: usize) -> Option<usize> {
+        self.argmax.get(idx).copied()
+    }
+
+    /// Return the index of the smallest recorded item at or before `idx`.
+    #[inline]
+    pub fn argmin(&self, idx: usize) -> Option<usize> {
+        self.argmin.get(idx).copied()
+    }
+
+    /// Return the largest item at or before `idx`, evaluating the `MemoIter` up
+    ///     to `idx` if needed. Returns `None` if the `MemoIter` ends first.
+    pub fn max_to<'m, I, T>(&mut self, memo: &'m mut MemoIter<I, T>, idx: usize) -> Option<&'m T> where
+        I: Iterator<Item=T>,
+        T: Ord,
+    {
+        memo.get(idx)?;
+        self.sync(memo);
+
+        let memo: &'m MemoIter<I, T> = memo;
+        memo.sequence.get(self.argmax[idx])
+    }
+
+    /// Return the smallest item at or before `idx`, evaluating the `MemoIter`
+    ///     up to `idx` if needed. Returns `None` if the `MemoIter` ends first.
+    pub fn min_to<'m, I, T>(&mut self, memo: &'m mut MemoIter<I, T>, idx: usize) -> Option<&'m T> where
+        I: Iterator<Item=T>,
+        T: Ord,
+    {
+        memo.get(idx)?;
+        self.sync(memo);
+
+        let memo: &'m MemoIter<I, T> = memo;
+        memo.sequence.get(self.argmin[idx])
+    }
+}
+
+
+/// The index used in place of a child for a missing node.
+const NIL: usize = usize::MAX;
+
+
+#[derive(Clone, Debug)]
+struct Node {
+    idx: usize,
+    left: usize,
+    right: usize,
+    priority: u64,
+    size: usize,
+}
+
+
+/// A companion indexing the items of a sequence in sorted order, so that the
+///     rank of a value, and the `k`th smallest item, can be found in
+///     logarithmic time. This supports percentile tracking and similar
+///     algorithms over memoized data, without sorting it again as it grows.
+///
+/// Items are referred to by their index in the sequence, in a randomized
+///     balanced tree, so no cloning is needed. Equal items are ordered by
+///     their index.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, companion::OrderIndex};
+///
+/// let mut scores = MemoIter::new(vec![70, 95, 40, 85, 60].into_iter());
+/// let mut order = OrderIndex::new();
+///
+/// scores.get(2);
+/// assert_eq!(order.select(&scores, 1), Some(&70));
+/// assert_eq!(order.rank(&scores, &90), 2);
+///
+/// scores.exhaust();
+/// assert_eq!(order.select(&scores, 1), Some(&60));
+/// assert_eq!(order.rank(&scores, &90), 4);
+/// assert_eq!(order.select(&scores, 5), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrderIndex {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+
+impl Default for OrderIndex {
+    fn default() -> Self {
+        Self { nodes: Vec::new(), root: NIL }
+    }
+}
+
+
+impl OrderIndex {
+    /// Create an empty `OrderIndex`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of items of the sequence which have been indexed.
+    #[inline]
+    pub fn synced(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Index any items of the sequence which are not yet indexed.
+    pub fn sync<T: Ord>(&mut self, items: &[T]) {
+        for idx in self.nodes.len()..items.len() {
+            let (left, right) = self.split(self.root, &|other| {
+                (&items[other], other) < (&items[idx], idx)
+            });
+
+            //  SplitMix64 of the index, for priorities which are effectively
+            //      random but reproducible.
+            let mut z: u64 = (idx as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+            self.nodes.push(Node {
+                idx,
+                left: NIL,
+                right: NIL,
+                priority: z ^ (z >> 31),
+                size: 1,
+            });
+
+            let merged: usize = self.merge(left, idx);
+            self.root = self.merge(merged, right);
+        }
+    }
+
+    /// Return the number of indexed items strictly less than `value`, after
+    ///     indexing any new items of the sequence.
+    pub fn rank<T: Ord>(&mut self, items: &[T], value: &T) -> usize {
+        self.sync(items);
+
+        let mut node: usize = self.root;
+        let mut rank: usize = 0;
+
+        while node != NIL {
+            let Node { idx, left, right, .. } = self.nodes[node];
+
+            if items[idx] < *value {
+                rank += self.size(left) + 1;
+                node = right;
+            } else {
+                node = left;
+            }
+        }
+
+        rank
+    }
+
+    /// Return the `k`th smallest item, counting from zero, after indexing any
+    ///     new items of the sequence. Returns `None` if there are not enough
+    ///     items.
+    pub fn select<'m, T: Ord>(&mut self, items: &'m [T], k: usize) -> Option<&'m T> {
+        self.sync(items);
+        self.select_index(k).map(|idx| &items[idx])
+    }
+
+    /// Return the index in the sequence of the `k`th smallest indexed item,
+    ///     counting from zero. No new items are indexed.
+    pub fn select_index(&self, mut k: usize) -> Option<usize> {
+        let mut node: usize = self.root;
+
+        while node != NIL {
+            let Node { idx, left, right, .. } = self.nodes[node];
+            let below: usize = self.size(left);
+
+            match k.cmp(&below) {
+                std::cmp::Ordering::Less => node = left,
+                std::cmp::Ordering::Equal => return Some(idx),
+                std::cmp::Ordering::Greater => {
+                    k -= below + 1;
+                    node = right;
+                }
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size(&self, node: usize) -> usize {
+        if node == NIL { 0 } else { self.nodes[node].size }
+    }
+
+    fn update(&mut self, node: usize) {
+        let Node { left, right, .. } = self.nodes[node];
+        self.nodes[node].size = self.size(left) + self.size(right) + 1;
+    }
+
+    /// Split a subtree into the nodes for which `goes_left` is `true`, and the
+    ///     rest. The predicate must be monotonic in the order of the tree.
+    fn split(&mut self, node: usize, goes_left: &dyn Fn(usize) -> bool) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+
+        if goes_left(self.nodes[node].idx) {
+            let (a, b) = self.split(self.nodes[node].right, goes_left);
+            self.nodes[node].right = a;
+            self.update(node);
+            (node, b)
+        } else {
+            let (a, b) = self.split(self.nodes[node].left, goes_left);
+            self.nodes[node].left = b;
+            self.update(node);
+            (a, node)
+        }
+    }
+
+    /// Join two subtrees, where every node of `a` precedes every node of `b`.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            b
+        } else if b == NIL {
+            a
+        } else if self.nodes[a].priority > self.nodes[b].priority {
+            self.nodes[a].right = self.merge(self.nodes[a].right, b);
+            self.update(a);
+            a
+        } else {
+            self.nodes[b].left = self.merge(a, self.nodes[b].left);
+            self.update(b);
+            b
+        }
+    }
+}
+
+
+/// A companion maintaining a Fenwick tree over a sequence of numbers, so that
+///     the sum of any range of items can be found in logarithmic time, and
+///     remains correct after items are replaced with `set()`. This makes a
+///     `MemoIter` usable as a lazily evaluated table with point updates.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, companion::RangeSums};
+///
+/// let mut memo = MemoIter::new((1..).map(|n| n * n));
+/// let mut sums = RangeSums::new();
+///
+/// assert_eq!(sums.range_sum(&mut memo, 0..4), Some(1 + 4 + 9 + 16));
+///
+/// assert_eq!(sums.set(&mut memo, 1, 0), Some(4));
+/// assert_eq!(memo.recall(1), Some(&0));
+/// assert_eq!(sums.range_sum(&mut memo, 0..4), Some(1 + 9 + 16));
+/// assert_eq!(sums.range_sum(&mut memo, 2..6), Some(9 + 16 + 25 + 36));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RangeSums<T> {
+    tree: Vec<T>,
+}
+
+
+impl<T> Default for RangeSums<T> {
+    fn default() -> Self {
+        Self { tree: Vec::new() }
+    }
+}
+
+
+impl<T> RangeSums<T> where
+    T: Copy + Default + Add<Output=T> + Sub<Output=T>,
+{
+    /// Create an empty `RangeSums`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of items of the sequence which have been recorded.
+    #[inline]
+    pub fn synced(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Record any items of the sequence which are not yet recorded.
+    pub fn sync(&mut self, items: &[T]) {
+        for &item in &items[self.tree.len().min(items.len())..] {
+            //  Node `n` (from one) covers the `n & n.wrapping_neg()` items
+            //      ending with item `n`, so it is the sum of the item with
+            //      the nodes covering the rest of that span.
+            let n: usize = self.tree.len() + 1;
+            let start: usize = n - (n & n.wrapping_neg());
+            let mut node: T = item;
+            let mut j: usize = n - 1;
+
+            while j > start {
+                node = node + self.tree[j - 1];
+                j -= j & j.wrapping_neg();
+            }
+
+            self.tree.push(node);
+        }
+    }
+
+    /// Return the sum of the first `n` recorded items. No new items are
+    ///     recorded.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `n` is greater than the number of recorded
+    ///     items.
+    pub fn prefix_sum(&self, mut n: usize) -> T {
+        assert!(n <= self.tree.len(), "prefix of {} items exceeds {} recorded", n, self.tree.len());
+        let mut sum: T = T::default();
+
+        while n > 0 {
+            sum = sum + self.tree[n - 1];
+            n -= n & n.wrapping_neg();
+        }
+
+        sum
+    }
+
+    /// Return the sum of the items in `range`, evaluating the `MemoIter` up to
+    ///     the end of the range if needed. Returns `None` if the `MemoIter`
+    ///     ends first.
+    pub fn range_sum<I>(&mut self, memo: &mut MemoIter<I, T>, range: Range<usize>) -> Option<T> where
+        I: Iterator<Item=T>,
+    {
+        if range.end > 0 {
+            memo.get(range.end - 1)?;
+        }
+
+        self.sync(memo);
+
+        if range.start < range.end {
+            Some(self.prefix_sum(range.end) - self.prefix_sum(range.start))
+        } else {
+            Some(T::default())
+        }
+    }
+
+    /// Replace the item at `idx` in the `MemoIter` with `value`, evaluating up
+    ///     to `idx` if needed, and update the sums to match. Returns the
+    ///     replaced item, or `None` if the `MemoIter` ends first, in which case
+    ///     nothing is changed.
+    pub fn set<I>(&mut self, memo: &mut MemoIter<I, T>, idx: usize, value: T) -> Option<T> where
+        I: Iterator<Item=T>,
+    {
+        memo.get(idx)?;
+        self.sync(memo);
+
+        let old: T = std::mem::replace(&mut memo.sequence[idx], value);
+        let mut n: usize = idx + 1;
+
+        while n <= self.tree.len() {
+            self.tree[n - 1] = self.tree[n - 1] - old + value;
+            n += n & n.wrapping_neg();
+        }
+
+        Some(old)
+    }
+}
+
+
+/// A companion indexing every window of `k` consecutive items in a sequence,
+///     so that it can be found quickly whether, and where, a window has
+///     occurred before. This is a building block for spotting cycles,
+///     deduplicating, and mining patterns in memoized streams.
+///
+/// Windows are stored as their starting indices, grouped by hash, and are
+///     compared against the sequence itself, so no cloning is needed.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, companion::KGramIndex};
+///
+/// let mut digits = MemoIter::new([3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 1, 4].iter().copied());
+/// let mut trigrams = KGramIndex::new(3);
+///
+/// assert_eq!(trigrams.earlier(&mut digits, 9), Some(0));
+/// assert_eq!(trigrams.earlier(&mut digits, 3), None);
+///
+/// let starts: Vec<usize> = trigrams.positions(&digits, &[3, 1, 4]).collect();
+/// assert_eq!(starts, [0, 9]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KGramIndex {
+    k: usize,
+    starts: HashMap<u64, Vec<usize>>,
+    synced: usize,
+}
+
+
+impl KGramIndex {
+    /// Create an empty `KGramIndex` of windows of `k` items.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `k` is zero.
+    pub fn new(k: usize) -> Self {
+        assert_ne!(k, 0, "windows must not be empty");
+
+        Self {
+            k,
+            starts: HashMap::new(),
+            synced: 0,
+        }
+    }
+
+    /// Return the number of items in each window.
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Return the number of windows of the sequence which have been indexed.
+    #[inline]
+    pub fn synced(&self) -> usize {
+        self.synced
+    }
+
+    /// Index any complete windows of the sequence which are not yet indexed.
+    pub fn sync<T: Hash>(&mut self, items: &[T]) {
+        let windows: usize = (items.len() + 1).saturating_sub(self.k);
+
+        for start in self.synced..windows {
+            let hash: u64 = Self::hash(&items[start..start + self.k]);
+            self.starts.entry(hash).or_default().push(start);
+        }
+
+        self.synced = self.synced.max(windows);
+    }
+
+    /// Return an Iterator over the starting indices of the indexed windows
+    ///     equal to `window`, in ascending order, after indexing any new
+    ///     windows of the sequence.
+    pub fn positions<'a, T: Eq + Hash>(&'a mut self, items: &'a [T], window: &'a [T])
+        -> impl Iterator<Item=usize> + 'a
+    {
+        self.sync(items);
+
+        let k: usize = self.k;
+        let starts: &'a [usize] = match window.len() == k {
+            true => self.starts.get(&Self::hash(window)).map_or(&[], Vec::as_slice),
+            false => &[],
+        };
+
+        starts.iter().copied().filter(move |&start| items[start..start + k] == *window)
+    }
+
+    /// Return the first index at which the window starting at `start` also
+    ///     occurs, if it occurs anywhere before `start`. The `MemoIter` is
+    ///     evaluated up to the end of the window if needed. Returns `None` if
+    ///     the `MemoIter` ends first.
+    pub fn earlier<I, T>(&mut self, memo: &mut MemoIter<I, T>, start: usize) -> Option<usize> where
+        I: Iterator<Item=T>,
+        T: Eq + Hash,
+    {
+        memo.get(start + self.k - 1)?;
+
+        let memo: &MemoIter<I, T> = memo;
+        let window: &[T] = &memo[start..start + self.k];
+
+        self.positions(memo, window).next().filter(|&first| first < start)
+    }
+
+    fn hash<T: Hash>(window: &[T]) -> u64 {
+        let mut hasher: DefaultHasher = DefaultHasher::new();
+        window.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+
+/// A companion tracking the maximum and minimum of the most recent `k` items of
+///     a sequence, updated incrementally as items are evaluated, for rolling
+///     extremes over a memoized feed.
+///
+/// Indices of candidate extremes are kept in monotonic deques, so each item is
+///     added and removed at most once, and queries take constant time. Of
+///     several equal extremes, the latest is reported.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, companion::SlidingExtrema};
+///
+/// let mut feed = MemoIter::new(vec![5, 3, 8, 1, 9, 2, 4].into_iter());
+/// let mut window = SlidingExtrema::new(3);
+///
+/// feed.get(3);
+/// assert_eq!(window.max(&feed), Some(&8));
+/// assert_eq!(window.min(&feed), Some(&1));
+///
+/// feed.exhaust();
+/// assert_eq!(window.max(&feed), Some(&9));
+/// assert_eq!(window.min(&feed), Some(&2));
+/// assert_eq!(window.argmax(), Some(4));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SlidingExtrema {
+    k: usize,
+    maxima: VecDeque<usize>,
+    minima: VecDeque<usize>,
+    synced: usize,
+}
+
+
+impl SlidingExtrema {
+    /// Create an empty `SlidingExtrema` over windows of `k` items.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `k` is zero.
+    pub fn new(k: usize) -> Self {
+        assert_ne!(k, 0, "windows must not be empty");
+
+        Self {
+            k,
+            maxima: VecDeque::new(),
+            minima: VecDeque::new(),
+            synced: 0,
+        }
+    }
+
+    /// Return the number of items in the window.
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Return the number of items of the sequence which have been recorded.
+    #[inline]
+    pub fn synced(&self) -> usize {
+        self.synced
+    }
+
+    /// Record any items of the sequence which are not yet recorded.
+    pub fn sync<T: Ord>(&mut self, items: &[T]) {
+        //  Items which fall out of the window before the end need not enter
+        //      the deques at all.
+        let first: usize = self.synced.max(items.len().saturating_sub(self.k));
+
+        if first > self.synced {
+            self.maxima.clear();
+            self.minima.clear();
+        }
+
+        for idx in first..items.len() {
+            while self.maxima.back().is_some_and(|&i| items[i] <= items[idx]) {
+                self.maxima.pop_back();
+            }
+
+            while self.minima.back().is_some_and(|&i| items[i] >= items[idx]) {
+                self.minima.pop_back();
+            }
+
+            self.maxima.push_back(idx);
+            self.minima.push_back(idx);
+        }
+
+        self.synced = self.synced.max(items.len());
+        let start: usize = self.synced.saturating_sub(self.k);
+
+        while self.maxima.front().is_some_and(|&i| i < start) {
+            self.maxima.pop_front();
+        }
+
+        while self.minima.front().is_some_and(|&i| i < start) {
+            self.minima.pop_front();
+        }
+    }
+
+    /// Return the index of the largest item in the window, without recording
+    ///     any new items.
+    #[inline]
+    pub fn argmax(&self) -> Option<usize> {
+        self.maxima.front().copied()
+    }
+
+    /// Return the index of the smallest item in the window, without recording
+    ///     any new items.
+    #[inline]
+    pub fn argmin(&self) -> Option<usize> {
+        self.minima.front().copied()
+    }
+
+    /// Return the largest of the last `k` items of the sequence, after
+    ///     recording any new items. Returns `None` if the sequence is empty.
+    pub fn max<'m, T: Ord>(&mut self, items: &'m [T]) -> Option<&'m T> {
+        self.sync(items);
+        self.argmax().map(|idx| &items[idx])
+    }
+
+    /// Return the smallest of the last `k` items of the sequence, after
+    ///     recording any new items. Returns `None` if the sequence is empty.
+    pub fn min<'m, T: Ord>(&mut self, items: &'m [T]) -> Option<&'m T> {
+        self.sync(items);
+        self.argmin().map(|idx| &items[idx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrema() {
+        let mut memo = MemoIter::new([4, 4, 2, 7, 7, 1].iter().copied());
+        let mut extrema = RunningExtrema::new();
+
+        extrema.sync(&memo);
+        assert_eq!(extrema.synced(), 0);
+        assert_eq!(extrema.argmax(0), None);
+
+        memo.exhaust();
+        extrema.sync(&memo);
+        assert_eq!(extrema.synced(), 6);
+
+        let argmax: Vec<usize> = (0..6).filter_map(|i| extrema.argmax(i)).collect();
+        let argmin: Vec<usize> = (0..6).filter_map(|i| extrema.argmin(i)).collect();
+        assert_eq!(argmax, [0, 0, 0, 3, 3, 3]);
+        assert_eq!(argmin, [0, 0, 2, 2, 2, 5]);
+    }
+
+    #[test]
+    fn test_sliding_extrema() {
+        let mut memo = MemoIter::new((0..500u64).map(|n| n * 7919 % 1009));
+        let mut window = SlidingExtrema::new(10);
+
+        for len in (0..=500).step_by(4) {
+            if len > 0 {
+                memo.get(len - 1);
+            }
+
+            let recent: &[u64] = &memo[len.saturating_sub(10)..];
+            assert_eq!(window.max(&memo), recent.iter().max());
+            assert_eq!(window.min(&memo), recent.iter().min());
+        }
+
+        assert_eq!(window.synced(), 500);
+    }
+
+    #[test]
+    fn test_kgram_index() {
+        //  The sequence of `x -> x * x + 1 mod 101` falls into a cycle, which
+        //      appears as the first repeated window.
+        let mut memo = MemoIter::successors(Some(3u32), |x| Some((x * x + 1) % 101));
+        let mut index = KGramIndex::new(2);
+
+        let (start, first) = (0..)
+            .find_map(|start| index.earlier(&mut memo, start).map(|first| (start, first)))
+            .unwrap();
+
+        assert_eq!(memo[first], memo[start]);
+        assert!(memo[first..start].iter().all(|x| memo[..first].iter().all(|y| x != y)));
+        assert_eq!(index.positions(&memo, &memo[first..first + 2]).collect::<Vec<_>>(), [first, start]);
+        assert_eq!(index.positions(&memo, &memo[..1]).count(), 0);
+    }
+
+    #[test]
+    fn test_range_sums() {
+        let mut memo = MemoIter::new((0..100i64).map(|n| n * 3 - 50));
+        let mut sums = RangeSums::new();
+        let mut naive: Vec<i64> = (0..100).map(|n| n * 3 - 50).collect();
+
+        assert_eq!(sums.range_sum(&mut memo, 10..20), Some(naive[10..20].iter().sum()));
+        assert_eq!(sums.synced(), 20);
+
+        for idx in (0..100).step_by(7) {
+            assert_eq!(sums.set(&mut memo, idx, idx as i64), Some(naive[idx]));
+            naive[idx] = idx as i64;
+        }
+
+        for start in (0..100).step_by(9) {
+            for end in (start..=100).step_by(13) {
+                assert_eq!(sums.range_sum(&mut memo, start..end), Some(naive[start..end].iter().sum()));
+            }
+        }
+
+        assert_eq!(memo.to_vec(), naive);
+        assert_eq!(sums.set(&mut memo, 100, 0), None);
+        assert_eq!(sums.range_sum(&mut memo, 90..101), None);
+    }
+
+    #[test]
+    fn test_order_index() {
+        let mut memo = MemoIter::new((0..1000u64).map(|n| n * 7919 % 1009 / 4));
+        let mut order = OrderIndex::new();
+        memo.exhaust();
+
+        let mut sorted: Vec<u64> = memo.to_vec();
+        sorted.sort_unstable();
+
+        for k in (0..1000).step_by(37) {
+            assert_eq!(order.select(&memo, k), Some(&sorted[k]));
+            assert_eq!(order.rank(&memo, &sorted[k]), sorted.partition_point(|&v| v < sorted[k]));
+        }
+
+        assert_eq!(order.synced(), 1000);
+        assert_eq!(order.select_index(1000), None);
+        assert_eq!(order.rank(&memo, &u64::MAX), 1000);
+    }
+}
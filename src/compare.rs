@@ -0,0 +1,306 @@
+//! Lazy comparisons and searches of `MemoIter`s against other sequences.
+
+use crate::{MemoIter, validate::{CacheDiff, Divergence}};
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Compare the memoized sequence against another Iterator, item by item,
+    ///     evaluating this `MemoIter` only as far as the comparison requires.
+    ///     At most `limit` items are compared.
+    ///
+    /// Returns the number of items compared if no difference is found, which
+    ///     is `limit` unless both sequences end first. Otherwise, returns the
+    ///     index of the first difference, where either the items are unequal
+    ///     or only one sequence has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, validate::Divergence};
+    ///
+    /// let mut reference = MemoIter::new((0u64..).map(|n| n * (n + 1) / 2));
+    /// let candidate = (0u64..).scan(0, |acc, n| { *acc += n; Some(*acc) });
+    ///
+    /// assert_eq!(reference.eq_iter(candidate, 1000), Ok(1000));
+    /// assert_eq!(reference.eq_iter(vec![0, 1, 3, 7], 1000), Err(Divergence { index: 3 }));
+    /// assert_eq!(reference.eq_iter(vec![0, 1, 3], 1000), Err(Divergence { index: 3 }));
+    /// ```
+    pub fn eq_iter<J>(&mut self, other: J, limit: usize) -> Result<usize, Divergence> where
+        J: IntoIterator,
+        T: PartialEq<J::Item>,
+    {
+        let mut other = other.into_iter();
+
+        for index in 0..limit {
+            match (self.get(index), other.next()) {
+                (Some(a), Some(b)) if *a == b => {}
+                (None, None) => return Ok(index),
+                _ => return Err(Divergence { index }),
+            }
+        }
+
+        Ok(limit)
+    }
+
+    /// Return the length of the longest common prefix of two memoized
+    ///     sequences, evaluating both in lockstep until they diverge, either
+    ///     ends, or `limit` items have been compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// //  Two approximations of the harmonic series, in different precisions.
+    /// let mut exact = MemoIter::new((1..).scan(0.0f64, |sum, n| {
+    ///     *sum += 1.0 / n as f64;
+    ///     Some(format!("{:.3}", sum))
+    /// }));
+    /// let mut rough = MemoIter::new((1..).scan(0.0f32, |sum, n| {
+    ///     *sum += 1.0 / n as f32;
+    ///     Some(format!("{:.3}", sum))
+    /// }));
+    ///
+    /// let agreed: usize = exact.common_prefix_len(&mut rough, 1_000_000);
+    /// assert!(0 < agreed && agreed < 1_000_000);
+    /// assert_eq!(exact.evaluated(), agreed + 1);
+    /// ```
+    pub fn common_prefix_len<J, U>(&mut self, other: &mut MemoIter<J, U>, limit: usize) -> usize where
+        J: Iterator<Item=U>,
+        T: PartialEq<U>,
+    {
+        for idx in 0..limit {
+            match (self.get(idx), other.get(idx)) {
+                (Some(a), Some(b)) if a == b => {}
+                _ => return idx,
+            }
+        }
+
+        limit
+    }
+
+    /// Return `true` if the memoized sequence begins with the given prefix.
+    ///
+    /// If `evaluate` is `true`, items are evaluated one at a time as needed to
+    ///     decide, stopping at the first mismatch. Otherwise, only the items
+    ///     already evaluated are checked, and `false` is returned if there are
+    ///     too few of them to match the whole prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new(b"HTTP/1.1 200 OK".iter().copied());
+    ///
+    /// assert!(!memo.starts_with(b"HTTP/", false));
+    /// assert!(memo.starts_with(b"HTTP/", true));
+    /// assert!(!memo.starts_with(b"HTTP/2", true));
+    /// assert_eq!(memo.evaluated(), 6);
+    /// ```
+    pub fn starts_with(&mut self, prefix: &[T], evaluate: bool) -> bool where
+        T: PartialEq,
+    {
+        if evaluate {
+            prefix.iter().enumerate().all(|(idx, item)| self.get(idx) == Some(item))
+        } else {
+            self.sequence.starts_with(prefix)
+        }
+    }
+
+    /// Search for the first occurrence of a pattern within the first `limit`
+    ///     items of the memoized sequence, returning the index at which it
+    ///     begins. Items are evaluated as the search reaches them.
+    ///
+    /// Each item is examined only once, using the Knuth-Morris-Pratt algorithm,
+    ///     so the search takes linear time even when partial matches overlap.
+    ///     An empty pattern is found at index zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// //  The digits of the Champernowne constant: 0.123456789101112...
+    /// let mut digits = MemoIter::new((1u32..).flat_map(|n| {
+    ///     n.to_string().into_bytes()
+    /// }));
+    ///
+    /// assert_eq!(digits.find_subslice(b"1011", 1000), Some(9));
+    /// assert_eq!(digits.find_subslice(b"999", 100), None);
+    /// assert_eq!(digits.find_subslice(b"999", 10_000), Some(2587));
+    /// ```
+    pub fn find_subslice(&mut self, pattern: &[T], limit: usize) -> Option<usize> where
+        T: PartialEq,
+    {
+        if pattern.is_empty() {
+            return Some(0);
+        }
+
+        //  For each prefix of the pattern, the length of its longest proper
+        //      prefix which is also a suffix.
+        let mut fallback: Vec<usize> = vec![0; pattern.len()];
+        let mut k: usize = 0;
+
+        for i in 1..pattern.len() {
+            while k > 0 && pattern[i] != pattern[k] {
+                k = fallback[k - 1];
+            }
+
+            if pattern[i] == pattern[k] {
+                k += 1;
+            }
+
+            fallback[i] = k;
+        }
+
+        let mut matched: usize = 0;
+
+        for idx in 0..limit {
+            let item: &T = self.get(idx)?;
+
+            while matched > 0 && *item != pattern[matched] {
+                matched = fallback[matched - 1];
+            }
+
+            if *item == pattern[matched] {
+                matched += 1;
+
+                if matched == pattern.len() {
+                    return Some(idx + 1 - matched);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Return `true` if the items evaluated so far end with the given suffix.
+    ///     Nothing new is evaluated, so unless the `MemoIter` is exhausted,
+    ///     this describes only the current end of the stored sequence.
+    #[inline]
+    pub fn ends_with_evaluated(&self, suffix: &[T]) -> bool where
+        T: PartialEq,
+    {
+        self.sequence.ends_with(suffix)
+    }
+
+    /// Compare the items evaluated so far against those of another cache of the
+    ///     same sequence, such as another `MemoIter` or a saved snapshot,
+    ///     without evaluating anything. Reports the first index at which the
+    ///     caches disagree, along with how many items each holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, validate::{CacheDiff, Divergence}};
+    ///
+    /// let mut live = MemoIter::new((0u64..).map(|n| n * 3));
+    /// let mut restored = MemoIter::from(vec![0, 3, 6, 9]);
+    /// live.get(2);
+    /// restored.exhaust();
+    ///
+    /// let diff: CacheDiff = live.diff(&restored);
+    /// assert!(diff.is_consistent());
+    /// assert_eq!((diff.left, diff.right), (3, 4));
+    ///
+    /// assert_eq!(live.diff(&[0, 3, 7]).divergence, Some(Divergence { index: 2 }));
+    /// ```
+    pub fn diff<U>(&self, other: &[U]) -> CacheDiff where
+        T: PartialEq<U>,
+    {
+        let index: Option<usize> = self.sequence.iter()
+            .zip(other)
+            .position(|(a, b)| *a != *b);
+
+        CacheDiff {
+            divergence: index.map(|index| Divergence { index }),
+            left: self.sequence.len(),
+            right: other.len(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut memo = MemoIter::new(0..10);
+        let snapshot: Vec<i32> = (0..10).collect();
+
+        assert!(memo.diff(&snapshot).is_consistent());
+        memo.exhaust();
+        assert!(memo.diff(&snapshot).is_identical());
+        assert!(!memo.diff(&snapshot[..5]).is_identical());
+
+        let diff: CacheDiff = memo.diff(&[0, 1, 2, 0]);
+        assert_eq!(diff, CacheDiff {
+            divergence: Some(Divergence { index: 3 }),
+            left: 10,
+            right: 4,
+        });
+    }
+
+    #[test]
+    fn test_eq_iter() {
+        let source = CountingIter::new(0..10);
+        let counter = source.counter();
+        let mut memo = MemoIter::new(source);
+
+        assert_eq!(memo.eq_iter(0..5, 3), Ok(3));
+        assert_eq!(counter.times_advanced(), 3);
+
+        assert_eq!(memo.eq_iter(vec![0, 1, 9], 10), Err(Divergence { index: 2 }));
+        assert_eq!(memo.eq_iter(0..10, 20), Ok(10));
+        assert_eq!(memo.eq_iter(0..11, 20), Err(Divergence { index: 10 }));
+        assert_eq!(memo.eq_iter(0..0, 0), Ok(0));
+    }
+
+    #[test]
+    fn test_affixes() {
+        let mut memo = MemoIter::new(0..5);
+
+        assert!(memo.starts_with(&[], false));
+        assert!(memo.ends_with_evaluated(&[]));
+        assert!(!memo.starts_with(&[0, 1, 2, 3, 4, 5], true));
+        assert!(memo.is_exhausted());
+
+        assert!(memo.starts_with(&[0, 1, 2, 3, 4], false));
+        assert!(memo.ends_with_evaluated(&[3, 4]));
+        assert!(!memo.ends_with_evaluated(&[2, 4]));
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        let mut a = MemoIter::new(0..);
+        let mut b = MemoIter::new(vec![0, 1, 2, 3, 7].into_iter());
+
+        assert_eq!(a.common_prefix_len(&mut b, 2), 2);
+        assert_eq!(a.evaluated(), 2);
+        assert_eq!(a.common_prefix_len(&mut b, 100), 4);
+        assert_eq!(a.evaluated(), 5);
+
+        let mut c = MemoIter::new(0..3);
+        assert_eq!(a.common_prefix_len(&mut c, 100), 3);
+        assert_eq!(a.evaluated(), 5);
+    }
+
+    #[test]
+    fn test_find_subslice() {
+        let mut memo = MemoIter::new("aabaabaaab".chars());
+
+        assert_eq!(memo.find_subslice(&['a', 'a', 'a', 'b'], 9), None);
+        assert_eq!(memo.evaluated(), 9);
+        assert_eq!(memo.find_subslice(&['a', 'a', 'a', 'b'], 100), Some(6));
+        assert_eq!(memo.find_subslice(&['b', 'a', 'a'], 100), Some(2));
+        assert_eq!(memo.find_subslice(&['b', 'b'], 100), None);
+        assert_eq!(memo.find_subslice(&[], 0), Some(0));
+        assert!(memo.is_exhausted());
+    }
+}
@@ -0,0 +1,324 @@
+//! Downsampled views and storage of memoized sequences.
+
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+use crate::MemoIter;
+
+
+/// An indexable view of every `step`-th item of a `MemoIter`, in which item `i`
+///     is item `i * step` of the parent. This is returned by
+///     `MemoIter::decimate()`.
+///
+/// The view borrows its parent, evaluating it as needed. Every item of the
+///     parent up to the requested one is still evaluated and stored, so the
+///     view saves no memory, but it allows long memoized signals to be
+///     inspected at a lower rate without recomputing indices by hand.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+///
+/// let mut signal = MemoIter::new((0..).map(|n: u32| n * n));
+/// let mut every_tenth = signal.decimate(10);
+///
+/// assert_eq!(every_tenth.get(3), Some(&900));
+/// assert_eq!(every_tenth.evaluated(), 4);
+/// assert_eq!(every_tenth.iter().collect::<Vec<_>>(), [&0, &100, &400, &900]);
+///
+/// assert_eq!(signal.evaluated(), 31);
+/// ```
+#[derive(Debug)]
+pub struct Decimated<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    memo: &'a mut MemoIter<I, T>,
+    step: usize,
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return a view of every `step`-th item of this `MemoIter`, starting with
+    ///     the first.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `step` is zero.
+    pub fn decimate(&mut self, step: usize) -> Decimated<'_, I, T> {
+        assert_ne!(step, 0, "decimation step must be nonzero");
+        Decimated { memo: self, step }
+    }
+}
+
+
+impl<I, T> Decimated<'_, I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the step between the parent indices of consecutive items.
+    #[inline]
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Return the number of items of the view which are already evaluated in
+    ///     the parent.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        let parent: usize = self.memo.evaluated();
+        parent / self.step + usize::from(parent % self.step != 0)
+    }
+
+    /// Retrieve item `idx` of the view, evaluating the parent up to index
+    ///     `idx * step` if needed. Returns `None` if the parent ends first, or
+    ///     if that index would overflow.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        let parent: usize = idx.checked_mul(self.step)?;
+        self.memo.get(parent)
+    }
+
+    /// Retrieve item `idx` of the view, if it has already been evaluated in the
+    ///     parent. Nothing new is evaluated.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        let parent: usize = idx.checked_mul(self.step)?;
+        self.memo.sequence.get(parent)
+    }
+
+    /// Return an Iterator over the items of the view which are already
+    ///     evaluated in the parent.
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.memo.sequence.iter().step_by(self.step)
+    }
+}
+
+
+/// An error indicating that an item was evaluated, but not retained.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Dropped {
+    /// The index of the item which was requested.
+    pub index: usize,
+    /// The index of the closest retained landmark before the item.
+    pub landmark: usize,
+}
+
+
+impl Display for Dropped {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "item {} was not retained; the closest landmark is item {}", self.index, self.landmark)
+    }
+}
+
+
+impl Error for Dropped {}
+
+
+/// A Memoized Iterator which permanently stores only every `step`-th item, as
+///     landmarks, and the most recent `window` items. Every other item is
+///     discarded once it leaves the window, bounding the memory used by a long
+///     stream to one item in `step`.
+///
+/// Retained items are returned exactly. Requesting a discarded item returns a
+///     `Dropped` error, which gives the index of the closest landmark before
+///     it. The most recently evaluated item is always retained, even with a
+///     window of zero, so that an item can be returned when it is first
+///     evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::decimate::{Dropped, LandmarkMemoIter};
+///
+/// let mut memo = LandmarkMemoIter::new((0..).map(|n: u64| n * n), 100, 10);
+///
+/// assert_eq!(memo.get(1_234), Ok(Some(&1_522_756)));
+/// assert_eq!(memo.get(1_230), Ok(Some(&1_512_900)));
+/// assert_eq!(memo.get(1_200), Ok(Some(&1_440_000)));
+/// assert_eq!(memo.get(1_201), Err(Dropped { index: 1_201, landmark: 1_200 }));
+///
+/// assert_eq!(memo.landmarks().len(), 13);
+/// ```
+#[derive(Debug)]
+pub struct LandmarkMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    exhausted: bool,
+    iterator: I,
+    landmarks: Vec<T>,
+    position: usize,
+    recent: VecDeque<Option<T>>,
+    step: usize,
+    window: usize,
+}
+
+
+impl<I, T> LandmarkMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `LandmarkMemoIter` wrapping a given Iterator, which will
+    ///     retain every `step`-th item, starting with the first, and the last
+    ///     `window` items evaluated.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `step` is zero.
+    pub fn new(iterator: I, step: usize, window: usize) -> Self {
+        assert_ne!(step, 0, "landmark step must be nonzero");
+
+        Self {
+            exhausted: false,
+            iterator,
+            landmarks: Vec::new(),
+            position: 0,
+            recent: VecDeque::new(),
+            step,
+            window,
+        }
+    }
+
+    /// Return the step between the indices of consecutive landmarks.
+    #[inline]
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Return the number of recent items retained.
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Return the number of items evaluated, including those discarded.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.position
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Return the landmarks evaluated so far. Landmark `i` is the item at index
+    ///     `i * step`.
+    #[inline]
+    pub fn landmarks(&self) -> &[T] {
+        &self.landmarks
+    }
+
+    /// Return `true` if the item at an index has been evaluated and retained.
+    pub fn is_retained(&self, idx: usize) -> bool {
+        matches!(self.recall(idx), Ok(Some(_)))
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.position <= idx {
+            match self.iterator.next() {
+                Some(item) => {
+                    if self.position % self.step == 0 {
+                        self.landmarks.push(item);
+                        self.recent.push_back(None);
+                    } else {
+                        self.recent.push_back(Some(item));
+                    }
+
+                    self.position += 1;
+
+                    if self.recent.len() > self.window.max(1) {
+                        self.recent.pop_front();
+                    }
+                }
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    /// Retrieve, by its index, an item returned by the Iterator. If the item
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    ///
+    /// Returns `Ok(None)` if the Iterator ends before the index, or a `Dropped`
+    ///     error if the item was evaluated earlier but not retained.
+    pub fn get(&mut self, idx: usize) -> Result<Option<&T>, Dropped> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, an item returned by the Iterator, without
+    ///     evaluating anything. Returns `Ok(None)` if the item has not yet been
+    ///     evaluated, or a `Dropped` error if it was not retained.
+    pub fn recall(&self, idx: usize) -> Result<Option<&T>, Dropped> {
+        if idx >= self.position {
+            return Ok(None);
+        }
+
+        if idx % self.step == 0 {
+            return Ok(self.landmarks.get(idx / self.step));
+        }
+
+        match (idx + self.recent.len()).checked_sub(self.position) {
+            Some(offset) => Ok(self.recent[offset].as_ref()),
+            None => Err(Dropped { index: idx, landmark: idx - idx % self.step }),
+        }
+    }
+
+    /// Return the closest landmark at or before an index, with its own index,
+    ///     if it has been evaluated. Nothing new is evaluated.
+    pub fn landmark_before(&self, idx: usize) -> Option<(usize, &T)> {
+        let landmark: usize = idx.min(self.position.checked_sub(1)?) / self.step;
+        Some((landmark * self.step, &self.landmarks[landmark]))
+    }
+
+    /// Consume self, returning the landmarks evaluated so far, and the internal
+    ///     Iterator.
+    pub fn consume(self) -> (Vec<T>, I) {
+        (self.landmarks, self.iterator)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimate() {
+        let mut memo = MemoIter::new(0..20);
+        let mut view = memo.decimate(3);
+
+        assert_eq!(view.evaluated(), 0);
+        assert_eq!(view.recall(0), None);
+        assert_eq!(view.get(6), Some(&18));
+        assert_eq!(view.get(7), None);
+        assert_eq!(view.evaluated(), 7);
+        assert_eq!(view.get(usize::MAX), None);
+
+        let mut memo = MemoIter::new(0..);
+        memo.get(4);
+        assert_eq!(memo.decimate(2).evaluated(), 3);
+        assert_eq!(memo.decimate(5).evaluated(), 1);
+        assert_eq!(memo.decimate(1).iter().count(), 5);
+    }
+
+    #[test]
+    fn test_landmarks() {
+        let mut memo = LandmarkMemoIter::new(0..25, 5, 0);
+
+        assert_eq!(memo.get(7), Ok(Some(&7)));
+        assert_eq!(memo.recall(6), Err(Dropped { index: 6, landmark: 5 }));
+        assert_eq!(memo.get(5), Ok(Some(&5)));
+        assert_eq!(memo.recall(8), Ok(None));
+        assert_eq!(memo.landmark_before(9), Some((5, &5)));
+
+        assert_eq!(memo.get(30), Ok(None));
+        assert!(memo.is_exhausted());
+        assert!(memo.is_retained(24));
+        assert!(!memo.is_retained(23));
+        assert_eq!(memo.landmark_before(100), Some((20, &20)));
+        assert_eq!(memo.consume().0, [0, 5, 10, 15, 20]);
+    }
+}
@@ -0,0 +1,96 @@
+//! Implementations of `defmt::Format`, allowing the state of a `MemoIter` or a
+//!     `FixedMemoIter` to be logged efficiently from embedded targets.
+
+use ::defmt::{Format, Formatter, write};
+#[cfg(feature = "heapless")]
+use crate::fixed::{CapacityError, FixedMemoIter};
+#[cfg(feature = "std")]
+use crate::{MemoIter, validate::{CacheDiff, Divergence}};
+
+
+/// The largest number of items included when formatting a `MemoIter`, so that
+///     the output stays small however long the sequence grows.
+const PREVIEW: usize = 4;
+
+
+/// Emit an event for the evaluation of the items from `start` to `end`
+///     inclusive. This is only logged if `defmt` is configured to log `trace`
+///     messages for this crate, and otherwise compiles to nothing.
+#[inline]
+pub(crate) fn trace_evaluation(start: usize, end: usize) {
+    ::defmt::trace!("memoiter: evaluating {=usize}..={=usize}", start, end);
+}
+
+
+/// Format a summary of a `MemoIter`: the number of items evaluated, whether it
+///     is exhausted, and its first few items.
+#[cfg(feature = "std")]
+impl<I, T> Format for MemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Format,
+{
+    fn format(&self, f: Formatter<'_>) {
+        let head: &[T] = &self.sequence[..self.sequence.len().min(PREVIEW)];
+
+        write!(
+            f,
+            "MemoIter {{ evaluated: {=usize}, exhausted: {=bool}, head: {=[?]} }}",
+            self.sequence.len(),
+            self.exhausted,
+            head,
+        );
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl Format for Divergence {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "sequences diverge at index {=usize}", self.index);
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl Format for CacheDiff {
+    fn format(&self, f: Formatter<'_>) {
+        write!(
+            f,
+            "CacheDiff {{ divergence: {=?}, left: {=usize}, right: {=usize} }}",
+            self.divergence,
+            self.left,
+            self.right,
+        );
+    }
+}
+
+
+/// Format a summary of a `FixedMemoIter`, as for a `MemoIter`, along with its
+///     capacity.
+#[cfg(feature = "heapless")]
+impl<I, T, const N: usize> Format for FixedMemoIter<I, T, N> where
+    I: Iterator<Item=T>,
+    T: Format,
+{
+    fn format(&self, f: Formatter<'_>) {
+        let items: &[T] = self.as_slice();
+        let head: &[T] = &items[..items.len().min(PREVIEW)];
+
+        write!(
+            f,
+            "FixedMemoIter {{ evaluated: {=usize}, capacity: {=usize}, exhausted: {=bool}, head: {=[?]} }}",
+            items.len(),
+            N,
+            self.is_exhausted(),
+            head,
+        );
+    }
+}
+
+
+#[cfg(feature = "heapless")]
+impl Format for CapacityError {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "storage is full at {=usize} items", self.capacity);
+    }
+}
@@ -0,0 +1,151 @@
+//! Access to a single index of a `MemoIter`, whose item may be taken from the
+//!     cache, evaluated, or supplied by the caller.
+
+use crate::MemoIter;
+
+
+/// A view into a single index of a `MemoIter`, which may or may not have been
+///     evaluated yet. Returned by `MemoIter::entry()`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+///
+/// let mut memo = MemoIter::new((0..).map(|n: u64| n * n));
+///
+/// //  Evaluate the item if needed, and then change it.
+/// *memo.entry(2).or_eval().unwrap() += 1;
+/// assert_eq!(memo.get(2), Some(&5));
+///
+/// //  Supply an item from elsewhere, instead of evaluating it.
+/// assert_eq!(memo.entry(3).or_insert(1000), Some(&mut 1000));
+/// assert_eq!(memo.get_slice(..5), [0, 1, 5, 1000, 16]);
+///
+/// //  Change an item only if it has already been evaluated.
+/// memo.entry(4).and_modify(|n| *n = 0);
+/// memo.entry(9).and_modify(|n| *n = 0);
+/// assert_eq!(memo.get_slice(..), [0, 1, 5, 1000, 0]);
+/// ```
+#[derive(Debug)]
+pub struct Entry<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    idx: usize,
+    memo: &'a mut MemoIter<I, T>,
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return an `Entry` for the item at a given index. Nothing is evaluated
+    ///     until a method of the `Entry` requires it.
+    ///
+    /// An `Entry` gives mutable access to the stored item. Changing an item
+    ///     does not notify observers or a mirror, which will already have seen
+    ///     the original item if they were set when it was evaluated.
+    #[inline]
+    pub fn entry(&mut self, idx: usize) -> Entry<'_, I, T> {
+        Entry { idx, memo: self }
+    }
+}
+
+
+impl<'a, I, T> Entry<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the index of this entry.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// Return `true` if the item of this entry has already been evaluated.
+    #[inline]
+    pub fn is_evaluated(&self) -> bool {
+        self.idx < self.memo.sequence.len()
+    }
+
+    /// Call a function on the item of this entry, if it has already been
+    ///     evaluated. Nothing is evaluated.
+    pub fn and_modify<F>(self, func: F) -> Self where
+        F: FnOnce(&mut T),
+    {
+        if let Some(item) = self.memo.sequence.get_mut(self.idx) {
+            func(item);
+        }
+
+        self
+    }
+
+    /// Return the item of this entry, evaluating the Iterator until it is
+    ///     stored, as with `MemoIter::get()`. Returns `None` if the Iterator
+    ///     ends first.
+    pub fn or_eval(self) -> Option<&'a mut T> {
+        self.memo.expand_to_contain(self.idx);
+        self.memo.sequence.get_mut(self.idx)
+    }
+
+    /// Return the item of this entry, storing a given value as the item if it
+    ///     has not yet been evaluated. See `Entry::or_insert_with()`.
+    pub fn or_insert(self, value: T) -> Option<&'a mut T> {
+        self.or_insert_with(|| value)
+    }
+
+    /// Return the item of this entry, storing the result of a function as the
+    ///     item if it has not yet been evaluated.
+    ///
+    /// The items before this entry are evaluated first, so that the value
+    ///     is stored at the right index. The Iterator is then advanced past the
+    ///     item it would have produced here, the next time it is evaluated.
+    ///     Returns `None`, without calling the function, if the Iterator ends
+    ///     before the index before this entry.
+    pub fn or_insert_with<F>(self, func: F) -> Option<&'a mut T> where
+        F: FnOnce() -> T,
+    {
+        if let Some(before) = self.idx.checked_sub(1) {
+            self.memo.expand_to_contain(before);
+        }
+
+        if self.memo.sequence.len() == self.idx {
+            if !self.memo.exhausted {
+                self.memo.skip += 1;
+            }
+
+            self.memo.store(func());
+        }
+
+        self.memo.sequence.get_mut(self.idx)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_entry() {
+        let iter = CountingIter::new(10..15);
+        let counter = iter.counter();
+        let mut memo = MemoIter::new(iter);
+        let indices = memo.subscribe_indices();
+
+        assert_eq!(memo.entry(2).or_insert_with(|| 0), Some(&mut 0));
+        assert_eq!(counter.times_advanced(), 2);
+        assert!(memo.entry(2).is_evaluated());
+
+        assert_eq!(memo.entry(3).or_eval(), Some(&mut 13));
+        assert_eq!(counter.times_advanced(), 4);
+
+        assert_eq!(memo.entry(4).or_insert(99).map(|n| *n), Some(99));
+        assert_eq!(memo.exhaust(), [10, 11, 0, 13, 99]);
+        assert_eq!(memo.entry(5).or_insert(-1), Some(&mut -1));
+        assert_eq!(memo.entry(7).or_insert(-1), None);
+        assert_eq!(memo.entry(6).or_eval(), None);
+
+        assert_eq!(indices.try_iter().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+    }
+}
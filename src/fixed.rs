@@ -0,0 +1,163 @@
+//! Memoization into fixed-capacity storage, which never allocates.
+
+use core::fmt::{self, Display, Formatter};
+use heapless::Vec;
+
+
+/// An error indicating that an item could not be stored because the storage
+///     is full.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CapacityError {
+    /// The number of items the storage can hold.
+    pub capacity: usize,
+}
+
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "storage is full at {} items", self.capacity)
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+
+/// A Memoized Iterator which stores up to `N` items inline, in a
+///     `heapless::Vec`, and returns an error rather than allocating when more
+///     are needed. This type itself uses only `core`, for use in code targeting
+///     microcontrollers without an allocator.
+///
+/// When the storage is full, the Iterator is not advanced any further, so that
+///     no item is evaluated and then lost.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::fixed::{CapacityError, FixedMemoIter};
+///
+/// let mut memo: FixedMemoIter<_, u16, 8> = FixedMemoIter::new((0..).map(|n| n * n));
+///
+/// assert_eq!(memo.get(7), Ok(Some(&49)));
+/// assert_eq!(memo.get(8), Err(CapacityError { capacity: 8 }));
+/// assert_eq!(memo.recall(3), Some(&9));
+///
+/// let mut short: FixedMemoIter<_, u16, 8> = FixedMemoIter::new(0..3);
+/// assert_eq!(short.get(8), Ok(None));
+/// ```
+#[derive(Debug)]
+pub struct FixedMemoIter<I, T, const N: usize> where
+    I: Iterator<Item=T>,
+{
+    exhausted: bool,
+    iterator: I,
+    sequence: Vec<T, N>,
+}
+
+
+impl<I, T, const N: usize> FixedMemoIter<I, T, N> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `FixedMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            exhausted: false,
+            iterator,
+            sequence: Vec::new(),
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Return the number of items which can be stored.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) -> Result<(), CapacityError> {
+        #[cfg(feature = "defmt")]
+        if !self.exhausted && self.sequence.len() <= idx {
+            crate::defmt::trace_evaluation(self.sequence.len(), idx);
+        }
+
+        while !self.exhausted && self.sequence.len() <= idx {
+            if self.sequence.is_full() {
+                return Err(CapacityError { capacity: N });
+            }
+
+            match self.iterator.next() {
+                //  NOTE: The storage is not full, so this cannot fail.
+                Some(item) => { let _ = self.sequence.push(item); }
+                None => self.exhausted = true,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    ///     Returns `Ok(None)` if the Iterator ends before the index, or an
+    ///     error if the storage fills up first.
+    pub fn get(&mut self, idx: usize) -> Result<Option<&T>, CapacityError> {
+        self.expand_to_contain(idx)?;
+        Ok(self.recall(idx))
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.sequence.get(idx)
+    }
+
+    /// Return a slice of the items evaluated so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.sequence
+    }
+
+    /// Consume self, returning the storage and the internal Iterator.
+    #[inline]
+    pub fn consume(self) -> (Vec<T, N>, I) {
+        (self.sequence, self.iterator)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    use super::*;
+
+    #[test]
+    fn test_full() {
+        //  NOTE: `testing::CountingIter` needs `std`, which this module must
+        //      be testable without.
+        let advanced: Cell<usize> = Cell::new(0);
+        let source = (0..100).inspect(|_| advanced.set(advanced.get() + 1));
+        let mut memo: FixedMemoIter<_, i32, 4> = FixedMemoIter::new(source);
+
+        assert_eq!(memo.get(2), Ok(Some(&2)));
+        assert_eq!(memo.get(50), Err(CapacityError { capacity: 4 }));
+        assert_eq!(advanced.get(), 4);
+        assert!(!memo.is_exhausted());
+
+        let (sequence, mut rest) = memo.consume();
+        assert_eq!(sequence, [0, 1, 2, 3]);
+        assert_eq!(rest.next(), Some(4));
+    }
+}
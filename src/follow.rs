@@ -0,0 +1,240 @@
+//! Memoization of the lines of a growing file, in the manner of `tail -f`.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+
+/// The default time to wait between reads, while waiting for a new line.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(50);
+
+
+/// A memo of the lines read from a source which may grow, such as a log file
+///     which is still being written. Reaching the end of the source does not
+///     exhaust the memo; it only means that no more lines are available yet.
+///     Every line read so far can be accessed by its index.
+///
+/// Only complete lines, ending with a newline, are stored. If the source ends
+///     partway through a line, or even partway through a character, the
+///     partial line is held back until the rest of it arrives. Line endings
+///     are not included in the stored lines.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::follow::FollowLines;
+/// use std::{fs::OpenOptions, io::Write, time::Duration};
+///
+/// # let dir = std::env::temp_dir().join(format!("memoiter-doc-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("follow.log");
+/// let mut log = std::fs::File::create(&path)?;
+/// writeln!(log, "first")?;
+///
+/// let mut lines = FollowLines::open(&path)?;
+/// assert_eq!(lines.get(0)?, Some("first"));
+/// assert_eq!(lines.get(1)?, None);
+///
+/// write!(log, "second")?;
+/// assert_eq!(lines.get(1)?, None);
+/// assert_eq!(lines.pending(), "second");
+///
+/// writeln!(log)?;
+/// assert_eq!(lines.wait_for(1, Duration::from_secs(1))?, Some("second"));
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct FollowLines<B: BufRead> {
+    interval: Duration,
+    lines: Vec<String>,
+    pending: Vec<u8>,
+    reader: B,
+}
+
+
+impl FollowLines<BufReader<File>> {
+    /// Open a file to be followed from its beginning.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+
+impl<B: BufRead> FollowLines<B> {
+    /// Create an empty `FollowLines` reading from a given source.
+    pub fn new(reader: B) -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+            lines: Vec::new(),
+            pending: Vec::new(),
+            reader,
+        }
+    }
+
+    /// Return the time waited between reads by `wait_for()`.
+    #[inline]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Change the time waited between reads by `wait_for()`.
+    #[inline]
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Return the number of complete lines read so far.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Return every complete line read so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Return the partial line at the end of the source, which is not yet
+    ///     available as a line because its newline has not been read. If the
+    ///     source ends partway through a character, the text before that
+    ///     character is returned.
+    pub fn pending(&self) -> &str {
+        match std::str::from_utf8(&self.pending) {
+            Ok(text) => text,
+            Err(e) => std::str::from_utf8(&self.pending[..e.valid_up_to()]).unwrap_or_default(),
+        }
+    }
+
+    /// Read every complete line currently available from the source, without
+    ///     waiting for more. Returns the number of new lines.
+    ///
+    /// Returns an error of kind `InvalidData` if a complete line is not valid
+    ///     UTF-8. The line is kept as pending, so that no later line can take
+    ///     its index, and the same error is returned by every later read.
+    pub fn poll(&mut self) -> io::Result<usize> {
+        let before: usize = self.lines.len();
+
+        loop {
+            //  NOTE: The line is read as bytes, and decoded only once its
+            //      newline arrives, so that a character split between two
+            //      writes is not mistaken for invalid data.
+            if self.pending.last() != Some(&b'\n') {
+                match self.reader.read_until(b'\n', &mut self.pending) {
+                    Ok(_) if self.pending.last() == Some(&b'\n') => {}
+                    Ok(_) => break,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+
+            let end: usize = self.pending.iter()
+                .rposition(|&b| b != b'\n' && b != b'\r')
+                .map_or(0, |last| last + 1);
+            let line: &str = std::str::from_utf8(&self.pending[..end])
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            self.lines.push(line.to_owned());
+            self.pending.clear();
+        }
+
+        Ok(self.lines.len() - before)
+    }
+
+    /// Retrieve a line by its index. If it has not yet been read, any lines
+    ///     currently available are read first, without waiting for more.
+    pub fn get(&mut self, idx: usize) -> io::Result<Option<&str>> {
+        if idx >= self.lines.len() {
+            self.poll()?;
+        }
+
+        Ok(self.recall(idx))
+    }
+
+    /// Retrieve a line by its index, only if it has already been read.
+    pub fn recall(&self, idx: usize) -> Option<&str> {
+        self.lines.get(idx).map(String::as_str)
+    }
+
+    /// Retrieve a line by its index, waiting up to `timeout` for it to become
+    ///     available. The source is read again after each `interval()` until
+    ///     the line arrives. Returns `None` if the time runs out first.
+    pub fn wait_for(&mut self, idx: usize, timeout: Duration) -> io::Result<Option<&str>> {
+        let start: Instant = Instant::now();
+
+        while self.get(idx)?.is_none() {
+            let elapsed: Duration = start.elapsed();
+
+            if elapsed >= timeout {
+                return Ok(None);
+            }
+
+            sleep(self.interval.min(timeout - elapsed));
+        }
+
+        Ok(self.recall(idx))
+    }
+
+    /// Consume self, returning the lines read so far, and the source.
+    pub fn consume(self) -> (Vec<String>, B) {
+        (self.lines, self.reader)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write, path::PathBuf, thread};
+    use super::*;
+
+    #[test]
+    fn test_follow() {
+        let dir: PathBuf = std::env::temp_dir()
+            .join(format!("memoiter-follow-test-{}", std::process::id()));
+        let path: PathBuf = dir.join("follow.log");
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut file: File = File::create(&path).unwrap();
+        write!(file, "a\r\nb\nc").unwrap();
+
+        let mut lines = FollowLines::open(&path).unwrap();
+        lines.set_interval(Duration::from_millis(5));
+
+        assert_eq!(lines.poll().unwrap(), 2);
+        assert_eq!(lines.as_slice(), ["a", "b"]);
+        assert_eq!(lines.wait_for(2, Duration::from_millis(20)).unwrap(), None);
+
+        let writer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(30));
+            writeln!(file, "d\ne").unwrap();
+        });
+
+        assert_eq!(lines.wait_for(3, Duration::from_secs(5)).unwrap(), Some("e"));
+        assert_eq!(lines.recall(2), Some("cd"));
+        assert_eq!(lines.pending(), "");
+        writer.join().unwrap();
+
+        //  A character split between two writes is held back, not lost.
+        let mut file: File = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"f\xC3").unwrap();
+        assert_eq!(lines.poll().unwrap(), 0);
+        assert_eq!(lines.pending(), "f");
+
+        file.write_all(b"\xA9\n").unwrap();
+        assert_eq!(lines.poll().unwrap(), 1);
+        assert_eq!(lines.recall(4), Some("f\u{e9}"));
+
+        //  A complete line which is not UTF-8 is an error, and keeps its index.
+        file.write_all(b"\xFF\ng\n").unwrap();
+        assert_eq!(lines.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(lines.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(lines.evaluated(), 5);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
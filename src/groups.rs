@@ -0,0 +1,274 @@
+//! Lazy grouping of the items of an Iterator by key.
+
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    hash::Hash,
+};
+
+
+/// A Memoized Iterator which sorts its items into groups, according to a key
+///     function. A group is requested by its key, and the Iterator is consumed
+///     only until an item with that key is found, with every item seen along
+///     the way stored in its own group.
+///
+/// Because the rest of the Iterator has not been seen, a group may grow after
+///     it has been returned. To gather more of a group at once, a lookahead may
+///     be set: once an item of the requested group is found, consumption
+///     continues until that many items in a row belong to other groups. A
+///     lookahead of one gathers a whole run of items with the same key, which
+///     is a complete group if the source is sorted by key. The complete group
+///     can always be had, at the cost of consuming the whole Iterator, with
+///     `MemoGroups::group_complete()`.
+///
+/// Once a lookahead has run for a group, it is not run again for that group
+///     until another of its items has been found, so that requesting the same
+///     group repeatedly does not consume the Iterator any further.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::groups::MemoGroups;
+///
+/// let words = vec!["apple", "avocado", "banana", "blueberry", "cherry", "apricot"];
+/// let mut groups = MemoGroups::new(words.into_iter(), |w: &&str| w.chars().next().unwrap());
+///
+/// assert_eq!(groups.group(&'a'), Some(&["apple"][..]));
+/// assert_eq!(groups.evaluated(), 1);
+///
+/// let mut groups = groups.with_lookahead(1);
+/// assert_eq!(groups.group(&'b'), Some(&["banana", "blueberry"][..]));
+/// assert_eq!(groups.evaluated(), 5);
+///
+/// assert_eq!(groups.recall_group(&'a'), Some(&["apple", "avocado"][..]));
+/// assert_eq!(groups.group_complete(&'a'), Some(&["apple", "avocado", "apricot"][..]));
+/// assert_eq!(groups.group(&'z'), None);
+/// ```
+pub struct MemoGroups<I, T, K, F> where
+    I: Iterator<Item=T>,
+{
+    evaluated: usize,
+    exhausted: bool,
+    groups: HashMap<K, Group<T>>,
+    iterator: I,
+    key_fn: F,
+    lookahead: usize,
+}
+
+
+/// The items of one group, and the number there were when the lookahead last
+///     finished for it.
+struct Group<T> {
+    gathered: Option<usize>,
+    items: Vec<T>,
+}
+
+
+impl<T> Default for Group<T> {
+    fn default() -> Self {
+        Self { gathered: None, items: Vec::new() }
+    }
+}
+
+
+/// A group is shown as its items alone.
+impl<T: Debug> Debug for Group<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.items.fmt(f)
+    }
+}
+
+
+impl<I, T, K, F> MemoGroups<I, T, K, F> where
+    I: Iterator<Item=T>,
+    K: Eq + Hash,
+    F: FnMut(&T) -> K,
+{
+    /// Create an empty `MemoGroups` wrapping a given Iterator, grouping its
+    ///     items by the keys returned from `key_fn`. The lookahead is zero.
+    pub fn new(iterator: I, key_fn: F) -> Self {
+        Self {
+            evaluated: 0,
+            exhausted: false,
+            groups: HashMap::new(),
+            iterator,
+            key_fn,
+            lookahead: 0,
+        }
+    }
+
+    /// Set the number of items in a row outside a requested group after which
+    ///     to stop consuming, returning self.
+    pub fn with_lookahead(mut self, lookahead: usize) -> Self {
+        self.lookahead = lookahead;
+
+        for group in self.groups.values_mut() {
+            group.gathered = None;
+        }
+
+        self
+    }
+
+    /// Return the number of items in a row outside a requested group after
+    ///     which to stop consuming.
+    #[inline]
+    pub fn lookahead(&self) -> usize {
+        self.lookahead
+    }
+
+    /// Return the number of items taken from the Iterator.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.evaluated
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted, and every
+    ///     group is therefore complete.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Take one item from the Iterator and store it in its group. Returns
+    ///     whether it belongs to the group of `key`, or `None` if the Iterator
+    ///     has ended.
+    fn evaluate_next<Q>(&mut self, key: &Q) -> Option<bool> where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        match self.iterator.next() {
+            Some(item) => {
+                let item_key: K = (self.key_fn)(&item);
+                let hit: bool = item_key.borrow() == key;
+
+                self.evaluated += 1;
+                self.groups.entry(item_key).or_default().items.push(item);
+                Some(hit)
+            }
+            None => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+
+    /// Retrieve the items of a group seen so far, consuming the Iterator until
+    ///     the group has at least one item, and then according to the
+    ///     lookahead, unless it has already run since the last item of the
+    ///     group was found. Returns `None` if the Iterator ends without any
+    ///     item in the group.
+    pub fn group<Q>(&mut self, key: &Q) -> Option<&[T]> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        while !self.exhausted && !self.groups.contains_key(key) {
+            self.evaluate_next(key);
+        }
+
+        let gathered: bool = match self.groups.get(key) {
+            Some(group) => group.gathered == Some(group.items.len()),
+            None => true,
+        };
+
+        if !gathered {
+            let mut misses: usize = 0;
+
+            while misses < self.lookahead {
+                match self.evaluate_next(key) {
+                    Some(true) => misses = 0,
+                    Some(false) => misses += 1,
+                    None => break,
+                }
+            }
+
+            if let Some(group) = self.groups.get_mut(key) {
+                group.gathered = Some(group.items.len());
+            }
+        }
+
+        self.recall_group(key)
+    }
+
+    /// Retrieve every item of a group, consuming the Iterator until it ends.
+    ///     Returns `None` if the group has no items.
+    ///
+    /// This method will not return if the Iterator is infinite.
+    pub fn group_complete<Q>(&mut self, key: &Q) -> Option<&[T]> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        while self.evaluate_next(key).is_some() {}
+        self.recall_group(key)
+    }
+
+    /// Retrieve the items of a group seen so far, without consuming the
+    ///     Iterator. Returns `None` if the group has no items yet.
+    #[inline]
+    pub fn recall_group<Q>(&self, key: &Q) -> Option<&[T]> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.groups.get(key).map(|group| group.items.as_slice())
+    }
+
+    /// Return an Iterator over the keys of the groups seen so far, in no
+    ///     particular order.
+    pub fn keys(&self) -> impl Iterator<Item=&K> + '_ {
+        self.groups.keys()
+    }
+
+    /// Consume self, returning the groups seen so far, and the internal
+    ///     Iterator.
+    #[inline]
+    pub fn consume(self) -> (HashMap<K, Vec<T>>, I) {
+        let groups: HashMap<K, Vec<T>> = self.groups.into_iter()
+            .map(|(key, group)| (key, group.items))
+            .collect();
+
+        (groups, self.iterator)
+    }
+}
+
+
+impl<I, T, K, F> Debug for MemoGroups<I, T, K, F> where
+    I: Debug + Iterator<Item=T>,
+    T: Debug,
+    K: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoGroups")
+            .field("evaluated", &self.evaluated)
+            .field("exhausted", &self.exhausted)
+            .field("groups", &self.groups)
+            .field("iterator", &self.iterator)
+            .field("lookahead", &self.lookahead)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_sorted_runs() {
+        let source = CountingIter::new((0..60).map(|n| n / 10));
+        let counter = source.counter();
+        let mut groups = MemoGroups::new(source, |&n: &i32| n).with_lookahead(1);
+
+        assert_eq!(groups.group(&2).map(<[i32]>::len), Some(10));
+        assert_eq!(counter.times_advanced(), 31);
+
+        //  The lookahead has already run, and is not repeated.
+        assert_eq!(groups.group(&2).map(<[i32]>::len), Some(10));
+        assert_eq!(counter.times_advanced(), 31);
+
+        assert_eq!(groups.recall_group(&3), Some(&[3][..]));
+        assert_eq!(groups.group(&6), None);
+        assert!(groups.is_exhausted());
+        assert_eq!(groups.keys().count(), 6);
+    }
+}
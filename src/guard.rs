@@ -0,0 +1,175 @@
+//! A guard against runaway evaluation in debug builds.
+//!
+//! Passing an unbounded index to `MemoIter::get()` on an infinite Iterator will
+//!     evaluate and store items until memory runs out. In debug builds, every
+//!     access which would evaluate new items is first checked against global
+//!     limits, so that such a mistake is reported when it happens, rather than
+//!     as an allocation failure later. In release builds, nothing is checked.
+//!
+//! The limits are disabled by default. They can be set with `set_limits()`, or
+//!     with environment variables, which are read the first time they are
+//!     needed:
+//!
+//! `MEMOITER_GUARD_EVALUATION`: The most items that one access may evaluate.
+//!
+//! `MEMOITER_GUARD_CACHE`: The most items that one `MemoIter` may store.
+//!
+//! `MEMOITER_GUARD_ACTION`: Either `panic`, the default, or `warn`, to print a
+//!     warning to standard error and continue.
+//!
+//! Only accesses by index are checked, such as `get()` and `get_slice()` with a
+//!     bounded end. `MemoIter::exhaust()` cannot know in advance how many items
+//!     it will evaluate, and is not checked.
+
+use std::{
+    env,
+    str::FromStr,
+    sync::{Once, RwLock},
+};
+
+
+/// What to do when a limit is exceeded.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GuardAction {
+    /// Panic, before evaluating anything.
+    Panic,
+    /// Print a warning to standard error, and then evaluate as requested.
+    Warn,
+}
+
+
+/// Limits on the evaluation done by a `MemoIter`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GuardLimits {
+    /// The most items that a single access may evaluate.
+    pub max_evaluation: Option<usize>,
+    /// The most items that a single `MemoIter` may store.
+    pub max_cache: Option<usize>,
+    /// What to do when a limit would be exceeded.
+    pub action: GuardAction,
+}
+
+
+impl GuardLimits {
+    /// Read limits from the environment variables described in the module
+    ///     documentation. Returns `None` if neither limit is set.
+    pub fn from_env() -> Option<Self> {
+        fn var<T: FromStr>(key: &str) -> Option<T> {
+            env::var(key).ok()?.trim().parse().ok()
+        }
+
+        let max_evaluation: Option<usize> = var("MEMOITER_GUARD_EVALUATION");
+        let max_cache: Option<usize> = var("MEMOITER_GUARD_CACHE");
+
+        let action: GuardAction = match env::var("MEMOITER_GUARD_ACTION") {
+            Ok(action) if action.trim().eq_ignore_ascii_case("warn") => GuardAction::Warn,
+            _ => GuardAction::Panic,
+        };
+
+        if max_evaluation.is_some() || max_cache.is_some() {
+            Some(Self { max_evaluation, max_cache, action })
+        } else {
+            None
+        }
+    }
+
+    /// Describe the limit that would be exceeded by evaluating a sequence of
+    ///     `len` items until it contains the item at `idx`, if any would be. If
+    ///     the item at `idx` is already stored, nothing is evaluated, and no
+    ///     limit can be exceeded.
+    pub fn violation(&self, len: usize, idx: usize) -> Option<String> {
+        if idx < len {
+            return None;
+        }
+
+        let wanted: usize = (idx - len).saturating_add(1);
+
+        if let Some(max) = self.max_evaluation {
+            if wanted > max {
+                return Some(format!(
+                    "evaluating index {} would evaluate {} items, more than the limit of {}",
+                    idx, wanted, max,
+                ));
+            }
+        }
+
+        if let Some(max) = self.max_cache {
+            if idx >= max {
+                return Some(format!(
+                    "evaluating index {} would store {} items, more than the limit of {}",
+                    idx, idx.saturating_add(1), max,
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+
+static FROM_ENV: Once = Once::new();
+static LIMITS: RwLock<Option<GuardLimits>> = RwLock::new(None);
+
+
+fn init() {
+    FROM_ENV.call_once(|| {
+        if let Some(limits) = GuardLimits::from_env() {
+            *LIMITS.write().unwrap_or_else(|e| e.into_inner()) = Some(limits);
+        }
+    });
+}
+
+
+/// Return the limits currently in effect, if any.
+pub fn limits() -> Option<GuardLimits> {
+    init();
+    *LIMITS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+
+/// Replace the limits in effect for every `MemoIter`, overriding any set by
+///     environment variables. Limits of `None` disable the guard.
+pub fn set_limits(limits: Option<GuardLimits>) {
+    init();
+    *LIMITS.write().unwrap_or_else(|e| e.into_inner()) = limits;
+}
+
+
+/// Check an evaluation of a sequence of `len` items until it contains the
+///     item at `idx` against the limits in effect.
+#[cfg(debug_assertions)]
+pub(crate) fn check(len: usize, idx: usize) {
+    if let Some(limits) = limits() {
+        if let Some(violation) = limits.violation(len, idx) {
+            match limits.action {
+                GuardAction::Panic => panic!("memoiter: {}", violation),
+                GuardAction::Warn => eprintln!("memoiter: warning: {}", violation),
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violation() {
+        let limits = GuardLimits {
+            max_evaluation: Some(100),
+            max_cache: Some(1000),
+            action: GuardAction::Panic,
+        };
+
+        assert_eq!(limits.violation(0, 99), None);
+        assert!(limits.violation(0, 100).unwrap().contains("101 items"));
+        assert_eq!(limits.violation(950, 999), None);
+        assert!(limits.violation(990, 1000).unwrap().contains("1001 items"));
+        assert!(limits.violation(0, usize::MAX).is_some());
+        assert_eq!(limits.violation(2000, 10), None);
+
+        let unlimited = GuardLimits { max_evaluation: None, max_cache: None, ..limits };
+        assert_eq!(unlimited.violation(0, usize::MAX), None);
+    }
+}
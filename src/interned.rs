@@ -0,0 +1,171 @@
+//! Memoization which stores each distinct value only once.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::Arc,
+};
+
+
+/// A Memoized Iterator which interns its values. Each distinct value is stored
+///     once in a table, and the sequence itself holds only indices into that
+///     table, so the memory used grows with the number of distinct values
+///     rather than with the length of the sequence.
+///
+/// This is worthwhile for sequences which repeat large values often, such as
+///     strings or parsed records. Each value is compared against the table
+///     when it is evaluated, so it must implement `Hash` and `Eq`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::interned::InternedMemoIter;
+///
+/// let words = "the cat sat on the mat by the cat".split(' ').map(String::from);
+/// let mut memo = InternedMemoIter::new(words);
+///
+/// assert_eq!(memo.get(4).map(String::as_str), Some("the"));
+/// assert_eq!(memo.get(8).map(String::as_str), Some("cat"));
+///
+/// assert_eq!(memo.evaluated(), 9);
+/// assert_eq!(memo.distinct(), 6);
+/// assert_eq!(memo.id_of(0), memo.id_of(7));
+/// ```
+#[derive(Debug)]
+pub struct InternedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    exhausted: bool,
+    ids: Vec<usize>,
+    iterator: I,
+    lookup: HashMap<Arc<T>, usize>,
+    table: Vec<Arc<T>>,
+}
+
+
+impl<I, T> InternedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Eq + Hash,
+{
+    /// Create an empty `InternedMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            exhausted: false,
+            ids: Vec::new(),
+            iterator,
+            lookup: HashMap::new(),
+            table: Vec::new(),
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Return the number of distinct values evaluated.
+    #[inline]
+    pub fn distinct(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.ids.len() <= idx {
+            match self.iterator.next() {
+                Some(item) => {
+                    let id: usize = match self.lookup.get(&item) {
+                        Some(&id) => id,
+                        None => {
+                            let id: usize = self.table.len();
+                            let item: Arc<T> = Arc::new(item);
+
+                            self.table.push(item.clone());
+                            self.lookup.insert(item, id);
+                            id
+                        }
+                    };
+
+                    self.ids.push(id);
+                }
+                None => {
+                    self.exhausted = true;
+                    self.ids.shrink_to_fit();
+                }
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        let id: usize = *self.ids.get(idx)?;
+        Some(&self.table[id])
+    }
+
+    /// Return the interned ID of the value at an index, if it has been
+    ///     evaluated. Two indices have the same ID exactly when their values
+    ///     are equal. IDs are assigned in order of first appearance.
+    #[inline]
+    pub fn id_of(&self, idx: usize) -> Option<usize> {
+        self.ids.get(idx).copied()
+    }
+
+    /// Return the distinct value with a given interned ID.
+    #[inline]
+    pub fn value(&self, id: usize) -> Option<&T> {
+        self.table.get(id).map(Arc::as_ref)
+    }
+
+    /// Return an Iterator over references to the values evaluated so far.
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.ids.iter().map(move |&id| &*self.table[id])
+    }
+
+    /// Consume self, returning a Vector of shared handles to the values
+    ///     evaluated so far, and the internal Iterator. Repeated values share
+    ///     the same allocation.
+    pub fn consume(self) -> (Vec<Arc<T>>, I) {
+        let Self { ids, iterator, table, .. } = self;
+        let values: Vec<Arc<T>> = ids.into_iter().map(|id| table[id].clone()).collect();
+
+        (values, iterator)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning() {
+        let source = (0..1000).map(|n| vec![n % 3; 100]);
+        let mut memo = InternedMemoIter::new(source);
+
+        assert_eq!(memo.get(999).map(Vec::len), Some(100));
+        assert_eq!(memo.get(1000), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.distinct(), 3);
+        assert_eq!(memo.value(2), Some(&vec![2; 100]));
+        assert_eq!(memo.iter().filter(|v| v[0] == 1).count(), 333);
+
+        let (values, _) = memo.consume();
+        assert!(Arc::ptr_eq(&values[1], &values[997]));
+        assert_eq!(Arc::strong_count(&values[0]), 334);
+    }
+}
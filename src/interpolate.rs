@@ -0,0 +1,125 @@
+//! Interpolation between the items of a numeric `MemoIter`, treating them as
+//!     samples of a continuous function.
+
+use crate::MemoIter;
+
+
+/// Split a position into the index before it and the fraction of the way to
+///     the next index. Returns `None` if the position is negative or NaN.
+fn split_position(x: f64) -> Option<(usize, f64)> {
+    if x.is_nan() || x < 0.0 {
+        None
+    } else {
+        Some((x.floor() as usize, x - x.floor()))
+    }
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Copy + Into<f64>,
+{
+    fn sample_at(&mut self, idx: usize) -> Option<f64> {
+        self.get(idx).map(|&item| item.into())
+    }
+
+    /// Return the value at a fractional position `x`, interpolated linearly
+    ///     between the items at the indices on either side of it. An integer
+    ///     position returns its item exactly. Items are evaluated as needed.
+    ///
+    /// Returns `None` if `x` is negative or NaN, or if the sequence ends before
+    ///     the items needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut squares = MemoIter::new((0..).map(|n: u32| n * n));
+    ///
+    /// assert_eq!(squares.sample(2.0), Some(4.0));
+    /// assert_eq!(squares.sample(2.5), Some(6.5));
+    /// assert_eq!(squares.sample(-0.5), None);
+    /// ```
+    pub fn sample(&mut self, x: f64) -> Option<f64> {
+        let (idx, t) = split_position(x)?;
+
+        if t == 0.0 {
+            return self.sample_at(idx);
+        }
+
+        let b: f64 = self.sample_at(idx.saturating_add(1))?;
+        let a: f64 = self.sample_at(idx)?;
+
+        Some(a + (b - a) * t)
+    }
+
+    /// Return the value at a fractional position `x`, interpolated with a
+    ///     Catmull-Rom cubic spline through the two items on either side of it.
+    ///     This is smoother than `sample()`, at the cost of needing one more
+    ///     item on each side. An integer position returns its item exactly.
+    ///
+    /// At the start of the sequence, and at the end of a finite sequence, the
+    ///     item missing from the outside is taken to be equal to its neighbour.
+    ///     Returns `None` if `x` is negative or NaN, or if the sequence ends
+    ///     before the items on either side of `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut cubes = MemoIter::new((0..).map(|n: i32| n * n * n));
+    ///
+    /// //  Here the spline finds 3.5³ exactly, where a straight line cannot.
+    /// assert_eq!(cubes.sample_cubic(3.5), Some(42.875));
+    /// assert_eq!(cubes.sample(3.5), Some(45.5));
+    /// ```
+    pub fn sample_cubic(&mut self, x: f64) -> Option<f64> {
+        let (idx, t) = split_position(x)?;
+
+        if t == 0.0 {
+            return self.sample_at(idx);
+        }
+
+        let after: Option<f64> = self.sample_at(idx.saturating_add(2));
+        let p2: f64 = self.sample_at(idx.saturating_add(1))?;
+        let p1: f64 = self.sample_at(idx)?;
+        let p0: f64 = match idx.checked_sub(1) {
+            Some(before) => self.sample_at(before)?,
+            None => p1,
+        };
+        let p3: f64 = after.unwrap_or(p2);
+
+        Some(0.5 * (
+            2.0 * p1
+                + (p2 - p0) * t
+                + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+                + (3.0 * (p1 - p2) + p3 - p0) * t * t * t
+        ))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let mut memo = MemoIter::new(vec![0.0f32, 1.0, 4.0, 2.0].into_iter());
+
+        assert_eq!(memo.sample(0.25), Some(0.25));
+        assert_eq!(memo.evaluated(), 2);
+        assert_eq!(memo.sample(2.5), Some(3.0));
+        assert_eq!(memo.sample(3.0), Some(2.0));
+        assert_eq!(memo.sample(3.5), None);
+        assert_eq!(memo.sample(f64::NAN), None);
+
+        //  Ends are clamped, so the first segment is a spline through 0, 0, 1, 4.
+        assert_eq!(memo.sample_cubic(0.5), Some(0.3125));
+        assert_eq!(memo.sample_cubic(2.0), Some(4.0));
+        assert!(memo.sample_cubic(2.5).unwrap() > 3.0);
+        assert_eq!(memo.sample_cubic(3.5), None);
+    }
+}
@@ -9,14 +9,220 @@
 //!     999, 998, and so on. If these are stored, they can be retrieved later,
 //!     without needing to be recalculated for their own sake.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(iter_advance_by))]
+
+#[cfg(test)]
+extern crate self as memoiter;
+
+#[cfg(feature = "std")]
 use std::{
-    collections::Bound,
-    iter::FusedIterator,
+    collections::{BTreeMap, BTreeSet, Bound, TryReserveError},
+    convert::{TryFrom, TryInto},
+    fmt::{self, Debug, Formatter},
+    io,
+    iter::{Cloned, Copied, empty, Empty, FromIterator, FusedIterator, successors, Successors},
     ops::{Deref, RangeBounds},
+    panic::{AssertUnwindSafe, RefUnwindSafe, UnwindSafe},
     slice::SliceIndex,
+    sync::mpsc::{channel, Receiver},
 };
 
 
+#[cfg(feature = "std")]
+pub mod adaptive;
+#[cfg(feature = "std")]
+pub mod aligned;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "std")]
+pub mod baked;
+#[cfg(feature = "std")]
+pub mod bits;
+#[cfg(feature = "std")]
+pub mod brand;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+#[cfg(feature = "std")]
+pub mod channel;
+#[cfg(feature = "std")]
+pub mod checked;
+#[cfg(feature = "std")]
+pub mod chunk;
+#[cfg(feature = "std")]
+pub mod companion;
+#[cfg(feature = "std")]
+mod compare;
+#[cfg(feature = "std")]
+pub mod decimate;
+#[cfg(feature = "std")]
+pub mod entry;
+#[cfg(all(feature = "defmt", any(feature = "std", feature = "heapless")))]
+mod defmt;
+#[cfg(feature = "heapless")]
+pub mod fixed;
+#[cfg(feature = "std")]
+pub mod follow;
+#[cfg(feature = "std")]
+pub mod groups;
+#[cfg(feature = "std")]
+pub mod guard;
+#[cfg(feature = "std")]
+pub mod interned;
+#[cfg(feature = "std")]
+mod interpolate;
+#[cfg(feature = "std")]
+pub mod local;
+#[cfg(feature = "std")]
+mod lru;
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod macro_support;
+#[cfg(feature = "std")]
+pub mod map;
+#[cfg(feature = "std")]
+pub mod merge;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(all(feature = "bytemuck", feature = "memmap2"))]
+pub mod mmap;
+#[cfg(feature = "std")]
+pub mod paged;
+#[cfg(feature = "std")]
+pub mod periodic;
+#[cfg(feature = "std")]
+pub mod pointwise;
+#[cfg(feature = "std")]
+pub mod policy;
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "std")]
+mod prefetch;
+#[cfg(feature = "std")]
+pub mod product;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "std")]
+pub mod samples;
+#[cfg(feature = "std")]
+pub mod segmented;
+#[cfg(feature = "std")]
+pub mod sequences;
+#[cfg(feature = "std")]
+pub mod series;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod soa;
+#[cfg(feature = "std")]
+pub mod sparse;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(all(feature = "tokio", feature = "futures-core"))]
+pub mod stream;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "std")]
+pub mod record;
+#[cfg(feature = "regex-automata")]
+pub mod regex;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod text;
+#[cfg(feature = "std")]
+pub mod timed;
+#[cfg(feature = "std")]
+pub mod timeout;
+#[cfg(feature = "std")]
+pub mod typed;
+#[cfg(feature = "std")]
+mod unfold;
+#[cfg(feature = "std")]
+pub mod validate;
+#[cfg(feature = "std")]
+pub mod wal;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Memoize a function of a single `usize` index, so that each result is only
+///     computed once per thread. Requires the `macros` feature.
+///
+/// The annotated function is rewritten to store its results in a thread-local
+///     memo. When it is called with an index whose result is not yet stored,
+///     the original function is run for that index and for every lower index
+///     not yet stored, in ascending order. A recursive definition therefore
+///     finds every lower result already stored when it asks for one, and runs
+///     in linear time.
+///
+/// The function must take exactly one parameter, of type `usize`, and must not
+///     be generic, `async`, `const` or `unsafe`. Its return type must be
+///     `Clone + 'static`, because results are returned as clones of the
+///     stored values. The function should be pure: Its result for an index is
+///     stored and never recomputed, even if it depends on something else. If
+///     it calls itself, it must only do so with lower indices; a call with a
+///     higher index would need the result being computed, and never return.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::memoized;
+///
+/// #[memoized]
+/// fn fib(n: usize) -> u64 {
+///     match n {
+///         0 | 1 => n as u64,
+///         n => fib(n - 1) + fib(n - 2),
+///     }
+/// }
+///
+/// assert_eq!(fib(10), 55);
+/// assert_eq!(fib(90), 2880067194370816120);
+/// ```
+#[cfg(feature = "macros")]
+pub use memoiter_macros::memoized;
+#[cfg(feature = "metrics")]
+pub use metrics::describe_metrics;
+#[cfg(feature = "std")]
+pub use policy::{CancelToken, EvalPolicy};
+#[cfg(feature = "std")]
+pub use unfold::Unfold;
+#[cfg(feature = "std")]
+use checked::AccessError;
+#[cfg(feature = "std")]
+use record::Recorder;
+#[cfg(feature = "std")]
+use validate::Divergence;
+
+
+/// A callback run after each evaluation, which returns `false` when it should
+///     be removed.
+#[cfg(feature = "std")]
+type Observer<T> = Box<dyn FnMut(usize, &T) -> bool + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+
+/// A sink receiving every newly evaluated item.
+#[cfg(feature = "std")]
+type Mirror<T> = Box<dyn Recorder<T> + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+
+/// A policy consulted before each evaluation.
+#[cfg(feature = "std")]
+type Policy = Box<dyn EvalPolicy + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+
+/// A function cloning an Iterator, stored so that checkpoints may be taken
+///     without requiring `Clone` everywhere.
+#[cfg(feature = "std")]
+type Cloner<I> = fn(&I) -> I;
+
+
 /// A Memoized Iterator. Wraps an Iterator, associating it with a Vector to
 ///     store its returns. Past returns can then be retrieved by index.
 ///
@@ -46,169 +252,1386 @@ use std::{
 /// let (seq, _) = fibonacci.consume();
 /// assert_eq!(seq, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
 /// ```
-#[derive(Debug)]
+#[cfg(feature = "std")]
 pub struct MemoIter<I, T> where
     I: Iterator<Item=T>,
 {
+    cancel: Option<CancelToken>,
+    checkpoint_every: Option<(usize, Cloner<I>)>,
+    checkpoints: BTreeMap<usize, I>,
+    cursor: Option<usize>,
     exhausted: bool,
+    fused: bool,
+    halted: bool,
+    hints: BTreeSet<usize>,
     iterator: I,
+    mirror: Option<Mirror<T>>,
+    //  NOTE: An `io::Error` may hold any error type, which would otherwise make
+    //      every `MemoIter` lose `UnwindSafe`. It is only ever read, so no
+    //      broken invariant can be observed through it after a panic.
+    mirror_error: Option<AssertUnwindSafe<io::Error>>,
+    observers: Vec<Observer<T>>,
+    policy: Option<Policy>,
     sequence: Vec<T>,
+    skip: usize,
 }
 
 
+/// A type-erased `MemoIter`, wrapping any sendable Iterator of `T`. This allows
+///     `MemoIter`s over different sources to be stored in the same collection,
+///     or in a struct without generic parameters.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::DynMemoIter;
+///
+/// let mut memos: Vec<DynMemoIter<u32>> = vec![
+///     DynMemoIter::boxed(0..),
+///     DynMemoIter::boxed((1..).map(|n| n * n)),
+///     DynMemoIter::boxed(vec![7, 8, 9].into_iter()),
+/// ];
+///
+/// let thirds: Vec<Option<u32>> = memos.iter_mut()
+///     .map(|memo| memo.get(2).copied())
+///     .collect();
+///
+/// assert_eq!(thirds, [Some(2), Some(9), Some(9)]);
+/// ```
+#[cfg(feature = "std")]
+pub type DynMemoIter<T> = MemoIter<Box<dyn Iterator<Item=T> + Send>, T>;
+
+
+#[cfg(feature = "std")]
+impl<T> MemoIter<Box<dyn Iterator<Item=T> + Send>, T> {
+    /// Create an empty `DynMemoIter` wrapping a given Iterator.
+    pub fn boxed<I>(iterator: I) -> Self where
+        I: Iterator<Item=T> + Send + 'static,
+    {
+        Self::new(Box::new(iterator))
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<T, F> MemoIter<Successors<T, F>, T> where
+    F: FnMut(&T) -> Option<T>,
+{
+    /// Create an empty `MemoIter` over a sequence where each item is computed
+    ///     from the one before it, as with `std::iter::successors()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut powers = MemoIter::successors(Some(1u32), |&n| n.checked_mul(3));
+    ///
+    /// assert_eq!(powers.get(4), Some(&81));
+    /// assert_eq!(powers.get(20), Some(&3486784401));
+    /// assert_eq!(powers.get(21), None);
+    /// ```
+    pub fn successors(first: Option<T>, succ: F) -> Self {
+        Self::new(successors(first, succ))
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<S, F, T> MemoIter<Unfold<S, F>, T> where
+    F: FnMut(&mut S) -> Option<T>,
+{
+    /// Create an empty `MemoIter` over the values returned by a closure, which
+    ///     is given mutable access to a state value on each call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// //  The Collatz sequence, starting from 27.
+    /// let mut collatz = MemoIter::unfold(Some(27u32), |state| {
+    ///     let n: u32 = (*state)?;
+    ///
+    ///     *state = match n {
+    ///         1 => None,
+    ///         n if n % 2 == 0 => Some(n / 2),
+    ///         n => Some(3 * n + 1),
+    ///     };
+    ///
+    ///     Some(n)
+    /// });
+    ///
+    /// assert_eq!(collatz.get_slice(..5), [27, 82, 41, 124, 62]);
+    /// assert_eq!(collatz.exhaust().len(), 112);
+    /// assert_eq!(collatz.iter().max(), Some(&9232));
+    /// ```
+    pub fn unfold(state: S, func: F) -> Self {
+        Self::new(Unfold::new(state, func))
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<'a, I, T> MemoIter<Copied<I>, T> where
+    I: Iterator<Item=&'a T>,
+    T: 'a + Copy,
+{
+    /// Create an empty `MemoIter` wrapping an Iterator over references, which
+    ///     stores copies of the items referred to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let primes: [u32; 5] = [2, 3, 5, 7, 11];
+    /// let mut memo = MemoIter::copied(primes.iter());
+    ///
+    /// assert_eq!(memo.get(3), Some(&7));
+    /// assert_eq!(memo.get_slice(..), [2, 3, 5, 7]);
+    /// ```
+    pub fn copied(iterator: I) -> Self {
+        Self::new(iterator.copied())
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<'a, I, T> MemoIter<Cloned<I>, T> where
+    I: Iterator<Item=&'a T>,
+    T: 'a + Clone,
+{
+    /// Create an empty `MemoIter` wrapping an Iterator over references, which
+    ///     stores clones of the items referred to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let words: Vec<String> = vec!["alpha".into(), "beta".into(), "gamma".into()];
+    /// let mut memo = MemoIter::cloned(words.iter().rev());
+    ///
+    /// assert_eq!(memo.get(1).map(String::as_str), Some("beta"));
+    /// assert_eq!(memo.evaluated(), 2);
+    ///
+    /// let (owned, _) = memo.consume();
+    /// assert_eq!(owned, ["gamma", "beta"]);
+    /// ```
+    pub fn cloned(iterator: I) -> Self {
+        Self::new(iterator.cloned())
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<T> MemoIter<Empty<T>, T> {
+    /// Create an exhausted `MemoIter` holding a fully computed Vector, with an
+    ///     empty source. This allows precomputed data to be given to code
+    ///     written for `MemoIter`s, without a placeholder Iterator.
+    ///
+    /// This cannot be a `From<Vec<T>>` implementation, because that conversion
+    ///     already produces a `MemoIter` which will evaluate the Vector lazily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::from_vec(vec![2, 3, 5, 7]);
+    ///
+    /// assert!(memo.is_exhausted());
+    /// assert_eq!(memo.get(3), Some(&7));
+    /// assert_eq!(memo.get(4), None);
+    /// ```
+    pub fn from_vec(sequence: Vec<T>) -> Self {
+        let mut memo = Self::with_vec(empty(), sequence);
+        memo.exhausted = true;
+        memo
+    }
+}
+
+
+#[cfg(feature = "std")]
 impl<I, T> MemoIter<I, T> where
     I: Iterator<Item=T>,
 {
     /// Create an empty `MemoIter` wrapping a given Iterator.
     pub fn new(iterator: I) -> Self {
-        Self {
-            exhausted: false,
-            iterator,
-            sequence: Vec::new(),
-        }
+        Self::with_vec(iterator, Vec::new())
     }
 
     /// Create an empty `MemoIter`, but with a specified capacity, wrapping a
     ///     given Iterator. This only affects the initial capacity, and does
     ///     **not** restrict the size of the internal vector.
     pub fn with_capacity(capacity: usize, iterator: I) -> Self {
+        Self::with_vec(iterator, Vec::with_capacity(capacity))
+    }
+
+    /// Create a `MemoIter` wrapping a given Iterator, using a provided Vector
+    ///     for its storage.
+    pub fn with_vec(iterator: I, sequence: Vec<T>) -> Self {
         Self {
+            cancel: None,
+            checkpoint_every: None,
+            checkpoints: BTreeMap::new(),
+            cursor: None,
             exhausted: false,
+            fused: true,
+            halted: false,
+            hints: BTreeSet::new(),
             iterator,
-            sequence: Vec::with_capacity(capacity),
+            mirror: None,
+            mirror_error: None,
+            observers: Vec::new(),
+            policy: None,
+            sequence,
+            skip: 0,
+        }
+    }
+
+    /// Immediately evaluate the first `n` items, returning self. This moves the
+    ///     cost of evaluation to the point of construction, which may be on a
+    ///     thread where latency is less important, so that later accesses to
+    ///     those items are only lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let table = MemoIter::new((0u64..).map(|n| n.pow(3))).eager(256);
+    ///
+    /// assert_eq!(table.evaluated(), 256);
+    /// assert_eq!(table[255], 16581375);
+    /// ```
+    pub fn eager(mut self, n: usize) -> Self {
+        if let Some(last) = n.checked_sub(1) {
+            self.expand_to_contain(last);
+        }
+
+        self
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the number of items evaluated. This value will be one more than
+    ///     the highest index available via `MemoIter::recall()`.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.sequence.len()
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        #[cfg(feature = "metrics")]
+        let probe = metrics::Probe::start(self.sequence.len(), idx);
+
+        if !self.exhausted {
+            let len: usize = self.sequence.len();
+
+            if idx >= len && self.catch_up() {
+                let needed: usize = (idx - len).saturating_add(1);
+
+                #[cfg(debug_assertions)]
+                guard::check(len, idx);
+
+                #[cfg(feature = "defmt")]
+                defmt::trace_evaluation(len, idx);
+
+                if self.is_unchecked() {
+                    #[cfg(test)] println!("+ {}..={}", len, idx);
+
+                    self.sequence.extend(self.iterator.by_ref().take(needed));
+
+                    if self.sequence.len() <= idx {
+                        self.mark_exhausted();
+                    }
+                } else {
+                    //  NOTE: The request may be far beyond what the Iterator
+                    //      can produce, or what memory can hold, so this only
+                    //      reserves as much as the Iterator may return, and a
+                    //      failure is left for the Vector to handle as it grows.
+                    let upper: Option<usize> = self.iterator.size_hint().1;
+                    let _ = self.sequence.try_reserve(upper.map_or(needed, |upper| upper.min(needed)));
+
+                    for _i in len..=idx {
+                        #[cfg(test)] println!("+ {}", _i);
+
+                        if !self.evaluate_next() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        probe.finish(self.sequence.len());
+    }
+
+    /// Return `true` if there is nothing to be checked before or after each
+    ///     evaluation, allowing items to be evaluated in bulk.
+    #[inline]
+    fn is_unchecked(&self) -> bool {
+        self.policy.is_none()
+            && self.cancel.is_none()
+            && self.observers.is_empty()
+            && self.mirror.is_none()
+            && self.checkpoint_every.is_none()
+    }
+
+    /// Discard any items which the Iterator must produce again after being
+    ///     restored from a checkpoint, but which are still stored. Returns
+    ///     `false` if the Iterator ends first.
+    fn catch_up(&mut self) -> bool {
+        if self.skip > 0 {
+            if !self.fused {
+                //  NOTE: The items must be skipped one at a time, so that the
+                //      number still to be skipped is known if the Iterator
+                //      returns `None` and is tried again later.
+                while self.skip > 0 {
+                    if self.iterator.next().is_none() {
+                        return false;
+                    }
+
+                    self.skip -= 1;
+                }
+
+                return true;
+            }
+
+            let skip: usize = std::mem::take(&mut self.skip);
+
+            if self.iterator.nth(skip - 1).is_none() {
+                self.mark_exhausted();
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Record that the Iterator has returned `None`. Unless the `MemoIter` has
+    ///     been told that its Iterator is not fused, this is permanent.
+    fn mark_exhausted(&mut self) {
+        if self.fused {
+            self.exhausted = true;
+            self.sequence.shrink_to_fit();
+        }
+    }
+
+    /// Evaluate and store one more item from the Iterator, if the policy allows
+    ///     it. Returns `false` if no item was stored.
+    fn evaluate_next(&mut self) -> bool {
+        if let Some(token) = &self.cancel {
+            if token.is_cancelled() {
+                return false;
+            }
+        }
+
+        if let Some(policy) = &mut self.policy {
+            if policy.before_next(self.sequence.len()).is_break() {
+                return false;
+            }
+        }
+
+        if !self.catch_up() {
+            return false;
+        }
+
+        match self.iterator.next() {
+            Some(next) => {
+                self.store(next);
+                true
+            }
+            None => {
+                self.mark_exhausted();
+                false
+            }
+        }
+    }
+
+    /// Store one more item, passing it to any observers and mirror, and taking
+    ///     a checkpoint if one is due.
+    fn store(&mut self, next: T) {
+        self.sequence.push(next);
+
+        if !self.observers.is_empty() {
+            let idx: usize = self.sequence.len() - 1;
+            let item: &T = &self.sequence[idx];
+
+            //  NOTE: An observer returns `false` once its receiver is gone, and
+            //      is then removed.
+            self.observers.retain_mut(|observer| observer(idx, item));
+        }
+
+        if let Some(mirror) = &mut self.mirror {
+            let item: &T = &self.sequence[self.sequence.len() - 1];
+
+            if let Err(e) = mirror.record(item) {
+                self.mirror = None;
+                self.mirror_error = Some(AssertUnwindSafe(e));
+            }
+        }
+
+        //  NOTE: While items are still to be skipped, the Iterator is behind
+        //      the stored sequence, and cannot be used as a checkpoint.
+        if let (Some((every, clone)), 0) = (self.checkpoint_every, self.skip) {
+            let position: usize = self.sequence.len();
+
+            if position % every == 0 {
+                self.checkpoints.insert(position, clone(&self.iterator));
+            }
+        }
+    }
+
+    /// Evaluate the internal Iterator until it ends, returning a slice of the
+    ///     entire stored sequence.
+    ///
+    /// This method will not return if the Iterator is infinite, unless it is
+    ///     halted by a policy or a `CancelToken`.
+    pub fn exhaust(&mut self) -> &[T] {
+        if !self.exhausted {
+            if self.is_unchecked() {
+                if self.catch_up() {
+                    self.sequence.extend(self.iterator.by_ref());
+                }
+
+                self.mark_exhausted();
+            } else {
+                while self.evaluate_next() {}
+            }
         }
+
+        &self.sequence
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     at the index given has not yet been evaluated, it will be. Returns
+    ///     `None` if the internal Iterator terminates before reaching the given
+    ///     index.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        #[cfg(test)] println!("get({}):", idx);
+        self.expand_to_contain(idx);
+        self.sequence.get(idx)
+    }
+
+    /// Evaluate the Iterator until the value at an index is stored, as with
+    ///     `MemoIter::get()`, but growing the storage with `Vec::try_reserve()`
+    ///     so that running out of memory is returned as an error rather than
+    ///     aborting the process.
+    ///
+    /// Storage is reserved in steps, doubling its capacity at most, so that an
+    ///     index beyond the end of a finite Iterator does not fail merely by
+    ///     asking for too much. If an error is returned, every item evaluated
+    ///     before it is still stored.
+    pub fn try_expand(&mut self, idx: usize) -> Result<(), TryReserveError> {
+        while !self.exhausted && self.sequence.len() <= idx {
+            let len: usize = self.sequence.len();
+
+            if len == self.sequence.capacity() {
+                let wanted: usize = (idx - len).saturating_add(1);
+                self.sequence.try_reserve(wanted.min(len.max(8)))?;
+            }
+
+            let end: usize = idx.min(self.sequence.capacity() - 1);
+            self.expand_to_contain(end);
+
+            if self.sequence.len() <= end {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator, as with
+    ///     `MemoIter::get()`, but returning an error instead of aborting if
+    ///     memory for it cannot be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new(0..100u8);
+    ///
+    /// assert_eq!(memo.try_get(10), Ok(Some(&10)));
+    /// assert_eq!(memo.try_get(usize::MAX), Ok(None));
+    /// assert!(memo.is_exhausted());
+    /// ```
+    pub fn try_get(&mut self, idx: usize) -> Result<Option<&T>, TryReserveError> {
+        self.try_expand(idx)?;
+        Ok(self.sequence.get(idx))
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator, paired with
+    ///     the index itself. The value is evaluated if needed, as with
+    ///     `MemoIter::get()`.
+    #[inline]
+    pub fn get_entry(&mut self, idx: usize) -> Option<(usize, &T)> {
+        self.get(idx).map(|item| (idx, item))
+    }
+
+    /// Return an Iterator over the values evaluated so far, each paired with
+    ///     its index. Nothing new is evaluated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new("xyz".chars());
+    /// memo.get(1);
+    ///
+    /// let entries: Vec<(usize, &char)> = memo.entries().collect();
+    /// assert_eq!(entries, [(0, &'x'), (1, &'y')]);
+    /// assert_eq!(memo.get_entry(2), Some((2, &'z')));
+    /// ```
+    #[inline]
+    pub fn entries(&self) -> impl Iterator<Item=(usize, &T)> + '_ {
+        self.sequence.iter().enumerate()
+    }
+
+    /// Retrieve a slice of values returned by the Iterator. If the values in
+    ///     the range in question have not yet been evaluated, they will be.
+    ///
+    /// Retrieving a slice whose end bound has not yet been evaluated will cause
+    ///     all values up to that point to be evaluated. Because an Iterator may
+    ///     be infinite, an *unbounded* slice will, out of caution, **not** do
+    ///     any evaluations, instead being limited to the existing indices in
+    ///     the stored sequence. A convenient side effect of this is that a full
+    ///     range -- that is, `memiter.get_slice(..)` -- will return a full
+    ///     slice of the *stored* sequence, doing no new evaluations.
+    ///
+    /// However, because the final index may not be knowable, this method also
+    ///     includes a check to ensure that it will not panic if given a range
+    ///     with indices outside the final sequence, instead returning an empty
+    ///     slice.
+    ///
+    /// # Extreme Bounds
+    ///
+    /// Every range is clamped, rather than rejected. The part of a range past
+    ///     the end of the sequence is left out, and a range whose start is
+    ///     after its end is empty, although it is still evaluated up to its
+    ///     end. Bounds are never incremented past `usize::MAX`, so that
+    ///     `..=usize::MAX` reaches to the end of any sequence, and a range
+    ///     starting after `usize::MAX` is empty. An excluded end of zero
+    ///     evaluates nothing.
+    ///
+    /// To have any of these cases reported as an error instead, use
+    ///     `MemoIter::get_slice_checked()`.
+    pub fn get_slice<R>(&mut self, range: R) -> &[T] where
+        R: RangeBounds<usize> + SliceIndex<[T], Output=[T]>,
+    {
+        let first: usize = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.saturating_add(1),
+        };
+
+        match range.end_bound() {
+            Bound::Unbounded => {
+                let end: usize = self.sequence.len();
+
+                &self.sequence[first.min(end)..end]
+            }
+            Bound::Included(&i) => {
+                self.expand_to_contain(i);
+                let last: usize = self.sequence.len().saturating_sub(1).min(i);
+
+                if first <= last {
+                    &self.sequence[first..=last]
+                } else {
+                    //  NOTE: Edge case. Prevents `&[0, 1, 2, 3][10..=20]` from
+                    //      being evaluated as `&[3]`.
+                    &[]
+                }
+            }
+            Bound::Excluded(&i) => {
+                if let Some(last) = i.checked_sub(1) {
+                    self.expand_to_contain(last);
+                }
+
+                let end: usize = self.sequence.len().min(i);
+
+                &self.sequence[first.min(end)..end]
+            }
+        }
+    }
+
+    /// Retrieve a slice of values returned by the Iterator, as with
+    ///     `MemoIter::get_slice()`, but reporting ranges which cannot be
+    ///     returned in full as errors, instead of clamping them. A range
+    ///     without an end is still limited to the items already evaluated.
+    ///
+    /// Returns an error if the start of the range is after its end, if a bound
+    ///     cannot be represented, as in `..=usize::MAX`, or if the sequence ends
+    ///     before the end of the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, checked::AccessError};
+    ///
+    /// let mut memo = MemoIter::new(0..5);
+    ///
+    /// assert_eq!(memo.get_slice_checked(1..=3), Ok(&[1, 2, 3][..]));
+    /// assert_eq!(memo.get_slice_checked(..=usize::MAX), Err(AccessError::Overflow));
+    /// assert_eq!(memo.get_slice_checked(3..9), Err(AccessError::OutOfRange { index: 5, len: 5 }));
+    /// assert_eq!(memo.get_slice(3..9), [3, 4]);
+    /// ```
+    pub fn get_slice_checked<R>(&mut self, range: R) -> Result<&[T], AccessError> where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = checked::resolve(&range)?;
+        let len: usize = self.sequence.len();
+        let end: usize = match end {
+            Some(end) => end,
+            None if start > len => return Err(AccessError::OutOfRange { index: start, len }),
+            None => len,
+        };
+
+        if let Some(last) = end.checked_sub(1) {
+            self.expand_to_contain(last);
+        }
+
+        let len: usize = self.sequence.len();
+
+        match self.sequence.get(start..end) {
+            Some(slice) => Ok(slice),
+            None => Err(AccessError::OutOfRange { index: len.max(start), len }),
+        }
+    }
+
+    /// Retrieve a slice of up to `len` values returned by the Iterator,
+    ///     beginning at index `start`. If the values have not yet been
+    ///     evaluated, they will be.
+    ///
+    /// This is equivalent to `get_slice(start..start + len)`, but the end is
+    ///     computed with saturating arithmetic, so that it cannot overflow. As
+    ///     with `get_slice()`, the slice is shortened if the sequence ends
+    ///     before it does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((0..10).map(|n| n * n));
+    ///
+    /// assert_eq!(memo.get_slice_at(2, 3), [4, 9, 16]);
+    /// assert_eq!(memo.get_slice_at(8, 5), [64, 81]);
+    /// assert_eq!(memo.get_slice_at(5, usize::MAX), [25, 36, 49, 64, 81]);
+    /// ```
+    pub fn get_slice_at(&mut self, start: usize, len: usize) -> &[T] {
+        self.get_slice(start..start.saturating_add(len))
+    }
+
+    /// Retrieve a slice of exactly `len` values returned by the Iterator,
+    ///     beginning at index `start`, as with `MemoIter::get_slice_checked()`.
+    ///
+    /// Returns an error if `start + len` overflows, or if the sequence ends
+    ///     before the end of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, checked::AccessError};
+    ///
+    /// let mut memo = MemoIter::new(0..5);
+    ///
+    /// assert_eq!(memo.get_slice_at_checked(1, 3), Ok(&[1, 2, 3][..]));
+    /// assert_eq!(memo.get_slice_at_checked(3, 4), Err(AccessError::OutOfRange { index: 5, len: 5 }));
+    /// assert_eq!(memo.get_slice_at_checked(1, usize::MAX), Err(AccessError::Overflow));
+    /// ```
+    pub fn get_slice_at_checked(&mut self, start: usize, len: usize) -> Result<&[T], AccessError> {
+        let end: usize = start.checked_add(len).ok_or(AccessError::Overflow)?;
+        self.get_slice_checked(start..end)
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    ///
+    /// By default, the first `None` returned by the Iterator exhausts the
+    ///     `MemoIter`, and the Iterator is never advanced again. If it has been
+    ///     marked as not fused with `set_fused(false)`, this always returns
+    ///     `false`, because a `None` only means that no item is available yet.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     at the index given has not yet been evaluated, it will **NOT** be
+    ///     evaluated now, and this method will return `None`.
+    pub fn recall(&mut self, idx: usize) -> Option<&T> {
+        #[cfg(test)] println!("recall({})", idx);
+        self.sequence.get(idx)
+    }
+
+    /// Retrieve, by its index, a value which has already been evaluated,
+    ///     without a bounds check. Nothing is evaluated.
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be less than `MemoIter::evaluated()`. Calling this method
+    ///     with any other index is undefined behavior, even if the result is
+    ///     not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((0..).map(|n| n * 7));
+    /// memo.get(9);
+    ///
+    /// let total: i32 = (0..10).map(|i| unsafe { *memo.get_unchecked(i) }).sum();
+    /// assert_eq!(total, 315);
+    /// ```
+    #[inline]
+    pub unsafe fn get_unchecked(&self, idx: usize) -> &T {
+        debug_assert!(idx < self.sequence.len(), "index {} is not evaluated", idx);
+        self.sequence.get_unchecked(idx)
+    }
+
+    /// Retrieve a slice of values which have already been evaluated, without a
+    ///     bounds check. Nothing is evaluated.
+    ///
+    /// # Safety
+    ///
+    /// The range must lie within the evaluated items, and must not start
+    ///     after it ends, exactly as for `slice::get_unchecked()`. Calling this
+    ///     method with any other range is undefined behavior.
+    #[inline]
+    pub unsafe fn get_slice_unchecked<R>(&self, range: R) -> &[T] where
+        R: SliceIndex<[T], Output=[T]>,
+    {
+        self.sequence.get_unchecked(range)
+    }
+
+    /// Consume self, returning a Tuple containing the internal stored `Vec<T>`
+    ///     and the original Iterator.
+    pub fn consume(self) -> (Vec<T>, I) {
+        let Self { sequence, iterator, .. } = self;
+        (sequence, iterator)
+    }
+
+    /// Consume self, returning a new, empty `MemoIter` wrapping a different
+    ///     Iterator, which reuses the allocation of the stored sequence. This
+    ///     avoids reallocating when many short-lived `MemoIter`s are created
+    ///     in turn.
+    ///
+    /// Nothing else is carried over; the result is as though created by
+    ///     `MemoIter::with_vec()`, with no policy, `CancelToken`, checkpoints
+    ///     or subscriptions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new(0..1000);
+    /// memo.get(99);
+    ///
+    /// let memo = memo.recycle(vec![1, 2, 3].into_iter());
+    ///
+    /// assert_eq!(memo.evaluated(), 0);
+    /// assert!(memo.capacity() >= 100);
+    /// ```
+    pub fn recycle<J>(self, iterator: J) -> MemoIter<J, T> where
+        J: Iterator<Item=T>,
+    {
+        let mut sequence: Vec<T> = self.sequence;
+        sequence.clear();
+
+        MemoIter::with_vec(iterator, sequence)
+    }
+
+    /// Return the number of items which can be stored without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.sequence.capacity()
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Clone,
+{
+    /// Return an Iterator over the first `limit` values, each paired with its
+    ///     index, which evaluates values only as it reaches them. Values which
+    ///     are already stored are not evaluated again.
+    ///
+    /// Because evaluating a value may move the stored sequence, the values are
+    ///     cloned out, rather than borrowed. The Iterator ends early if the
+    ///     sequence does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((1..).map(|n| n * 10));
+    /// memo.get(1);
+    ///
+    /// let mut entries = memo.entries_evaluating(1000);
+    /// assert_eq!(entries.next(), Some((0, 10)));
+    /// assert_eq!(entries.nth(2), Some((3, 40)));
+    /// drop(entries);
+    ///
+    /// assert_eq!(memo.evaluated(), 4);
+    /// ```
+    pub fn entries_evaluating(&mut self, limit: usize) -> EntriesEvaluating<'_, I, T> {
+        EntriesEvaluating { limit, memo: self, next: 0 }
+    }
+
+    /// Retrieve a range of values returned by the Iterator, as an owned Vector.
+    ///     Values are evaluated as needed, exactly as with `get_slice()`, and
+    ///     then cloned out, so that the result does not borrow the `MemoIter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut squares = MemoIter::new((0..).map(|n| n * n));
+    /// let head: Vec<u32> = squares.get_slice_owned(..4);
+    ///
+    /// assert_eq!(squares.get(10), Some(&100));
+    /// assert_eq!(head, [0, 1, 4, 9]);
+    /// ```
+    pub fn get_slice_owned<R>(&mut self, range: R) -> Vec<T> where
+        R: RangeBounds<usize> + SliceIndex<[T], Output=[T]>,
+    {
+        self.get_slice(range).to_vec()
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: PartialEq,
+{
+    /// Merge a previously saved prefix of the same sequence into this memo,
+    ///     so that several processes computing the sequence can pool their
+    ///     progress. Where both have evaluated an item, they must agree; where
+    ///     the snapshot is longer, its extra items are stored, and the same
+    ///     number of items will be skipped from the Iterator when evaluation
+    ///     continues.
+    ///
+    /// Returns the number of items added. If the snapshot disagrees with an
+    ///     item already evaluated, or extends past the end of an exhausted
+    ///     sequence, returns the index where they diverge, and nothing is
+    ///     changed. Absorbed items are passed to any observers and mirror, as
+    ///     though they had been evaluated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, validate::Divergence};
+    ///
+    /// let mut memo = MemoIter::new((0u64..).map(|n| n * n));
+    /// memo.get(3);
+    ///
+    /// assert_eq!(memo.absorb(vec![0, 1, 4, 9, 16, 25]), Ok(2));
+    /// assert_eq!(memo.absorb(vec![0, 1, 5]), Err(Divergence { index: 2 }));
+    ///
+    /// assert_eq!(memo.evaluated(), 6);
+    /// assert_eq!(memo.get(6), Some(&36));
+    /// ```
+    pub fn absorb<S>(&mut self, snapshot: S) -> Result<usize, Divergence> where
+        S: IntoIterator<Item=T>,
+    {
+        let mut snapshot = snapshot.into_iter();
+        let len: usize = self.sequence.len();
+
+        for (index, known) in self.sequence.iter().enumerate() {
+            match snapshot.next() {
+                Some(item) if item == *known => {}
+                Some(_) => return Err(Divergence { index }),
+                None => return Ok(0),
+            }
+        }
+
+        let mut extra = snapshot.peekable();
+
+        if self.exhausted && extra.peek().is_some() {
+            return Err(Divergence { index: len });
+        }
+
+        //  NOTE: Each absorbed item is counted as skipped before it is stored,
+        //      so that no checkpoint is taken of an Iterator that has not yet
+        //      reached it.
+        for item in extra {
+            self.skip += 1;
+            self.store(item);
+        }
+
+        Ok(self.sequence.len() - len)
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<I, T> MemoIter<I, T> where
+    I: Clone + Iterator<Item=T>,
+{
+    /// Store a clone of the internal Iterator in its current state, so that
+    ///     evaluation may later be restarted from this point by
+    ///     `MemoIter::invalidate_from()`.
+    pub fn checkpoint(&mut self) {
+        let position: usize = self.sequence.len() - self.skip;
+        self.checkpoints.insert(position, self.iterator.clone());
+    }
+
+    /// Store a checkpoint now, and then automatically each time the number of
+    ///     items evaluated reaches a multiple of `every`, replacing any previous
+    ///     interval. Items are then evaluated one at a time, rather than in
+    ///     bulk.
+    ///
+    /// With periodic checkpoints, stored values may be released with
+    ///     `MemoIter::release_from()` and later re-derived, at the cost of
+    ///     evaluating at most `every` items more than the ones requested.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `every` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, testing::CountingIter};
+    ///
+    /// let source = CountingIter::new((0..).map(|n| n * n));
+    /// let counter = source.counter();
+    ///
+    /// let mut memo = MemoIter::new(source);
+    /// memo.set_checkpoint_interval(100);
+    /// assert_eq!(memo.get(999), Some(&998001));
+    /// assert_eq!(memo.checkpoints(), 11);
+    ///
+    /// //  Keep only the first 500 values, recomputing later ones on demand.
+    /// assert!(memo.release_from(500));
+    /// assert_eq!(memo.evaluated(), 500);
+    /// assert_eq!(memo.checkpoints(), 6);
+    ///
+    /// assert_eq!(memo.get(720), Some(&518400));
+    /// assert_eq!(counter.times_advanced(), 1000 + 221);
+    /// ```
+    pub fn set_checkpoint_interval(&mut self, every: usize) {
+        assert_ne!(every, 0, "checkpoint interval must be nonzero");
+
+        self.checkpoint();
+        self.checkpoint_every = Some((every, I::clone));
+    }
+
+    /// Stop storing checkpoints automatically. Checkpoints already stored are
+    ///     kept.
+    pub fn clear_checkpoint_interval(&mut self) {
+        self.checkpoint_every = None;
+    }
+
+    /// Discard every stored value from the given index onwards, as with
+    ///     `MemoIter::invalidate_from()`, and release the memory they used.
+    ///     This is intended for deterministic sources, whose values can be
+    ///     re-derived from the nearest checkpoint whenever they are needed
+    ///     again.
+    ///
+    /// Returns `false`, and changes nothing, if there is no checkpoint at or
+    ///     before the index.
+    pub fn release_from(&mut self, idx: usize) -> bool {
+        let released: bool = self.invalidate_from(idx);
+
+        if released {
+            self.sequence.shrink_to_fit();
+        }
+
+        released
+    }
+
+    /// Return the number of checkpoints stored.
+    #[inline]
+    pub fn checkpoints(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Discard every stored value from the given index onwards, so that they
+    ///     will be evaluated again on the next access. The internal Iterator
+    ///     is restored from the nearest checkpoint at or before the index, and
+    ///     the values between the checkpoint and the index are recomputed, but
+    ///     not replaced, when evaluation resumes. Any checkpoints after the
+    ///     index are discarded.
+    ///
+    /// This is useful when the source depends on some parameter which has
+    ///     changed, making its later values out of date.
+    ///
+    /// Returns `false`, and changes nothing, if there is no checkpoint at or
+    ///     before the index. Returns `true` if the index is beyond the stored
+    ///     values, as there is nothing to discard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    /// use std::sync::{Arc, atomic::{AtomicU32, Ordering}};
+    ///
+    /// let scale = Arc::new(AtomicU32::new(1));
+    /// let param = scale.clone();
+    /// let mut memo = MemoIter::new((0..).map(move |n| n * param.load(Ordering::Relaxed)));
+    ///
+    /// memo.checkpoint();
+    /// assert_eq!(memo.get_slice(..4), [0, 1, 2, 3]);
+    ///
+    /// scale.store(10, Ordering::Relaxed);
+    /// assert!(memo.invalidate_from(2));
+    /// assert_eq!(memo.get_slice(..4), [0, 1, 20, 30]);
+    /// ```
+    pub fn invalidate_from(&mut self, idx: usize) -> bool {
+        if idx >= self.sequence.len() {
+            return true;
+        }
+
+        let (&position, checkpoint) = match self.checkpoints.range(..=idx).next_back() {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        self.iterator = checkpoint.clone();
+        self.skip = idx - position;
+        self.exhausted = false;
+        self.sequence.truncate(idx);
+        self.checkpoints.split_off(&(idx + 1));
+
+        true
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Set a policy to be consulted before each evaluation of the internal
+    ///     Iterator, replacing any previous policy. The policy may delay the
+    ///     evaluation, as with a `TokenBucket`, or may halt it by returning
+    ///     `ControlFlow::Break`.
+    ///
+    /// If the policy halts evaluation, the method which needed the value will
+    ///     act as though the Iterator had ended, returning `None` or a short
+    ///     slice. However, the `MemoIter` is **not** marked as exhausted, and
+    ///     evaluation will be attempted again on the next access.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let mut memo = MemoIter::new(0..);
+    /// memo.set_policy(|idx| if idx < 5 {
+    ///     ControlFlow::Continue(())
+    /// } else {
+    ///     ControlFlow::Break(())
+    /// });
+    ///
+    /// assert_eq!(memo.get_slice(..10), [0, 1, 2, 3, 4]);
+    /// assert!(!memo.is_exhausted());
+    ///
+    /// memo.clear_policy();
+    /// assert_eq!(memo.get(9), Some(&9));
+    /// ```
+    pub fn set_policy<P>(&mut self, policy: P) where
+        P: EvalPolicy + Send + Sync + UnwindSafe + RefUnwindSafe + 'static,
+    {
+        self.policy = Some(Box::new(policy));
+        self.halted = false;
+    }
+
+    /// Remove the evaluation policy, if one is set.
+    pub fn clear_policy(&mut self) {
+        self.policy = None;
+        self.halted = false;
+    }
+
+    /// Associate a `CancelToken` with this `MemoIter`, replacing any previous
+    ///     token. The token is checked before each evaluation of the internal
+    ///     Iterator, and while it is cancelled, no evaluation will be done.
+    ///
+    /// A cancelled evaluation is treated in the same way as one halted by a
+    ///     policy. Every item evaluated before the cancellation remains stored,
+    ///     and the `MemoIter` is not marked as exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, policy::CancelToken};
+    /// use std::{thread, time::Duration};
+    ///
+    /// let token = CancelToken::new();
+    /// let mut memo = MemoIter::new(0u64..);
+    /// memo.set_cancel_token(token.clone());
+    ///
+    /// let supervisor = thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(10));
+    ///     token.cancel();
+    /// });
+    ///
+    /// //  Without cancellation, this would never return.
+    /// let evaluated: usize = memo.exhaust().len();
+    /// supervisor.join().unwrap();
+    ///
+    /// assert!(!memo.is_exhausted());
+    /// assert_eq!(memo.evaluated(), evaluated);
+    /// ```
+    pub fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = Some(token);
+        self.halted = false;
+    }
+
+    /// Remove the `CancelToken`, if one is set.
+    pub fn clear_cancel_token(&mut self) {
+        self.cancel = None;
+        self.halted = false;
+    }
+
+    /// Set whether the internal Iterator is fused, which it is assumed to be by
+    ///     default. A fused Iterator never returns another item after returning
+    ///     `None`, so the first `None` marks the `MemoIter` as exhausted.
+    ///
+    /// Some sources are not fused, and may return `None` while they are only
+    ///     temporarily empty, such as a polling reader or the `try_iter()` of a
+    ///     channel. If `fused` is `false`, a `None` is instead treated like a
+    ///     halt by a policy: No item is stored, the `MemoIter` is not marked as
+    ///     exhausted, and the Iterator will be tried again on the next access
+    ///     to an item not yet stored. Setting this also clears any exhaustion
+    ///     already recorded.
+    ///
+    /// Once `next()` has returned `None`, it continues to do so until `resume()`
+    ///     is called, as required by `FusedIterator`. Methods such as `get()`
+    ///     are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    /// use std::sync::mpsc::channel;
+    ///
+    /// let (tx, rx) = channel();
+    /// tx.send(1).unwrap();
+    ///
+    /// let mut memo = MemoIter::new(rx.try_iter());
+    /// memo.set_fused(false);
+    ///
+    /// assert_eq!(memo.get(1), None);
+    /// assert!(!memo.is_exhausted());
+    ///
+    /// tx.send(2).unwrap();
+    /// assert_eq!(memo.get(1), Some(&2));
+    /// ```
+    pub fn set_fused(&mut self, fused: bool) {
+        self.fused = fused;
+
+        if !fused {
+            self.exhausted = false;
+        }
+    }
+
+    /// Return `false` if the internal Iterator has been marked as not fused,
+    ///     with `set_fused(false)`.
+    #[inline]
+    pub fn is_fused(&self) -> bool {
+        self.fused
+    }
+
+    /// Move the cursor of the Iterator implementation back to the start, so
+    ///     that `next()` replays the stored items from the first, before it
+    ///     continues to evaluate new items. See `MemoIter::seek()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new(1..=5);
+    ///
+    /// assert_eq!(memo.by_ref().take(3).collect::<Vec<_>>(), [1, 2, 3]);
+    ///
+    /// memo.reset();
+    /// assert_eq!(memo.collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn reset(&mut self) {
+        self.seek(0);
+    }
+
+    /// Move the cursor of the Iterator implementation to an index, so that the
+    ///     next call to `next()` returns the item at that index. Stored items
+    ///     are replayed from the cursor without being evaluated again. Once the
+    ///     cursor passes the last stored item, new items are evaluated as usual.
+    ///
+    /// Seeking past the stored items is allowed; the items before the cursor
+    ///     will be evaluated and stored when `next()` is next called.
+    #[inline]
+    pub fn seek(&mut self, idx: usize) {
+        self.cursor = if idx == self.sequence.len() { None } else { Some(idx) };
+        self.halted = false;
+    }
+
+    /// Allow `next()` to try to evaluate new items again, after it has returned
+    ///     `None` without the `MemoIter` being exhausted, such as because a
+    ///     policy halted evaluation or an unfused Iterator returned `None`.
+    ///
+    /// Until this is called, or the policy or `CancelToken` is replaced or
+    ///     removed, `next()` keeps returning `None`, so that a `MemoIter` can be
+    ///     relied upon as a `FusedIterator`.
+    #[inline]
+    pub fn resume(&mut self) {
+        self.halted = false;
     }
 
-    /// Create a `MemoIter` wrapping a given Iterator, using a provided Vector
-    ///     for its storage.
-    pub fn with_vec(iterator: I, sequence: Vec<T>) -> Self {
-        Self {
-            exhausted: false,
-            iterator,
-            sequence,
-        }
+    /// Return the index of the item which the Iterator implementation will
+    ///     return next.
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor.unwrap_or(self.sequence.len())
+    }
+
+    /// Subscribe to the indices of newly evaluated items. Every item evaluated
+    ///     after this call will have its index sent to the returned Receiver.
+    ///     The subscription ends when the Receiver is dropped.
+    ///
+    /// While any subscription is active, items are evaluated one at a time,
+    ///     rather than in bulk.
+    pub fn subscribe_indices(&mut self) -> Receiver<usize> {
+        let (tx, rx) = channel();
+        self.observers.push(Box::new(move |idx, _| tx.send(idx).is_ok()));
+        rx
     }
 }
 
 
+#[cfg(feature = "std")]
 impl<I, T> MemoIter<I, T> where
     I: Iterator<Item=T>,
+    T: Clone + Send + 'static,
 {
-    /// Return the number of items evaluated. This value will be one more than
-    ///     the highest index available via `MemoIter::recall()`.
-    #[inline]
-    pub fn evaluated(&self) -> usize {
-        self.sequence.len()
+    /// Subscribe to newly evaluated items. Every item evaluated after this call
+    ///     will be cloned and sent, with its index, to the returned Receiver.
+    ///     The subscription ends when the Receiver is dropped.
+    ///
+    /// While any subscription is active, items are evaluated one at a time,
+    ///     rather than in bulk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((0..).map(|n| n * 10));
+    /// memo.get(1);
+    ///
+    /// let updates = memo.subscribe();
+    /// memo.get(3);
+    /// memo.get(2);
+    ///
+    /// let received: Vec<(usize, i32)> = updates.try_iter().collect();
+    /// assert_eq!(received, [(2, 20), (3, 30)]);
+    /// ```
+    pub fn subscribe(&mut self) -> Receiver<(usize, T)> {
+        let (tx, rx) = channel();
+        self.observers.push(Box::new(move |idx, item: &T| tx.send((idx, item.clone())).is_ok()));
+        rx
     }
+}
 
-    fn expand_to_contain(&mut self, idx: usize) {
-        if !self.exhausted {
-            let len: usize = self.sequence.len();
-
-            if idx >= len {
-                self.sequence.reserve(idx - len + 1);
-
-                for _i in len..=idx {
-                    #[cfg(test)] println!("+ {}", _i);
-
-                    match self.iterator.next() {
-                        Some(next) => self.sequence.push(next),
-                        None => {
-                            self.exhausted = true;
-                            self.sequence.shrink_to_fit();
-                            return;
-                        }
-                    }
-                }
-            }
-        }
-    }
 
-    /// Retrieve, by its index, a value returned by the Iterator. If the value
-    ///     at the index given has not yet been evaluated, it will be. Returns
-    ///     `None` if the internal Iterator terminates before reaching the given
-    ///     index.
-    pub fn get(&mut self, idx: usize) -> Option<&T> {
-        #[cfg(test)] println!("get({}):", idx);
-        self.expand_to_contain(idx);
-        self.sequence.get(idx)
-    }
+/// An Iterator over the values of a `MemoIter` paired with their indices, which
+///     evaluates values as it reaches them. Returned by
+///     `MemoIter::entries_evaluating()`.
+#[derive(Debug)]
+#[cfg(feature = "std")]
+pub struct EntriesEvaluating<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    limit: usize,
+    memo: &'a mut MemoIter<I, T>,
+    next: usize,
+}
 
-    /// Retrieve a slice of values returned by the Iterator. If the values in
-    ///     the range in question have not yet been evaluated, they will be.
-    ///
-    /// Retrieving a slice whose end bound has not yet been evaluated will cause
-    ///     all values up to that point to be evaluated. Because an Iterator may
-    ///     be infinite, an *unbounded* slice will, out of caution, **not** do
-    ///     any evaluations, instead being limited to the existing indices in
-    ///     the stored sequence. A convenient side effect of this is that a full
-    ///     range -- that is, `memiter.get_slice(..)` -- will return a full
-    ///     slice of the *stored* sequence, doing no new evaluations.
-    ///
-    /// However, because the final index may not be knowable, this method also
-    ///     includes a check to ensure that it will not panic if given a range
-    ///     with indices outside the final sequence, instead returning an empty
-    ///     slice.
-    pub fn get_slice<R>(&mut self, range: R) -> &[T] where
-        R: RangeBounds<usize> + SliceIndex<[T], Output=[T]>,
-    {
-        let first: usize = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(&i) => i,
-            Bound::Excluded(&i) => i + 1,
-        };
 
-        match range.end_bound() {
-            Bound::Unbounded => {
-                let end: usize = self.sequence.len();
+#[cfg(feature = "std")]
+impl<I, T> Iterator for EntriesEvaluating<'_, I, T> where
+    I: Iterator<Item=T>,
+    T: Clone,
+{
+    type Item = (usize, T);
 
-                &self.sequence[first.min(end)..end]
-            }
-            Bound::Included(&i) => {
-                self.expand_to_contain(i);
-                let last: usize = self.sequence.len().saturating_sub(1).min(i);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next < self.limit {
+            let idx: usize = self.next;
+            let item: T = self.memo.get(idx)?.clone();
 
-                if first <= last {
-                    &self.sequence[first..=last]
-                } else {
-                    //  NOTE: Edge case. Prevents `&[0, 1, 2, 3][10..=20]` from
-                    //      being evaluated as `&[3]`.
-                    &[]
-                }
-            }
-            Bound::Excluded(&i) => {
-                self.expand_to_contain(i.saturating_sub(1));
-                let end: usize = self.sequence.len().min(i);
+            self.next += 1;
+            Some((idx, item))
+        } else { None }
+    }
 
-                &self.sequence[first.min(end)..end]
-            }
-        }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.next = self.next.saturating_add(n).min(self.limit);
+        self.next()
     }
 
-    /// Return `true` if the internal Iterator has been exhausted and is done
-    ///     returning new values.
     #[inline]
-    pub fn is_exhausted(&self) -> bool {
-        self.exhausted
-    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining: usize = self.limit - self.next;
+        let stored: usize = self.memo.evaluated().saturating_sub(self.next);
 
-    /// Retrieve, by its index, a value returned by the Iterator. If the value
-    ///     at the index given has not yet been evaluated, it will **NOT** be
-    ///     evaluated now, and this method will return `None`.
-    pub fn recall(&mut self, idx: usize) -> Option<&T> {
-        #[cfg(test)] println!("recall({})", idx);
-        self.sequence.get(idx)
+        match self.memo.is_exhausted() {
+            true => (stored.min(remaining), Some(stored.min(remaining))),
+            false => (stored.min(remaining), Some(remaining)),
+        }
     }
+}
 
-    /// Consume self, returning a Tuple containing the internal stored `Vec<T>`
-    ///     and the original Iterator.
-    pub fn consume(self) -> (Vec<T>, I) {
-        let Self { sequence, iterator, .. } = self;
-        (sequence, iterator)
+
+#[cfg(feature = "std")]
+impl<I, T> Debug for MemoIter<I, T> where
+    I: Debug + Iterator<Item=T>,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoIter")
+            .field("cancel", &self.cancel)
+            .field("checkpoint_every", &self.checkpoint_every.map(|(every, _)| every))
+            .field("checkpoints", &self.checkpoints)
+            .field("cursor", &self.cursor)
+            .field("exhausted", &self.exhausted)
+            .field("fused", &self.fused)
+            .field("halted", &self.halted)
+            .field("hints", &self.hints)
+            .field("iterator", &self.iterator)
+            .field("mirror", &self.mirror.is_some())
+            .field("mirror_error", &self.mirror_error.as_ref().map(|e| &e.0))
+            .field("observers", &self.observers.len())
+            .field("policy", &self.policy.is_some())
+            .field("sequence", &self.sequence)
+            .field("skip", &self.skip)
+            .finish()
     }
 }
 
 
+#[cfg(feature = "std")]
 impl<I, T> AsRef<[T]> for MemoIter<I, T> where
     I: Iterator<Item=T>,
 {
@@ -218,6 +1641,7 @@ impl<I, T> AsRef<[T]> for MemoIter<I, T> where
 }
 
 
+#[cfg(feature = "std")]
 impl<I, T> Deref for MemoIter<I, T> where
     I: Iterator<Item=T>,
 {
@@ -231,22 +1655,20 @@ impl<I, T> Deref for MemoIter<I, T> where
 }
 
 
-impl<I, T> ExactSizeIterator for MemoIter<I, T> where
+#[cfg(feature = "std")]
+impl<I, T> MemoIter<I, T> where
     I: ExactSizeIterator + Iterator<Item=T>,
-    T: Copy,
 {
+    /// Return the length of the whole sequence: the items evaluated so far,
+    ///     and those the internal Iterator has yet to return.
     #[inline]
-    fn len(&self) -> usize {
-        self.sequence.len() + self.iterator.len()
+    pub fn total_len(&self) -> usize {
+        self.sequence.len() + self.iterator.len().saturating_sub(self.skip)
     }
-
-    // #[cfg(exact_size_is_empty)]
-    // fn is_empty(&self) -> bool {
-    //     self.iterator.is_empty()
-    // }
 }
 
 
+#[cfg(feature = "std")]
 impl<F, I, T> From<F> for MemoIter<I, T> where
     F: IntoIterator<Item=T, IntoIter=I>,
     I: Iterator<Item=T>,
@@ -257,13 +1679,91 @@ impl<F, I, T> From<F> for MemoIter<I, T> where
 }
 
 
+/// Collect an Iterator into an exhausted `MemoIter`, whose source is empty.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+/// use std::iter::Empty;
+///
+/// let mut memo: MemoIter<Empty<u32>, u32> = (1..=4).map(|n| n * n).collect();
+///
+/// assert!(memo.is_exhausted());
+/// assert_eq!(memo.get(3), Some(&16));
+/// assert_eq!(memo.get(4), None);
+/// ```
+#[cfg(feature = "std")]
+impl<T> FromIterator<T> for MemoIter<Empty<T>, T> {
+    fn from_iter<F: IntoIterator<Item=T>>(iter: F) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+
+/// Convert a `MemoIter` into an array of exactly `N` items. At most `N + 1`
+///     items are evaluated, which is enough to tell whether the sequence is
+///     longer than the array.
+///
+/// If the sequence does not have exactly `N` items, or if its length cannot be
+///     determined because a policy or `CancelToken` halts evaluation, the
+///     `MemoIter` is returned unchanged, apart from the items evaluated.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+/// use std::convert::TryFrom;
+///
+/// let key: [u8; 4] = <[u8; 4]>::try_from(MemoIter::new(b"\xde\xad\xbe\xef".iter().copied()))
+///     .unwrap();
+/// assert_eq!(key, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let long = <[u32; 4]>::try_from(MemoIter::new(0..));
+/// assert_eq!(long.unwrap_err().evaluated(), 5);
+///
+/// let short = <[u32; 4]>::try_from(MemoIter::new(0..3));
+/// assert!(short.unwrap_err().is_exhausted());
+/// ```
+#[cfg(feature = "std")]
+impl<I, T, const N: usize> TryFrom<MemoIter<I, T>> for [T; N] where
+    I: Iterator<Item=T>,
+{
+    type Error = MemoIter<I, T>;
+
+    fn try_from(mut memo: MemoIter<I, T>) -> Result<Self, Self::Error> {
+        memo.expand_to_contain(N);
+
+        if memo.exhausted && memo.sequence.len() == N {
+            let (sequence, iterator) = memo.consume();
+
+            sequence.try_into().map_err(|sequence| {
+                let mut memo = MemoIter::with_vec(iterator, sequence);
+                memo.exhausted = true;
+                memo
+            })
+        } else {
+            Err(memo)
+        }
+    }
+}
+
+
 /// Implement FusedIterator for MemoIters. The underlying iterator may not
 ///     necessarily be fused, but it will be *assumed* as such by the MemoIter;
 ///     The stored sequence contains `T`, not `Option<T>`, so we cannot properly
 ///     represent gaps.
+///
+/// An evaluation policy which halts evaluation will cause `next()` to return
+///     `None` without the MemoIter being exhausted, as will an Iterator marked
+///     as not fused with `set_fused(false)`. To uphold the guarantee of this
+///     trait, `next()` then keeps returning `None` until `MemoIter::resume()`
+///     or `MemoIter::seek()` is called, or the policy is changed.
+#[cfg(feature = "std")]
 impl<I: Iterator<Item=T>, T: Copy> FusedIterator for MemoIter<I, T> {}
 
 
+#[cfg(feature = "std")]
 impl<I, T> Iterator for MemoIter<I, T> where
     I: Iterator<Item=T>,
     T: Copy,
@@ -271,27 +1771,131 @@ impl<I, T> Iterator for MemoIter<I, T> where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if !self.exhausted {
-            match self.iterator.next() {
-                Some(next) => {
-                    self.sequence.push(next);
-                    Some(next)
-                }
-                None => {
-                    self.exhausted = true;
-                    self.sequence.shrink_to_fit();
-                    None
-                }
+        if self.halted {
+            return None;
+        }
+
+        let item: Option<T> = if let Some(pos) = self.cursor {
+            let item: Option<T> = self.get(pos).copied();
+
+            //  NOTE: Once the cursor reaches the end of the stored items, it is
+            //      cleared, and new items are evaluated one at a time below.
+            self.cursor = Some(pos + 1).filter(|&next| item.is_some() && next < self.sequence.len());
+            item
+        } else if !self.exhausted && self.evaluate_next() {
+            self.sequence.last().copied()
+        } else { None };
+
+        self.halted = item.is_none();
+        item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let replay: usize = match self.cursor {
+            Some(pos) => self.sequence.len().saturating_sub(pos),
+            None => 0,
+        };
+
+        if self.halted {
+            (0, Some(0))
+        } else if self.exhausted {
+            (replay, Some(replay))
+        } else {
+            let (lower, upper) = self.iterator.size_hint();
+            let upper: Option<usize> = upper.map(|n| n.saturating_sub(self.skip).saturating_add(replay));
+
+            //  NOTE: Only a policy or a `CancelToken` can end evaluation before
+            //      the Iterator does, so nothing more than the replayed items
+            //      can be promised while one is set. For this reason, neither
+            //      `ExactSizeIterator` nor `TrustedLen` is implemented.
+            let may_halt: bool = self.policy.is_some() || self.cancel.is_some();
+
+            if !may_halt && self.cursor.map_or(true, |pos| pos <= self.sequence.len()) {
+                (lower.saturating_sub(self.skip).saturating_add(replay), upper)
+            } else {
+                (replay, upper)
             }
-        } else { None }
+        }
+    }
+
+    /// Advance past `n` new items. The items are still stored, but they are
+    ///     evaluated in bulk, in the same way as by `get()`.
+    #[cfg(feature = "nightly")]
+    fn advance_by(&mut self, n: usize) -> Result<(), std::num::NonZeroUsize> {
+        if self.halted {
+            return std::num::NonZeroUsize::new(n).map_or(Ok(()), Err);
+        }
+
+        if let Some(pos) = self.cursor {
+            let target: usize = pos.saturating_add(n);
+
+            if let Some(last) = target.checked_sub(1) {
+                self.expand_to_contain(last);
+            }
+
+            let advanced: usize = self.sequence.len().min(target).saturating_sub(pos);
+            self.seek(target.min(self.sequence.len()));
+
+            return std::num::NonZeroUsize::new(n - advanced).map_or(Ok(()), Err);
+        }
+
+        let len: usize = self.sequence.len();
+
+        if let Some(last) = n.checked_sub(1) {
+            self.expand_to_contain(len.saturating_add(last));
+        }
+
+        let advanced: usize = self.sequence.len() - len;
+        std::num::NonZeroUsize::new(n - advanced).map_or(Ok(()), Err)
+    }
+
+    /// Fold the remaining items of the internal Iterator. Because `self` is
+    ///     consumed, the items do not need to be stored, and unless a policy or
+    ///     `CancelToken` must be consulted, this is forwarded directly to the
+    ///     `fold()` of the internal Iterator, which may be specialized. Stored
+    ///     items after the cursor are replayed first.
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc: B = init;
+
+        if self.halted {
+            return acc;
+        }
+
+        if let Some(pos) = self.cursor.take() {
+            if let Some(before) = pos.checked_sub(1) {
+                self.expand_to_contain(before);
+            }
+
+            match self.sequence.get(pos..) {
+                Some(replay) => acc = replay.iter().copied().fold(acc, &mut f),
+                None => return acc,
+            }
+        }
+
+        if self.exhausted || !self.catch_up() {
+            acc
+        } else if self.is_unchecked() {
+            self.iterator.fold(acc, f)
+        } else {
+            for next in self.by_ref() {
+                acc = f(acc, next);
+            }
+
+            acc
+        }
     }
 }
 
 
 #[cfg(test)]
+#[cfg(feature = "std")]
 mod tests {
-    use std::iter::successors;
+    use std::{iter::successors, ops::ControlFlow};
     use super::*;
+    use crate::testing::CountingIter;
 
     #[test]
     fn test_factorial() {
@@ -351,23 +1955,316 @@ mod tests {
         assert_eq!(seq, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
     }
 
+    #[test]
+    fn test_memoization() {
+        let iter = CountingIter::new(0..);
+        let counter = iter.counter();
+        let mut memo = MemoIter::new(iter);
+
+        assert_eq!(memo.get(4), Some(&4));
+        assert_eq!(counter.times_advanced(), 5);
+
+        assert_eq!(memo.get(2), Some(&2));
+        assert_eq!(memo.get_slice(..=4), [0, 1, 2, 3, 4]);
+        assert_eq!(counter.times_advanced(), 5);
+
+        assert_eq!(memo.get_slice(3..7), [3, 4, 5, 6]);
+        assert_eq!(counter.times_advanced(), 7);
+    }
+
+    #[test]
+    fn test_fold() {
+        fn push(mut v: Vec<i32>, n: i32) -> Vec<i32> {
+            v.push(n);
+            v
+        }
+
+        let mut memo = MemoIter::new(1..=10);
+        assert_eq!(memo.get(2), Some(&3));
+        assert_eq!(memo.fold(Vec::new(), push), [4, 5, 6, 7, 8, 9, 10]);
+
+        let mut memo = MemoIter::new(1..=10);
+        memo.set_policy(|idx| if idx < 5 {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        });
+        assert_eq!(memo.fold(Vec::new(), push), [1, 2, 3, 4, 5]);
+
+        let mut memo = MemoIter::new(0..3);
+        memo.get(5);
+        assert_eq!(memo.fold(Vec::new(), push), []);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let mut memo = MemoIter::new(0..10);
+        assert_eq!(memo.size_hint(), (10, Some(10)));
+
+        memo.get(3);
+        assert_eq!(memo.size_hint(), (6, Some(6)));
+        assert_eq!(memo.nth(2), Some(6));
+        assert_eq!(memo.evaluated(), 7);
+
+        memo.set_cancel_token(CancelToken::new());
+        assert_eq!(memo.size_hint(), (0, Some(3)));
+
+        memo.get(20);
+        assert_eq!(memo.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn test_advance_by() {
+        let mut memo = MemoIter::new(0..10);
+
+        assert_eq!(memo.advance_by(4), Ok(()));
+        assert_eq!(memo.next(), Some(4));
+        assert_eq!(memo.advance_by(0), Ok(()));
+        assert_eq!(memo.advance_by(7), Err(std::num::NonZeroUsize::new(2).unwrap()));
+        assert_eq!(*memo, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        memo.seek(3);
+        assert_eq!(memo.advance_by(5), Ok(()));
+        assert_eq!(memo.next(), Some(8));
+        assert_eq!(memo.advance_by(3), Err(std::num::NonZeroUsize::new(2).unwrap()));
+        assert_eq!(memo.next(), None);
+    }
+
     #[test]
     fn test_len() {
         let mut five = MemoIter::new(0..5);
 
         assert!(!five.is_exhausted());
         assert_eq!(five.evaluated(), 0);
-        assert_eq!(five.len(), 5);
+        assert_eq!(five.total_len(), 5);
         assert_eq!(five.get(3), Some(&3));
 
         assert!(!five.is_exhausted());
         assert_eq!(five.evaluated(), 4);
-        assert_eq!(five.len(), 5);
+        assert_eq!(five.total_len(), 5);
         assert_eq!(five.get(7), None);
 
         assert!(five.is_exhausted());
         assert_eq!(five.evaluated(), 5);
-        assert_eq!(five.len(), 5);
+        assert_eq!(five.total_len(), 5);
+
+        //  As an Iterator, only the items still to be returned are counted, and
+        //      only a policy or a `CancelToken` can make the count uncertain.
+        let mut five = MemoIter::new(0..5);
+        assert_eq!(five.get(1), Some(&1));
+        assert_eq!(five.size_hint(), (3, Some(3)));
+
+        let _indices = five.subscribe_indices();
+        assert_eq!(five.next(), Some(2));
+        assert_eq!(five.size_hint(), (2, Some(2)));
+
+        five.set_policy(|_| ControlFlow::Continue(()));
+        assert_eq!(five.size_hint(), (0, Some(2)));
+        assert_eq!(five.total_len(), 5);
+    }
+
+    #[test]
+    fn test_policy() {
+        let mut memo = MemoIter::new(0..10);
+        memo.set_policy(|idx| if idx % 4 == 3 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(memo.get(5), None);
+        assert_eq!(memo.get_slice(..), [0, 1, 2]);
+        assert_eq!(memo.next(), None);
+        assert!(!memo.is_exhausted());
+
+        memo.set_policy(|_| ControlFlow::Continue(()));
+        assert_eq!(memo.next(), Some(3));
+        assert_eq!(memo.get(20), None);
+        assert!(memo.is_exhausted());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let token = CancelToken::new();
+        let mut memo = MemoIter::new(0..);
+        memo.set_cancel_token(token.clone());
+
+        assert_eq!(memo.get(3), Some(&3));
+        token.cancel();
+
+        assert_eq!(memo.get(5), None);
+        assert_eq!(memo.exhaust(), [0, 1, 2, 3]);
+        assert!(!memo.is_exhausted());
+
+        token.reset();
+        assert_eq!(memo.get(5), Some(&5));
+
+        memo.clear_cancel_token();
+        token.cancel();
+        assert_eq!(memo.get_slice(6..8), [6, 7]);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let source = CountingIter::new(0..10);
+        let counter = source.counter();
+        let mut memo = MemoIter::new(source);
+
+        assert_eq!(memo.get(2), Some(&2));
+        assert!(!memo.invalidate_from(0));
+        memo.checkpoint();
+        assert_eq!(memo.exhaust().len(), 10);
+        assert_eq!(counter.times_advanced(), 11);
+
+        assert!(!memo.invalidate_from(1));
+        assert!(memo.invalidate_from(6));
+        assert_eq!(memo.evaluated(), 6);
+        assert!(!memo.is_exhausted());
+        assert_eq!(memo.total_len(), 10);
+        assert_eq!(counter.times_advanced(), 11);
+
+        assert_eq!(memo.get(6), Some(&6));
+        assert_eq!(counter.times_advanced(), 11 + 4);
+
+        assert!(memo.invalidate_from(4));
+        memo.set_policy(|_| ControlFlow::Continue(()));
+        assert_eq!(memo.exhaust(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(memo.checkpoints(), 1);
+    }
+
+    #[test]
+    fn test_absorb() {
+        let source = CountingIter::new(0..10);
+        let counter = source.counter();
+        let mut memo = MemoIter::new(source);
+
+        assert_eq!(memo.get(1), Some(&1));
+        assert_eq!(memo.absorb(0..6), Ok(4));
+        assert_eq!(memo.absorb(vec![0, 1]), Ok(0));
+        assert_eq!(memo.total_len(), 10);
+        assert_eq!(counter.times_advanced(), 2);
+
+        assert_eq!(memo.get(6), Some(&6));
+        assert_eq!(counter.times_advanced(), 7);
+
+        assert_eq!(memo.exhaust().len(), 10);
+        assert_eq!(memo.absorb(0..11), Err(Divergence { index: 10 }));
+        assert_eq!(memo.absorb(0..10), Ok(0));
+
+        //  Absorbed items reach the mirror, just as evaluated items do.
+        let (tx, rx) = channel();
+        let mut memo = MemoIter::new(0..10);
+        memo.set_mirror(tx);
+
+        assert_eq!(memo.get(1), Some(&1));
+        assert_eq!(memo.absorb(0..4), Ok(2));
+        assert_eq!(memo.get(4), Some(&4));
+        assert_eq!(rx.try_iter().collect::<Vec<i32>>(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_try_get() {
+        let mut memo = MemoIter::new((0..).map(|n: u64| n * 2));
+
+        assert_eq!(memo.try_get(1000), Ok(Some(&2000)));
+        assert!(memo.capacity() < 2 * 1024 + 8);
+
+        memo.set_policy(|n| match n < 1200 {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        });
+        assert_eq!(memo.try_get(5000), Ok(None));
+        assert_eq!(memo.evaluated(), 1200);
+        assert!(!memo.is_exhausted());
+    }
+
+    #[test]
+    fn test_unfused() {
+        let (tx, rx) = channel();
+        let mut memo = MemoIter::new(rx.try_iter());
+        memo.set_fused(false);
+
+        tx.send('a').unwrap();
+        assert_eq!(memo.get_slice(..4), ['a']);
+        assert!(!memo.is_exhausted());
+
+        tx.send('b').unwrap();
+        tx.send('c').unwrap();
+        assert_eq!(memo.exhaust(), ['a', 'b', 'c']);
+        assert_eq!(memo.next(), None);
+
+        //  Having returned `None`, `next()` stays fused until resumed.
+        tx.send('d').unwrap();
+        assert_eq!(memo.next(), None);
+        assert_eq!(memo.size_hint(), (0, Some(0)));
+        memo.resume();
+        assert_eq!(memo.next(), Some('d'));
+
+        memo.set_fused(true);
+        tx.send('e').unwrap();
+        assert_eq!(memo.get(5), None);
+        assert!(memo.is_exhausted());
+
+        tx.send('f').unwrap();
+        assert_eq!(memo.get(5), None);
+        assert_eq!(memo.get_slice(..), ['a', 'b', 'c', 'd', 'e']);
+    }
+
+    #[test]
+    fn test_unwind_safe() {
+        fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>(_: &T) {}
+
+        let mut memo = MemoIter::new(0..10u32);
+        memo.set_policy(|idx: usize| if idx < 5 { ControlFlow::Continue(()) } else { ControlFlow::Break(()) });
+        memo.set_cancel_token(CancelToken::new());
+        memo.set_mirror(Vec::new());
+        let _indices = memo.subscribe_indices();
+
+        assert_unwind_safe(&memo);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let iter = CountingIter::new(0..10);
+        let counter = iter.counter();
+        let mut memo = MemoIter::new(iter);
+
+        assert_eq!(memo.get(3), Some(&3));
+        assert_eq!(memo.next(), Some(4));
+
+        memo.reset();
+        assert_eq!(memo.cursor(), 0);
+        assert_eq!(memo.size_hint(), (10, Some(10)));
+        assert_eq!(memo.by_ref().take(6).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(counter.times_advanced(), 6);
+        assert_eq!(memo.cursor(), 6);
+
+        memo.seek(8);
+        assert_eq!(memo.next(), Some(8));
+        assert_eq!(memo.evaluated(), 9);
+
+        memo.seek(2);
+        assert_eq!(memo.by_ref().skip(5).sum::<i32>(), 7 + 8 + 9);
+        assert_eq!(counter.times_advanced(), 11);
+
+        memo.seek(20);
+        assert_eq!(memo.next(), None);
+        assert_eq!(memo.cursor(), 10);
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let mut memo = MemoIter::new(0..4);
+        let indices = memo.subscribe_indices();
+        let items = memo.subscribe();
+
+        assert_eq!(memo.get(1), Some(&1));
+        drop(items);
+        assert_eq!(memo.exhaust(), [0, 1, 2, 3]);
+
+        assert_eq!(indices.try_iter().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(memo.observers.len(), 1);
     }
 
     #[test]
@@ -375,7 +2272,7 @@ mod tests {
         let mut five = MemoIter::with_vec(1..5, vec![0]);
 
         assert!(!five.is_exhausted());
-        assert_eq!(five.len(), 5);
+        assert_eq!(five.total_len(), 5);
         assert_eq!(five.evaluated(), 1);
         assert_eq!(five.recall(0), Some(&0));
         assert_eq!(five.recall(1), None);
@@ -384,7 +2281,7 @@ mod tests {
         assert_eq!(five.get(10), None);
 
         assert!(five.is_exhausted());
-        assert_eq!(five.len(), 5);
+        assert_eq!(five.total_len(), 5);
         assert_eq!(five.evaluated(), 5);
         assert_eq!(five.recall(0), Some(&0));
         assert_eq!(five.recall(1), Some(&1));
@@ -392,6 +2289,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::reversed_empty_ranges)]
     fn test_slice() {
         let mut five = MemoIter::new(0..5);
 
@@ -400,6 +2298,7 @@ mod tests {
 
         assert_eq!(five.get_slice(..), []);
         assert_eq!(five.get_slice(..0), []);
+        assert_eq!(five.evaluated(), 0);
         assert_eq!(five.get_slice(..=0), [0]);
         assert_eq!(five.get_slice(0..1), [0]);
         assert_eq!(five.get_slice(0..), [0]);
@@ -417,11 +2316,20 @@ mod tests {
         assert_eq!(five.get_slice(..), [0, 1, 2, 3, 4]);
 
         assert_eq!(five.get_slice(..=usize::MAX), [0, 1, 2, 3, 4]);
+        assert_eq!(five.get_slice((Bound::Excluded(usize::MAX), Bound::Unbounded)), []);
         assert_eq!(five.get_slice(50..40), []);
+        assert_eq!(five.get_slice_checked(50..40), Err(AccessError::InvalidRange { start: 50, end: 40 }));
+        assert_eq!(five.get_slice_checked(..), Ok(&[0, 1, 2, 3, 4][..]));
+        assert_eq!(five.get_slice_checked(5..), Ok(&[][..]));
+        assert_eq!(five.get_slice_checked(6..), Err(AccessError::OutOfRange { index: 6, len: 5 }));
+        assert_eq!(five.get_slice_owned(3..), vec![3, 4]);
+        assert_eq!(five.get_slice_at(usize::MAX, 2), []);
+        assert_eq!(five.get_slice_at_checked(5, 0), Ok(&[][..]));
+        assert_eq!(five.get_slice_at_checked(6, 0), Err(AccessError::OutOfRange { index: 6, len: 5 }));
 
         assert!(five.is_exhausted());
         assert_eq!(five.evaluated(), 5);
-        assert_eq!(five.len(), 5);
+        assert_eq!(five.total_len(), 5);
 
         assert_eq!(*five, [0, 1, 2, 3, 4]);
         assert_eq!(five[..], [0, 1, 2, 3, 4]);
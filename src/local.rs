@@ -0,0 +1,198 @@
+//! Memoization with a separate cache on each thread, for sources which cannot
+//!     be sent between threads.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use crate::MemoIter;
+
+
+/// A cache of one thread, type-erased so that memos of any type can share it.
+type Cache = Rc<RefCell<dyn Any>>;
+
+
+thread_local! {
+    /// The caches of this thread, keyed by the ID of their `ThreadLocalMemo`.
+    static CACHES: RefCell<HashMap<usize, Cache>> = RefCell::new(HashMap::new());
+}
+
+
+/// The ID to be given to the next `ThreadLocalMemo`.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+
+/// A memoized sequence which keeps an independent `MemoIter` on each thread
+///     that uses it, each built from the same factory function. This allows
+///     many threads to share memoized access to a sequence whose Iterator is
+///     not `Send`, such as one holding an `Rc` or a handle from a foreign
+///     library, at the cost of each thread evaluating the items it uses.
+///
+/// The cache of a thread is created the first time that thread uses it, and
+///     lasts until the thread exits, `ThreadLocalMemo::clear()` is called on
+///     it, or the `ThreadLocalMemo` is dropped on it.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::local::ThreadLocalMemo;
+/// use std::{rc::Rc, sync::Arc, thread};
+///
+/// //  An `Rc` makes this Iterator impossible to send between threads.
+/// let squares = Arc::new(ThreadLocalMemo::new(|| {
+///     let step = Rc::new(1u64);
+///     (0u64..).map(move |n| n * n * *step)
+/// }));
+///
+/// let workers: Vec<_> = (0..4).map(|t| {
+///     let squares = squares.clone();
+///     thread::spawn(move || squares.with(|memo| *memo.get(t * 10).unwrap()))
+/// }).collect();
+///
+/// let results: Vec<u64> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+/// assert_eq!(results, [0, 100, 400, 900]);
+///
+/// assert_eq!(squares.get_cloned(12), Some(144));
+/// assert_eq!(squares.with(|memo| memo.evaluated()), 13);
+/// ```
+pub struct ThreadLocalMemo<F, I, T> {
+    factory: F,
+    id: usize,
+    _memo: PhantomData<fn() -> (I, T)>,
+}
+
+
+impl<F, I, T> ThreadLocalMemo<F, I, T> where
+    F: Fn() -> I,
+    I: Iterator<Item=T> + 'static,
+    T: 'static,
+{
+    /// Create a `ThreadLocalMemo` whose caches will wrap the Iterators returned
+    ///     by `factory`.
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            _memo: PhantomData,
+        }
+    }
+
+    /// Call a function with the `MemoIter` of the current thread, creating it
+    ///     if this thread has not used it before.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it is called again, for the same
+    ///     `ThreadLocalMemo`, from within the function.
+    pub fn with<R, G>(&self, f: G) -> R where
+        G: FnOnce(&mut MemoIter<I, T>) -> R,
+    {
+        let existing: Option<Cache> = CACHES.with(|caches| caches.borrow().get(&self.id).cloned());
+
+        let cache: Cache = match existing {
+            Some(cache) => cache,
+            None => {
+                //  NOTE: The factory may use other `ThreadLocalMemo`s, so it is
+                //      run while the caches of this thread are not borrowed.
+                let created: Cache = Rc::new(RefCell::new(MemoIter::new((self.factory)())));
+
+                CACHES.with(|caches| {
+                    caches.borrow_mut().entry(self.id).or_insert(created).clone()
+                })
+            }
+        };
+
+        let mut cache = cache.borrow_mut();
+
+        //  NOTE: The cache under this ID was created by this instance, so it
+        //      always has this type.
+        f(cache.downcast_mut().expect("thread-local cache has the wrong type"))
+    }
+
+    /// Retrieve a clone of an item from the `MemoIter` of the current thread,
+    ///     evaluating it if needed.
+    pub fn get_cloned(&self, idx: usize) -> Option<T> where
+        T: Clone,
+    {
+        self.with(|memo| memo.get(idx).cloned())
+    }
+
+    /// Return `true` if the current thread has a cache.
+    pub fn is_cached(&self) -> bool {
+        CACHES.with(|caches| caches.borrow().contains_key(&self.id))
+    }
+
+    /// Discard the cache of the current thread. It will be created again if
+    ///     this thread uses it.
+    pub fn clear(&self) {
+        let _removed: Option<Cache> = CACHES.with(|caches| caches.borrow_mut().remove(&self.id));
+    }
+}
+
+
+impl<F, I, T> Drop for ThreadLocalMemo<F, I, T> {
+    fn drop(&mut self) {
+        //  NOTE: The caches of other threads cannot be reached from here, and
+        //      are dropped when those threads exit.
+        let _ = CACHES.try_with(|caches| caches.borrow_mut().remove(&self.id));
+    }
+}
+
+
+impl<F, I, T> Debug for ThreadLocalMemo<F, I, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadLocalMemo")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+    use super::*;
+
+    #[test]
+    fn test_per_thread() {
+        let created: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let counted: Arc<AtomicUsize> = created.clone();
+
+        let memo = Arc::new(ThreadLocalMemo::new(move || {
+            counted.fetch_add(1, Ordering::Relaxed);
+            0..100
+        }));
+
+        assert_eq!(memo.get_cloned(9), Some(9));
+        assert_eq!(memo.get_cloned(4), Some(4));
+        assert_eq!(created.load(Ordering::Relaxed), 1);
+
+        let other = memo.clone();
+        thread::spawn(move || {
+            assert!(!other.is_cached());
+            assert_eq!(other.get_cloned(4), Some(4));
+            assert_eq!(other.with(|memo| memo.evaluated()), 5);
+        }).join().unwrap();
+
+        assert_eq!(created.load(Ordering::Relaxed), 2);
+        assert_eq!(memo.with(|memo| memo.evaluated()), 10);
+
+        memo.clear();
+        assert!(!memo.is_cached());
+
+        //  A factory may itself use another `ThreadLocalMemo`.
+        let base = ThreadLocalMemo::new(|| 0..10usize);
+        let derived = ThreadLocalMemo::new(|| {
+            let start: usize = base.get_cloned(3).unwrap();
+            start..start + 5
+        });
+
+        assert_eq!(derived.get_cloned(1), Some(4));
+        assert!(base.is_cached());
+    }
+}
@@ -0,0 +1,56 @@
+//! Tracking of the order in which keys were last used, for eviction of the
+//!     least recently used.
+
+use std::{collections::{BTreeMap, HashMap}, hash::Hash};
+
+
+/// A record of the order in which keys were last used. Every operation is
+///     logarithmic in the number of keys tracked.
+#[derive(Debug)]
+pub(crate) struct Recency<K> {
+    clock: u64,
+    order: BTreeMap<u64, K>,
+    stamps: HashMap<K, u64>,
+}
+
+
+impl<K: Clone + Eq + Hash> Recency<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            clock: 0,
+            order: BTreeMap::new(),
+            stamps: HashMap::new(),
+        }
+    }
+
+    /// Mark a key as the most recently used.
+    pub(crate) fn touch(&mut self, key: &K) {
+        if let Some(old) = self.stamps.insert(key.clone(), self.clock) {
+            self.order.remove(&old);
+        }
+
+        self.order.insert(self.clock, key.clone());
+        self.clock += 1;
+    }
+
+    /// Stop tracking a key.
+    pub(crate) fn forget(&mut self, key: &K) {
+        if let Some(old) = self.stamps.remove(key) {
+            self.order.remove(&old);
+        }
+    }
+
+    /// Stop tracking the least recently used key, and return it.
+    pub(crate) fn pop_oldest(&mut self) -> Option<K> {
+        let (&stamp, _) = self.order.iter().next()?;
+        let key: K = self.order.remove(&stamp)?;
+
+        self.stamps.remove(&key);
+        Some(key)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.order.clear();
+        self.stamps.clear();
+    }
+}
@@ -0,0 +1,88 @@
+//! Items used by the code generated by `#[memoized]`. These are not part of the
+//!     public API, and may change at any time.
+
+use std::{cell::RefCell, iter::Empty, thread::LocalKey};
+use crate::MemoIter;
+
+
+/// The storage of a memoized function, held in a thread-local static.
+pub struct Memo<T>(RefCell<MemoIter<Empty<T>, T>>);
+
+
+impl<T> Memo<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(RefCell::new(MemoIter::from_vec(Vec::new())))
+    }
+}
+
+
+/// Return the result of a memoized function for a given index, calling the
+///     function for it and for every lower index which has not yet been
+///     stored, in ascending order.
+///
+/// The memo is never borrowed while the function runs, so that the function
+///     may call its memoized wrapper recursively, for lower indices.
+pub fn get<T: Clone + 'static>(
+    memo: &'static LocalKey<Memo<T>>,
+    idx: usize,
+    func: fn(usize) -> T,
+) -> T {
+    loop {
+        let len: usize = match memo.with(|memo| {
+            let memo = memo.0.borrow();
+            memo.sequence.get(idx).cloned().ok_or(memo.sequence.len())
+        }) {
+            Ok(value) => return value,
+            Err(len) => len,
+        };
+
+        let value: T = func(len);
+
+        memo.with(|memo| {
+            let mut memo = memo.0.borrow_mut();
+
+            if memo.sequence.len() == len {
+                memo.sequence.push(value);
+            }
+        });
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use crate::memoized;
+
+    thread_local! {
+        static CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[memoized]
+    fn tribonacci(n: usize) -> u64 {
+        CALLS.with(|calls| calls.set(calls.get() + 1));
+
+        match n {
+            0 | 1 => 0,
+            2 => 1,
+            n => tribonacci(n - 1) + tribonacci(n - 2) + tribonacci(n - 3),
+        }
+    }
+
+    #[test]
+    fn test_memoized() {
+        assert_eq!(tribonacci(30), 15_902_591);
+        assert_eq!(CALLS.with(Cell::get), 31);
+
+        assert_eq!(tribonacci(12), 274);
+        assert_eq!(CALLS.with(Cell::get), 31);
+
+        let elsewhere: usize = std::thread::spawn(|| {
+            tribonacci(3);
+            CALLS.with(Cell::get)
+        }).join().unwrap();
+
+        assert_eq!(elsewhere, 4);
+    }
+}
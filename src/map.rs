@@ -0,0 +1,307 @@
+//! Memoization of Iterators over key-value pairs, addressed by key.
+
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::Hash,
+};
+
+
+/// A Memoized Iterator over key-value pairs, which answers lookups by key. The
+///     Iterator is consumed only until the requested key is found, and every
+///     pair seen along the way is stored, so that later lookups of those keys
+///     need no further evaluation. This is the associative counterpart of
+///     `MemoIter`, suited to sources such as a stream of parsed settings or a
+///     directory walk.
+///
+/// If a key occurs more than once, its first value is kept, and later pairs
+///     with the same key are ignored, so that the result of a lookup does not
+///     depend on how far the Iterator has been consumed.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::map::MemoMap;
+///
+/// let config = "name=demo\nport=8080\nmode=fast\nport=9090";
+/// let pairs = config.lines().filter_map(|line| line.split_once('='));
+/// let mut settings = MemoMap::new(pairs);
+///
+/// assert_eq!(settings.get("port"), Some(&"8080"));
+/// assert_eq!(settings.evaluated(), 2);
+///
+/// assert_eq!(settings.recall("name"), Some(&"demo"));
+/// assert_eq!(settings.recall("mode"), None);
+///
+/// assert_eq!(settings.get("missing"), None);
+/// assert!(settings.is_exhausted());
+/// assert_eq!(settings.len(), 3);
+/// ```
+#[derive(Debug)]
+pub struct MemoMap<I, K, V> where
+    I: Iterator<Item=(K, V)>,
+{
+    evaluated: usize,
+    exhausted: bool,
+    iterator: I,
+    map: HashMap<K, V>,
+}
+
+
+impl<I, K, V> MemoMap<I, K, V> where
+    I: Iterator<Item=(K, V)>,
+    K: Eq + Hash,
+{
+    /// Create an empty `MemoMap` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            evaluated: 0,
+            exhausted: false,
+            iterator,
+            map: HashMap::new(),
+        }
+    }
+
+    /// Return the number of pairs taken from the Iterator, including any with
+    ///     repeated keys.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.evaluated
+    }
+
+    /// Return the number of distinct keys stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if no keys have been stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new pairs.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Take one pair from the Iterator and store it, unless its key has been
+    ///     seen before. Returns `false` if the Iterator has ended.
+    fn evaluate_next(&mut self) -> bool {
+        match self.iterator.next() {
+            Some((key, value)) => {
+                self.evaluated += 1;
+                self.map.entry(key).or_insert(value);
+                true
+            }
+            None => {
+                self.exhausted = true;
+                self.map.shrink_to_fit();
+                false
+            }
+        }
+    }
+
+    /// Retrieve the value for a key. If it has not yet been seen, the Iterator
+    ///     will be run until it is found, or until it ends.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        while !self.exhausted && !self.map.contains_key(key) {
+            self.evaluate_next();
+        }
+
+        self.map.get(key)
+    }
+
+    /// Return `true` if the key occurs in the sequence, running the Iterator
+    ///     until it is found, or until it ends.
+    #[inline]
+    pub fn contains_key<Q>(&mut self, key: &Q) -> bool where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Retrieve the value for a key, if it has already been seen. The Iterator
+    ///     is not run.
+    #[inline]
+    pub fn recall<Q>(&self, key: &Q) -> Option<&V> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Run the Iterator until it ends, returning the map of every key seen.
+    ///
+    /// This method will not return if the Iterator is infinite.
+    pub fn exhaust(&mut self) -> &HashMap<K, V> {
+        while !self.exhausted && self.evaluate_next() {}
+
+        &self.map
+    }
+
+    /// Return the map of the keys seen so far.
+    #[inline]
+    pub fn as_map(&self) -> &HashMap<K, V> {
+        &self.map
+    }
+
+    /// Consume self, returning the map of the keys seen so far, and the
+    ///     internal Iterator.
+    #[inline]
+    pub fn consume(self) -> (HashMap<K, V>, I) {
+        (self.map, self.iterator)
+    }
+}
+
+
+
+/// A lazy join of two `MemoMap`s on their keys. A lookup takes values from both
+///     sides, consuming each source only as far as needed to find the key, so
+///     that two expensive or remote feeds can be stitched together without
+///     reading either in full.
+///
+/// The left side is searched first, and the right side is not consumed at all
+///     for a key missing from the left.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::map::{MemoJoin, MemoMap};
+///
+/// let names = vec![(1, "ada"), (2, "brian"), (3, "grace")].into_iter();
+/// let scores = vec![(3, 97), (1, 88), (4, 75)].into_iter();
+///
+/// let mut joined = MemoJoin::new(MemoMap::new(names), MemoMap::new(scores));
+///
+/// assert_eq!(joined.get(&1), Some((&"ada", &88)));
+/// assert_eq!(joined.right().evaluated(), 2);
+///
+/// assert_eq!(joined.get(&4), None);
+/// assert!(joined.left().is_exhausted());
+/// assert_eq!(joined.right().evaluated(), 2);
+///
+/// assert_eq!(joined.get(&2), None);
+/// assert_eq!(joined.get_left_outer(&2), Some((&"brian", None)));
+/// ```
+#[derive(Debug)]
+pub struct MemoJoin<L, R, K, A, B> where
+    L: Iterator<Item=(K, A)>,
+    R: Iterator<Item=(K, B)>,
+{
+    left: MemoMap<L, K, A>,
+    right: MemoMap<R, K, B>,
+}
+
+
+impl<L, R, K, A, B> MemoJoin<L, R, K, A, B> where
+    L: Iterator<Item=(K, A)>,
+    R: Iterator<Item=(K, B)>,
+    K: Eq + Hash,
+{
+    /// Join two `MemoMap`s. Any pairs they have already stored are used.
+    pub fn new(left: MemoMap<L, K, A>, right: MemoMap<R, K, B>) -> Self {
+        Self { left, right }
+    }
+
+    /// Retrieve the values for a key from both sides, consuming each source
+    ///     as needed. Returns `None` if either side lacks the key.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<(&A, &B)> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let a: &A = self.left.get(key)?;
+        let b: &B = self.right.get(key)?;
+        Some((a, b))
+    }
+
+    /// Retrieve the values for a key from both sides, consuming each source
+    ///     as needed. Returns `None` if the left side lacks the key, but only
+    ///     omits the right value if the right side lacks it.
+    ///
+    /// Finding that the right side lacks a key consumes its source in full.
+    pub fn get_left_outer<Q>(&mut self, key: &Q) -> Option<(&A, Option<&B>)> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let a: &A = self.left.get(key)?;
+        Some((a, self.right.get(key)))
+    }
+
+    /// Retrieve the values for a key from both sides, if both have already
+    ///     been seen. Neither source is consumed.
+    pub fn recall<Q>(&self, key: &Q) -> Option<(&A, &B)> where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        Some((self.left.recall(key)?, self.right.recall(key)?))
+    }
+
+    /// Return a reference to the left `MemoMap`.
+    #[inline]
+    pub fn left(&self) -> &MemoMap<L, K, A> {
+        &self.left
+    }
+
+    /// Return a reference to the right `MemoMap`.
+    #[inline]
+    pub fn right(&self) -> &MemoMap<R, K, B> {
+        &self.right
+    }
+
+    /// Consume self, returning the left and right `MemoMap`s.
+    #[inline]
+    pub fn into_parts(self) -> (MemoMap<L, K, A>, MemoMap<R, K, B>) {
+        (self.left, self.right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_lazy_lookup() {
+        let source = CountingIter::new((0..100).map(|n| (n % 10, n)));
+        let counter = source.counter();
+        let mut map = MemoMap::new(source);
+
+        assert_eq!(map.get(&4), Some(&4));
+        assert_eq!(counter.times_advanced(), 5);
+
+        assert_eq!(map.get(&2), Some(&2));
+        assert_eq!(counter.times_advanced(), 5);
+
+        assert!(!map.contains_key(&10));
+        assert_eq!(map.len(), 10);
+        assert_eq!(map.evaluated(), 100);
+        assert_eq!(map.exhaust()[&9], 9);
+    }
+
+    #[test]
+    fn test_join() {
+        let left = CountingIter::new((0..50).map(|n| (n * 2, n)));
+        let right = CountingIter::new((0..50).map(|n| (n * 3, -n)));
+        let (left_count, right_count) = (left.counter(), right.counter());
+
+        let mut joined = MemoJoin::new(MemoMap::new(left), MemoMap::new(right));
+
+        assert_eq!(joined.get(&12), Some((&6, &-4)));
+        assert_eq!((left_count.times_advanced(), right_count.times_advanced()), (7, 5));
+
+        assert_eq!(joined.get(&13), None);
+        assert_eq!(right_count.times_advanced(), 5);
+
+        assert_eq!(joined.recall(&6), Some((&3, &-2)));
+        assert_eq!(joined.get_left_outer(&14), Some((&7, None)));
+        assert!(joined.right().is_exhausted());
+    }
+}
@@ -0,0 +1,176 @@
+//! Lazy merging of sorted memoized sequences.
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+};
+use crate::MemoIter;
+
+
+/// An Iterator which merges several sorted `MemoIter`s into one sorted
+///     sequence. Wrapping it in a `MemoIter` produces a sorted memoized
+///     sequence whose inputs are evaluated only as far as the merge requires.
+///
+/// Each input is kept one item ahead of the merge, so that its next item can
+///     be compared with the others. Items that compare equal are returned in
+///     the order of their inputs, unless duplicates are removed with `dedup()`.
+///
+/// The inputs are assumed to be sorted in ascending order. If they are not,
+///     the merged sequence will not be sorted either, but every item will
+///     still be returned.
+///
+/// The merge is not fused, because an input marked with `set_fused(false)` may
+///     return more items after ending, and so may the merge.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, merge::Merge};
+///
+/// //  Multiples of 3 or 5, as in the first Project Euler problem.
+/// let threes = MemoIter::new((3..).step_by(3));
+/// let fives = MemoIter::new((5..).step_by(5));
+///
+/// let mut merged = MemoIter::new(Merge::new(vec![threes, fives]).dedup());
+///
+/// assert_eq!(merged.get_slice(..8), [3, 5, 6, 9, 10, 12, 15, 18]);
+///
+/// let (_, merge) = merged.consume();
+/// assert_eq!(merge.inputs()[0].evaluated(), 7);
+/// assert_eq!(merge.inputs()[1].evaluated(), 4);
+/// ```
+#[derive(Debug)]
+pub struct Merge<I, T> where
+    I: Iterator<Item=T>,
+{
+    dedup: bool,
+    heads: BinaryHeap<Reverse<(T, usize)>>,
+    inputs: Vec<MemoIter<I, T>>,
+    last: Option<T>,
+    positions: Vec<usize>,
+    primed: bool,
+}
+
+
+impl<I, T> Merge<I, T> where
+    I: Iterator<Item=T>,
+    T: Clone + Ord,
+{
+    /// Create a merge of several sorted `MemoIter`s. Nothing is evaluated
+    ///     until the first item is requested.
+    pub fn new<M>(inputs: M) -> Self where
+        M: IntoIterator<Item=MemoIter<I, T>>,
+    {
+        let inputs: Vec<MemoIter<I, T>> = inputs.into_iter().collect();
+
+        Self {
+            dedup: false,
+            heads: BinaryHeap::with_capacity(inputs.len()),
+            positions: vec![0; inputs.len()],
+            inputs,
+            last: None,
+            primed: false,
+        }
+    }
+
+    /// Remove duplicates from the merged sequence, so that each distinct item
+    ///     is returned only once.
+    pub fn dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+
+    /// Return a slice of the input `MemoIter`s.
+    #[inline]
+    pub fn inputs(&self) -> &[MemoIter<I, T>] {
+        &self.inputs
+    }
+
+    /// Consume self, returning the input `MemoIter`s.
+    pub fn into_inputs(self) -> Vec<MemoIter<I, T>> {
+        self.inputs
+    }
+
+    /// Take the next item of an input into the heap, if it has one.
+    fn advance(&mut self, input: usize) {
+        let pos: usize = self.positions[input];
+
+        if let Some(item) = self.inputs[input].get(pos) {
+            self.heads.push(Reverse((item.clone(), input)));
+            self.positions[input] += 1;
+        }
+    }
+}
+
+
+impl<I, T> Iterator for Merge<I, T> where
+    I: Iterator<Item=T>,
+    T: Clone + Ord,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.primed {
+            self.primed = true;
+
+            for input in 0..self.inputs.len() {
+                self.advance(input);
+            }
+        }
+
+        loop {
+            let Reverse((item, input)) = self.heads.pop()?;
+            self.advance(input);
+
+            if self.dedup {
+                if self.last.as_ref() == Some(&item) {
+                    continue;
+                }
+
+                self.last = Some(item.clone());
+            }
+
+            break Some(item);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let inputs = vec![
+            MemoIter::new(vec![1, 4, 4, 9].into_iter()),
+            MemoIter::new(vec![].into_iter()),
+            MemoIter::new(vec![0, 4, 10].into_iter()),
+        ];
+
+        let mut merged = MemoIter::new(Merge::new(inputs));
+        assert_eq!(merged.exhaust(), [0, 1, 4, 4, 4, 9, 10]);
+
+        let inputs = vec![
+            MemoIter::new(vec![1, 4, 4, 9].into_iter()),
+            MemoIter::new(vec![0, 4, 10].into_iter()),
+        ];
+
+        let mut merged = MemoIter::new(Merge::new(inputs).dedup());
+        assert_eq!(merged.exhaust(), [0, 1, 4, 9, 10]);
+    }
+
+    #[test]
+    fn test_lazy() {
+        let inputs = [2u64, 3, 5].iter().map(|&p| {
+            MemoIter::successors(Some(1u64), move |&n| n.checked_mul(p))
+        });
+
+        let mut merged = MemoIter::new(Merge::new(inputs).dedup());
+        assert_eq!(merged.get_slice(..10), [1, 2, 3, 4, 5, 8, 9, 16, 25, 27]);
+
+        let (_, merge) = merged.consume();
+        let evaluated: Vec<usize> = merge.inputs().iter().map(MemoIter::evaluated).collect();
+        assert_eq!(evaluated, [6, 5, 4]);
+    }
+}
@@ -0,0 +1,145 @@
+//! Reporting of cache activity through the `metrics` facade, so that the
+//!     behaviour of a `MemoIter` can be observed by whichever exporter a
+//!     service has installed, such as one for Prometheus.
+//!
+//! Every access to an item by index is counted as a hit if the item was already
+//!     stored, and as a miss otherwise. When a miss causes new items to be
+//!     evaluated, the number of items and the time taken are recorded, and the
+//!     size of the cache is reported. The metrics are shared by every
+//!     `MemoIter` in the process.
+
+use ::metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit};
+use std::time::Instant;
+
+
+const HITS: &str = "memoiter_hits_total";
+const MISSES: &str = "memoiter_misses_total";
+const EVALUATIONS: &str = "memoiter_evaluations_total";
+const EVALUATION_TIME: &str = "memoiter_evaluation_seconds";
+const CACHE_SIZE: &str = "memoiter_cache_size";
+
+
+/// Describe the metrics reported by this crate to the installed recorder, so
+///     that exporters can include their units and help text. This should be
+///     called once, after the recorder is installed.
+///
+/// The metrics are:
+///
+/// `memoiter_hits_total`: Accesses to items which were already evaluated.
+///
+/// `memoiter_misses_total`: Accesses to items which were not yet evaluated.
+///
+/// `memoiter_evaluations_total`: Items evaluated from the internal Iterators.
+///
+/// `memoiter_evaluation_seconds`: A histogram of the time taken by each
+///     evaluation of new items.
+///
+/// `memoiter_cache_size`: The number of items stored by the `MemoIter` which
+///     most recently evaluated new items.
+pub fn describe_metrics() {
+    describe_counter!(HITS, Unit::Count, "Accesses to items which were already evaluated.");
+    describe_counter!(MISSES, Unit::Count, "Accesses to items which were not yet evaluated.");
+    describe_counter!(EVALUATIONS, Unit::Count, "Items evaluated from memoized Iterators.");
+    describe_histogram!(EVALUATION_TIME, Unit::Seconds, "Time taken to evaluate new items.");
+    describe_gauge!(CACHE_SIZE, Unit::Count, "Items stored by the most recently grown memo.");
+}
+
+
+/// A measurement of one access to a `MemoIter`, started before any items are
+///     evaluated for it.
+pub(crate) struct Probe {
+    before: usize,
+    started: Option<Instant>,
+}
+
+
+impl Probe {
+    /// Count an access to the item at `idx` of a sequence with `len` items
+    ///     stored, starting a timer if the access is a miss.
+    pub(crate) fn start(len: usize, idx: usize) -> Self {
+        if idx < len {
+            counter!(HITS).increment(1);
+            Self { before: len, started: None }
+        } else {
+            counter!(MISSES).increment(1);
+            Self { before: len, started: Some(Instant::now()) }
+        }
+    }
+
+    /// Finish the measurement, given the number of items now stored.
+    pub(crate) fn finish(self, len: usize) {
+        if let Some(started) = self.started {
+            if len > self.before {
+                counter!(EVALUATIONS).increment((len - self.before) as u64);
+                histogram!(EVALUATION_TIME).record(started.elapsed());
+                gauge!(CACHE_SIZE).set(len as f64);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder,
+        SharedString, with_local_recorder,
+    };
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, atomic::{AtomicU64, Ordering}},
+    };
+    use crate::MemoIter;
+    use super::*;
+
+    #[derive(Default)]
+    struct Totals(Mutex<HashMap<String, Arc<AtomicU64>>>);
+
+    impl Totals {
+        fn cell(&self, key: &str) -> Arc<AtomicU64> {
+            self.0.lock().unwrap().entry(key.to_owned()).or_default().clone()
+        }
+
+        fn value(&self, key: &str) -> u64 {
+            self.cell(key).load(Ordering::Relaxed)
+        }
+    }
+
+    impl Recorder for Totals {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.cell(key.name()))
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.cell(key.name()))
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        let totals: Totals = Totals::default();
+
+        with_local_recorder(&totals, || {
+            let mut memo = MemoIter::new(0..10);
+
+            memo.get(4);
+            memo.get(2);
+            memo.get(3);
+            memo.get(20);
+            memo.get(20);
+        });
+
+        assert_eq!(totals.value(HITS), 2);
+        assert_eq!(totals.value(MISSES), 3);
+        assert_eq!(totals.value(EVALUATIONS), 10);
+        assert_eq!(f64::from_bits(totals.value(CACHE_SIZE)), 10.0);
+    }
+}
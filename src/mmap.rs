@@ -0,0 +1,289 @@
+//! Storage of evaluated items in a memory-mapped file.
+
+use bytemuck::Pod;
+use memmap2::MmapMut;
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    path::Path,
+};
+
+
+/// The number of bytes before the first item of a file. The header holds the
+///     number of items stored, and the size of each item, as little-endian
+///     `u64`s, and is padded so that the items are well aligned.
+const HEADER: usize = 64;
+
+
+/// The number of items for which space is reserved in a new file.
+const INITIAL_CAPACITY: usize = 64;
+
+
+/// A Memoized Iterator which stores its items directly in a memory-mapped
+///     file. Because the file is paged in and out by the operating system,
+///     the sequence may grow larger than memory, and it persists between
+///     runs, while reading it is nearly as fast as reading a Vector.
+///
+/// Only plain-data types implementing `bytemuck::Pod` can be stored this way.
+///     The file grows by doubling its capacity, so it may be larger than the
+///     items it holds, and the number of items is kept in a header so that
+///     they can be restored when the file is opened again.
+///
+/// The file must not be modified by anything else while it is open, which is
+///     why `MmapMemoIter::open()` is unsafe.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::mmap::MmapMemoIter;
+///
+/// # let dir = std::env::temp_dir().join(format!("memoiter-mmap-doc-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("cubes.bin");
+/// # let _ = std::fs::remove_file(&path);
+///
+/// //  SAFETY: Nothing else has this file open.
+/// let mut memo = unsafe { MmapMemoIter::open(&path, (0u64..).map(|n| n.pow(3)))? };
+/// assert_eq!(memo.get(999), Some(&997002999));
+/// drop(memo);
+///
+/// let mut memo = unsafe { MmapMemoIter::open(&path, (0u64..).map(|n| n.pow(3)))? };
+/// assert_eq!(memo.evaluated(), 1000);
+/// assert_eq!(memo.as_slice()[10], 1000);
+/// assert_eq!(memo.get(1000), Some(&1000000000));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MmapMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    capacity: usize,
+    exhausted: bool,
+    file: File,
+    iterator: I,
+    len: usize,
+    map: MmapMut,
+    skip: usize,
+    _item: PhantomData<T>,
+}
+
+
+impl<I, T> MmapMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Pod,
+{
+    /// Open the file at `path` as storage for the items of an Iterator,
+    ///     creating it if it does not exist. Any items already stored in the
+    ///     file are restored, and the Iterator will skip the same number of
+    ///     items when it is first advanced.
+    ///
+    /// Returns an error of kind `InvalidData` if the file was written for
+    ///     items of a different size, or is too short for its header.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `T` is zero-sized, or has an alignment larger
+    ///     than 64 bytes.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated or mapped by anything else,
+    ///     including another `MmapMemoIter` in this process, for as long as
+    ///     the returned `MmapMemoIter` exists. Items are read directly from
+    ///     the mapping, so a change made elsewhere would alter an item while it
+    ///     is borrowed, which is undefined behaviour.
+    pub unsafe fn open<P>(path: P, iterator: I) -> io::Result<Self> where
+        P: AsRef<Path>,
+    {
+        assert_ne!(size_of::<T>(), 0, "zero-sized items cannot be stored");
+        assert!(align_of::<T>() <= HEADER, "items must be aligned to at most {} bytes", HEADER);
+
+        let file: File = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let size: u64 = file.metadata()?.len();
+
+        if size == 0 {
+            file.set_len((HEADER + INITIAL_CAPACITY * size_of::<T>()) as u64)?;
+        } else if size < HEADER as u64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "file is too short"));
+        }
+
+        //  SAFETY: The caller guarantees that the file is not modified by
+        //      anything else while it is mapped.
+        let mut map: MmapMut = unsafe { MmapMut::map_mut(&file)? };
+        let capacity: usize = (map.len() - HEADER) / size_of::<T>();
+
+        let len: usize = if size == 0 {
+            map[8..16].copy_from_slice(&(size_of::<T>() as u64).to_le_bytes());
+            0
+        } else {
+            let stored: [u64; 2] = bytemuck::pod_read_unaligned(&map[..16]);
+            let (len, item_size) = (u64::from_le(stored[0]), u64::from_le(stored[1]));
+
+            if item_size != size_of::<T>() as u64 || len > capacity as u64 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "file holds items of a different type",
+                ));
+            }
+
+            len as usize
+        };
+
+        Ok(Self {
+            capacity,
+            exhausted: false,
+            file,
+            iterator,
+            len,
+            map,
+            skip: len,
+            _item: PhantomData,
+        })
+    }
+
+    /// Return the number of items evaluated, including those restored.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Return a slice of the items evaluated so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        bytemuck::cast_slice(&self.map[HEADER..HEADER + self.len * size_of::<T>()])
+    }
+
+    fn push(&mut self, item: T) -> io::Result<()> {
+        if self.len == self.capacity {
+            //  NOTE: A file may hold only its header, with no room for items,
+            //      in which case doubling alone would never make room.
+            let capacity: usize = self.capacity.checked_mul(2)
+                .map(|capacity| capacity.max(INITIAL_CAPACITY))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "mapped file capacity overflow"))?;
+            let size: usize = capacity.checked_mul(size_of::<T>())
+                .and_then(|bytes| bytes.checked_add(HEADER))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "mapped file capacity overflow"))?;
+
+            self.map.flush()?;
+            self.file.set_len(size as u64)?;
+
+            //  SAFETY: As in `open()`.
+            self.map = unsafe { MmapMut::map_mut(&self.file)? };
+            self.capacity = capacity;
+        }
+
+        let start: usize = HEADER + self.len * size_of::<T>();
+        self.map[start..start + size_of::<T>()].copy_from_slice(bytemuck::bytes_of(&item));
+
+        self.len += 1;
+        self.map[..8].copy_from_slice(&(self.len as u64).to_le_bytes());
+        Ok(())
+    }
+
+    /// Evaluate the Iterator until the item at `idx` is stored. Returns an
+    ///     error if the file cannot be grown, in which case the item which
+    ///     could not be stored is lost.
+    pub fn try_expand_to_contain(&mut self, idx: usize) -> io::Result<()> {
+        if !self.exhausted && self.len <= idx && self.skip > 0 {
+            let skip: usize = std::mem::take(&mut self.skip);
+
+            if self.iterator.nth(skip - 1).is_none() {
+                self.exhausted = true;
+            }
+        }
+
+        while !self.exhausted && self.len <= idx {
+            match self.iterator.next() {
+                Some(item) => self.push(item)?,
+                None => self.exhausted = true,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the file cannot be grown to hold new items.
+    ///     Use `try_expand_to_contain()` to handle this as an error instead.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        if let Err(e) = self.try_expand_to_contain(idx) {
+            panic!("failed to grow mapped file: {}", e);
+        }
+
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.as_slice().get(idx)
+    }
+
+    /// Write any changes to the mapped file through to the storage device.
+    pub fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+    use super::*;
+
+    #[test]
+    fn test_reopen() {
+        let dir: PathBuf = std::env::temp_dir()
+            .join(format!("memoiter-mmap-test-{}", std::process::id()));
+        let path: PathBuf = dir.join("pairs.bin");
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut memo = unsafe { MmapMemoIter::open(&path, (0..300u32).map(|n| [n, n * n])) }.unwrap();
+        assert_eq!(memo.get(200), Some(&[200, 40000]));
+        memo.flush().unwrap();
+        drop(memo);
+
+        let mut memo = unsafe { MmapMemoIter::open(&path, (0..300u32).map(|n| [n, n * n])) }.unwrap();
+        assert_eq!(memo.evaluated(), 201);
+        assert_eq!(memo.get(400), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.as_slice().len(), 300);
+        assert_eq!(memo.recall(299), Some(&[299, 89401]));
+
+        assert_eq!(
+            unsafe { MmapMemoIter::open(&path, 0..10u8) }.unwrap_err().kind(),
+            io::ErrorKind::InvalidData,
+        );
+
+        //  A file holding only its header has no room for items at first.
+        let bare: PathBuf = dir.join("bare.bin");
+        let mut header: [u8; HEADER] = [0; HEADER];
+        header[8..16].copy_from_slice(&4u64.to_le_bytes());
+        fs::write(&bare, header).unwrap();
+
+        let mut memo = unsafe { MmapMemoIter::open(&bare, 0..100u32) }.unwrap();
+        assert_eq!(memo.get(70), Some(&70));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+//! Memoization of Iterators which return their items in pages, such as
+//!     paginated APIs and files read in chunks.
+
+use std::ops::Deref;
+
+
+/// A Memoized Iterator over a paged source. Each item returned by the source is
+///     a page of items, which are stored in a single flat sequence. The
+///     boundaries between pages are remembered, so that items may be
+///     retrieved either by their overall index or by their page.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::paged::PagedMemoIter;
+///
+/// let pages = vec![vec![0, 1, 2], vec![], vec![3, 4], vec![5]];
+/// let mut memo = PagedMemoIter::new(pages.into_iter());
+///
+/// assert_eq!(memo.get(3), Some(&3));
+/// assert_eq!(memo.pages_evaluated(), 3);
+///
+/// assert_eq!(memo.page_of(4), Some(2));
+/// assert_eq!(memo.get_page(1), Some(&[][..]));
+/// assert_eq!(memo.get_page(3), Some(&[5][..]));
+/// assert_eq!(memo.get_page(4), None);
+/// ```
+#[derive(Debug)]
+pub struct PagedMemoIter<I, P, T> where
+    I: Iterator<Item=P>,
+    P: IntoIterator<Item=T>,
+{
+    exhausted: bool,
+    pages: I,
+    sequence: Vec<T>,
+    starts: Vec<usize>,
+}
+
+
+impl<I, P, T> PagedMemoIter<I, P, T> where
+    I: Iterator<Item=P>,
+    P: IntoIterator<Item=T>,
+{
+    /// Create an empty `PagedMemoIter` wrapping a given Iterator of pages.
+    pub fn new(pages: I) -> Self {
+        Self {
+            exhausted: false,
+            pages,
+            sequence: Vec::new(),
+            starts: Vec::new(),
+        }
+    }
+}
+
+
+impl<I, P, T> PagedMemoIter<I, P, T> where
+    I: Iterator<Item=P>,
+    P: IntoIterator<Item=T>,
+{
+    /// Return the number of items evaluated, across all pages.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Return the number of pages evaluated.
+    #[inline]
+    pub fn pages_evaluated(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new pages.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Evaluate one more page. Returns `false` if there are no more pages.
+    fn fetch_page(&mut self) -> bool {
+        if !self.exhausted {
+            match self.pages.next() {
+                Some(page) => {
+                    self.starts.push(self.sequence.len());
+                    self.sequence.extend(page);
+                    return true;
+                }
+                None => {
+                    self.exhausted = true;
+                    self.sequence.shrink_to_fit();
+                    self.starts.shrink_to_fit();
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Retrieve, by its overall index, an item returned by the Iterator. If the
+    ///     page containing the index has not yet been evaluated, it will be, as
+    ///     will every page before it.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        while self.sequence.len() <= idx && self.fetch_page() {}
+        self.sequence.get(idx)
+    }
+
+    /// Retrieve, by its page number, a page of items returned by the Iterator.
+    ///     If the page has not yet been evaluated, it will be, as will every
+    ///     page before it.
+    pub fn get_page(&mut self, page: usize) -> Option<&[T]> {
+        while self.starts.len() <= page && self.fetch_page() {}
+
+        let start: usize = *self.starts.get(page)?;
+        let end: usize = match self.starts.get(page + 1) {
+            Some(&next) => next,
+            None => self.sequence.len(),
+        };
+
+        Some(&self.sequence[start..end])
+    }
+
+    /// Return the number of the page containing a given overall index. If the
+    ///     index has not yet been evaluated, it will be.
+    pub fn page_of(&mut self, idx: usize) -> Option<usize> {
+        self.get(idx)?;
+
+        //  NOTE: Empty pages share their start with the following page, so the
+        //      last page starting at or before the index is the one holding it.
+        Some(self.starts.partition_point(|&start| start <= idx) - 1)
+    }
+
+    /// Retrieve, by its overall index, a value returned by the Iterator. If the
+    ///     value has not yet been evaluated, it will **NOT** be evaluated now,
+    ///     and this method will return `None`.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.sequence.get(idx)
+    }
+
+    /// Consume self, returning a Tuple containing the internal stored `Vec<T>`,
+    ///     the starting index of each page within it, and the original Iterator.
+    pub fn consume(self) -> (Vec<T>, Vec<usize>, I) {
+        let Self { sequence, starts, pages, .. } = self;
+        (sequence, starts, pages)
+    }
+}
+
+
+impl<I, P, T> Deref for PagedMemoIter<I, P, T> where
+    I: Iterator<Item=P>,
+    P: IntoIterator<Item=T>,
+{
+    type Target = [T];
+
+    /// A PagedMemoIter dereferences to the slice of its stored values.
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.sequence[..]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages() {
+        let pages = (0..).map(|p: usize| (p * 10)..(p * 10 + p));
+        let mut memo = PagedMemoIter::new(pages);
+
+        //  Page zero is empty, so index zero falls within page one.
+        assert_eq!(memo.get(0), Some(&10));
+        assert_eq!(memo.page_of(0), Some(1));
+        assert_eq!(memo.pages_evaluated(), 2);
+
+        assert_eq!(memo.get_page(3), Some(&[30, 31, 32][..]));
+        assert_eq!(memo.page_of(3), Some(3));
+        assert_eq!(memo.page_of(5), Some(3));
+        assert_eq!(memo.page_of(6), Some(4));
+        assert_eq!(*memo, [10, 20, 21, 30, 31, 32, 40, 41, 42, 43]);
+
+        let (seq, starts, _) = memo.consume();
+        assert_eq!(seq.len(), 10);
+        assert_eq!(starts, [0, 0, 1, 3, 6]);
+    }
+
+    #[test]
+    fn test_exhaustion() {
+        let mut memo = PagedMemoIter::new(vec!["ab", "", "c"].into_iter().map(str::chars));
+
+        assert_eq!(memo.get(5), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.page_of(2), Some(2));
+        assert_eq!(memo.get_page(2), Some(&['c'][..]));
+        assert_eq!(memo.get_page(3), None);
+        assert_eq!(memo.recall(1), Some(&'b'));
+    }
+}
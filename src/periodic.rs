@@ -0,0 +1,233 @@
+//! Memoization of eventually periodic sequences, in memory proportional to the
+//!     length of the period.
+
+use std::ops::Deref;
+
+
+/// A Memoized Iterator which detects when its sequence becomes periodic. Once
+///     a cycle is found, no further items are evaluated or stored, and any
+///     index may be retrieved arithmetically from the stored pre-period and
+///     cycle.
+///
+/// Cycles are detected with Brent's algorithm, by finding an item equal to an
+///     earlier one. This is only correct for sequences in which each item is
+///     determined entirely by the item before it, such as the iteration of a
+///     function, `x -> f(x)`. If a sequence depends on more state than its
+///     items hold, such as the Fibonacci sequence modulo some number, the
+///     items should include that state, and can be mapped afterward.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::periodic::PeriodicMemoIter;
+///
+/// let mut digits = PeriodicMemoIter::new(
+///     std::iter::successors(Some(3u32), |&x| Some((x * x + 1) % 100)),
+/// );
+///
+/// assert_eq!(digits.get(1_000_000_000_000), Some(&5));
+/// assert_eq!(digits.preperiod(), Some(2));
+/// assert_eq!(digits.period(), Some(6));
+/// assert_eq!(digits.cycle(), Some(&[1, 2, 5, 26, 77, 30][..]));
+/// assert_eq!(digits.evaluated(), 8);
+/// ```
+#[derive(Debug)]
+pub struct PeriodicMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    cycle: Option<(usize, usize)>,
+    exhausted: bool,
+    iterator: I,
+    lam: usize,
+    power: usize,
+    sequence: Vec<T>,
+    tortoise: usize,
+}
+
+
+impl<I, T> PeriodicMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: PartialEq,
+{
+    /// Create an empty `PeriodicMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            cycle: None,
+            exhausted: false,
+            iterator,
+            lam: 1,
+            power: 1,
+            sequence: Vec::new(),
+            tortoise: 0,
+        }
+    }
+
+    /// Return the number of items evaluated and stored. This will not increase
+    ///     once a cycle has been found.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Return `true` if a cycle has been found.
+    #[inline]
+    pub fn is_periodic(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// Return the length of the cycle, if one has been found.
+    #[inline]
+    pub fn period(&self) -> Option<usize> {
+        self.cycle.map(|(_, lam)| lam)
+    }
+
+    /// Return the number of items before the start of the cycle, if one has
+    ///     been found.
+    #[inline]
+    pub fn preperiod(&self) -> Option<usize> {
+        self.cycle.map(|(mu, _)| mu)
+    }
+
+    /// Return the items of one full cycle, if one has been found.
+    pub fn cycle(&self) -> Option<&[T]> {
+        self.cycle.map(|(mu, _)| &self.sequence[mu..])
+    }
+
+    /// Evaluate one more item, checking it against the tortoise. Returns
+    ///     `false` if no more items will be evaluated.
+    fn evaluate_next(&mut self) -> bool {
+        if self.exhausted || self.cycle.is_some() {
+            return false;
+        }
+
+        match self.iterator.next() {
+            Some(next) => self.sequence.push(next),
+            None => {
+                self.exhausted = true;
+                self.sequence.shrink_to_fit();
+                return false;
+            }
+        }
+
+        let hare: usize = self.sequence.len() - 1;
+
+        if hare == 0 {
+            //  There is nothing to compare the first item to.
+        } else if self.sequence[self.tortoise] == self.sequence[hare] {
+            self.found_cycle(self.lam);
+        } else {
+            if self.power == self.lam {
+                self.tortoise = hare;
+                self.power *= 2;
+                self.lam = 0;
+            }
+
+            self.lam += 1;
+        }
+
+        true
+    }
+
+    /// Locate the start of a cycle of known length, and discard everything
+    ///     stored after the end of its first repetition.
+    fn found_cycle(&mut self, lam: usize) {
+        let mu: usize = (0..)
+            .find(|&mu| self.sequence[mu] == self.sequence[mu + lam])
+            .unwrap();
+
+        self.sequence.truncate(mu + lam);
+        self.sequence.shrink_to_fit();
+        self.cycle = Some((mu, lam));
+    }
+
+    /// Retrieve, by its index, a value in the sequence. If the value at the
+    ///     index given has not yet been evaluated, it will be, unless a cycle
+    ///     is found first. Returns `None` if the internal Iterator terminates
+    ///     before reaching the given index.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        while self.sequence.len() <= idx && self.evaluate_next() {}
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value in the sequence. If the value has not
+    ///     been evaluated, and no cycle has been found, it will **NOT** be
+    ///     evaluated now, and this method will return `None`.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        match self.cycle {
+            Some((mu, lam)) if idx >= mu => self.sequence.get(mu + (idx - mu) % lam),
+            _ => self.sequence.get(idx),
+        }
+    }
+
+    /// Evaluate until either a cycle is found or the internal Iterator ends,
+    ///     returning the period if there is one. This method will not return
+    ///     if the sequence is infinite and never repeats.
+    pub fn find_cycle(&mut self) -> Option<usize> {
+        while self.evaluate_next() {}
+        self.period()
+    }
+}
+
+
+impl<I, T> Deref for PeriodicMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    type Target = [T];
+
+    /// A PeriodicMemoIter dereferences to the slice of its stored values. Once
+    ///     a cycle is found, this is the pre-period followed by one cycle.
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.sequence[..]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::iter::successors;
+    use super::*;
+
+    #[test]
+    fn test_periodic() {
+        let step = |&x: &u64| Some((x * 7_919 + 3) % 10_007 % 1_000);
+        let mut memo = PeriodicMemoIter::new(successors(Some(1), step));
+
+        let naive: Vec<u64> = successors(Some(1), step).take(5_000).collect();
+
+        assert_eq!(memo.get(4_999), Some(&naive[4_999]));
+        assert!(memo.is_periodic());
+
+        for (idx, value) in naive.iter().enumerate() {
+            assert_eq!(memo.get(idx), Some(value));
+        }
+
+        let (mu, lam) = (memo.preperiod().unwrap(), memo.period().unwrap());
+        assert_eq!(memo.len(), mu + lam);
+        assert_eq!(naive[mu], naive[mu + lam]);
+        assert!(mu == 0 || naive[mu - 1] != naive[mu - 1 + lam]);
+        assert!(memo.get(usize::MAX).is_some());
+    }
+
+    #[test]
+    fn test_aperiodic() {
+        let mut memo = PeriodicMemoIter::new(0..10);
+
+        assert_eq!(memo.find_cycle(), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.get(9), Some(&9));
+        assert_eq!(memo.get(10), None);
+
+        let mut constant = PeriodicMemoIter::new(std::iter::repeat(5));
+        assert_eq!(constant.find_cycle(), Some(1));
+        assert_eq!(constant.get(100), Some(&5));
+        assert_eq!(*constant, [5]);
+    }
+}
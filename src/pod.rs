@@ -0,0 +1,86 @@
+//! Conversion of sequences of plain-data items to and from raw bytes.
+
+use bytemuck::{Pod, PodCastError};
+use crate::MemoIter;
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: Pod,
+{
+    /// Return the items evaluated so far as their raw bytes, without copying,
+    ///     so that they can be written to a binary file or a network buffer
+    ///     directly. The bytes are in the native layout of `T`, including its
+    ///     byte order.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.sequence)
+    }
+
+    /// Create a `MemoIter` whose first items are read from raw bytes, as
+    ///     written from `MemoIter::as_bytes()`, and which continues with the
+    ///     items of `iterator` after them. The Iterator will skip the same
+    ///     number of items as were read, when it is first advanced.
+    ///
+    /// The bytes need not be aligned for `T`, and are copied once into the
+    ///     storage of the `MemoIter`. Returns an error if their length is not a
+    ///     multiple of the size of `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((0u32..).map(|n| n * 3));
+    /// memo.get(3);
+    ///
+    /// let bytes: Vec<u8> = memo.as_bytes().to_vec();
+    /// assert_eq!(bytes.len(), 16);
+    ///
+    /// let mut restored = MemoIter::from_bytes_seed((0u32..).map(|n| n * 3), &bytes).unwrap();
+    /// assert_eq!(restored.evaluated(), 4);
+    /// assert_eq!(restored.get(5), Some(&15));
+    ///
+    /// assert!(MemoIter::from_bytes_seed(0u32.., &bytes[..3]).is_err());
+    /// ```
+    pub fn from_bytes_seed(iterator: I, bytes: &[u8]) -> Result<Self, PodCastError> {
+        let size: usize = std::mem::size_of::<T>();
+
+        if size == 0 || bytes.len() % size != 0 {
+            return Err(PodCastError::OutputSliceWouldHaveSlop);
+        }
+
+        let sequence: Vec<T> = bytes.chunks_exact(size)
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        let mut memo: Self = Self::with_vec(iterator, sequence);
+
+        memo.skip = memo.sequence.len();
+        Ok(memo)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_unaligned_seed() {
+        let mut memo = MemoIter::new((0..20u64).map(|n| n << 40));
+        memo.get(9);
+
+        let mut buffer: Vec<u8> = vec![0xFF];
+        buffer.extend_from_slice(memo.as_bytes());
+
+        let source = CountingIter::new((0..20u64).map(|n| n << 40));
+        let counter = source.counter();
+        let mut restored = MemoIter::from_bytes_seed(source, &buffer[1..]).unwrap();
+
+        assert!(restored.diff(&memo).is_identical());
+        assert_eq!(counter.times_advanced(), 0);
+        assert_eq!(restored.exhaust().len(), 20);
+        assert_eq!(restored[19], 19 << 40);
+    }
+}
@@ -0,0 +1,184 @@
+//! Lazy pointwise combination of memoized sequences.
+
+use std::ops::{Add, Mul, Sub};
+use crate::MemoIter;
+
+
+/// An Iterator over the results of a function applied to the items at each
+///     index of two `MemoIter`s. Wrapping it in a `MemoIter` produces a derived
+///     sequence, whose items are computed on demand and then stored, and whose
+///     parents are evaluated only as far as needed.
+///
+/// The sequence ends when either parent ends. It is not fused, because a parent
+///     marked with `set_fused(false)` may return more items after ending, and
+///     so may this. Such a sequence is usually made
+///     with `MemoIter::combine_with()`, or with one of the arithmetic
+///     shorthands for it, such as `MemoIter::add_memo()`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+///
+/// let squares = MemoIter::new((0..).map(|n: u32| n * n));
+/// let cubes = MemoIter::new((0..).map(|n: u32| n * n * n));
+///
+/// let mut sums = squares.add_memo(cubes);
+/// assert_eq!(sums.get(3), Some(&36));
+///
+/// let (_, pointwise) = sums.consume();
+/// assert_eq!(pointwise.left().evaluated(), 4);
+/// ```
+#[derive(Debug)]
+pub struct Pointwise<A, B, TA, TB, F> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+{
+    func: F,
+    idx: usize,
+    left: MemoIter<A, TA>,
+    right: MemoIter<B, TB>,
+}
+
+
+impl<A, B, TA, TB, F> Pointwise<A, B, TA, TB, F> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+{
+    /// Combine two `MemoIter`s with a function of the items at each index.
+    ///     Nothing is evaluated until the first item is requested.
+    pub fn new(left: MemoIter<A, TA>, right: MemoIter<B, TB>, func: F) -> Self {
+        Self { func, idx: 0, left, right }
+    }
+
+    /// Return a reference to the `MemoIter` providing the first argument of
+    ///     each call to the function.
+    #[inline]
+    pub fn left(&self) -> &MemoIter<A, TA> {
+        &self.left
+    }
+
+    /// Return a reference to the `MemoIter` providing the second argument of
+    ///     each call to the function.
+    #[inline]
+    pub fn right(&self) -> &MemoIter<B, TB> {
+        &self.right
+    }
+
+    /// Consume self, returning both parent `MemoIter`s.
+    pub fn into_parts(self) -> (MemoIter<A, TA>, MemoIter<B, TB>) {
+        (self.left, self.right)
+    }
+}
+
+
+impl<A, B, TA, TB, F, U> Iterator for Pointwise<A, B, TA, TB, F> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+    F: FnMut(&TA, &TB) -> U,
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a: &TA = self.left.get(self.idx)?;
+        let b: &TB = self.right.get(self.idx)?;
+
+        self.idx += 1;
+        Some((self.func)(a, b))
+    }
+}
+
+
+/// A `MemoIter` over the pointwise combination of two others, using a function
+///     pointer, as returned by the arithmetic combinators.
+pub type PointwiseMemo<A, B, TA, TB, U> = MemoIter<Pointwise<A, B, TA, TB, fn(&TA, &TB) -> U>, U>;
+
+
+impl<A, TA> MemoIter<A, TA> where
+    A: Iterator<Item=TA>,
+{
+    /// Combine this `MemoIter` with another, producing a `MemoIter` whose item
+    ///     at each index is the result of a function of the items at that index
+    ///     of both. Items of the parents are evaluated only when an item of the
+    ///     result requires them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let names = MemoIter::new(["Ada", "Alan", "Grace"].iter().copied());
+    /// let years = MemoIter::new([1815, 1912, 1906].iter().copied());
+    ///
+    /// let mut labels = names.combine_with(years, |name, year| format!("{} ({})", name, year));
+    ///
+    /// assert_eq!(labels.get(1).map(String::as_str), Some("Alan (1912)"));
+    /// assert_eq!(labels.exhaust().len(), 3);
+    /// ```
+    pub fn combine_with<B, TB, F, U>(
+        self,
+        other: MemoIter<B, TB>,
+        func: F,
+    ) -> MemoIter<Pointwise<A, B, TA, TB, F>, U> where
+        B: Iterator<Item=TB>,
+        F: FnMut(&TA, &TB) -> U,
+    {
+        MemoIter::new(Pointwise::new(self, other, func))
+    }
+
+    /// Combine this `MemoIter` with another by adding the items at each index.
+    pub fn add_memo<B, TB, U>(self, other: MemoIter<B, TB>) -> PointwiseMemo<A, B, TA, TB, U> where
+        B: Iterator<Item=TB>,
+        TA: Clone + Add<TB, Output=U>,
+        TB: Clone,
+    {
+        self.combine_with(other, |a, b| a.clone() + b.clone())
+    }
+
+    /// Combine this `MemoIter` with another by subtracting the items of the
+    ///     other from the items of this one at each index.
+    pub fn sub_memo<B, TB, U>(self, other: MemoIter<B, TB>) -> PointwiseMemo<A, B, TA, TB, U> where
+        B: Iterator<Item=TB>,
+        TA: Clone + Sub<TB, Output=U>,
+        TB: Clone,
+    {
+        self.combine_with(other, |a, b| a.clone() - b.clone())
+    }
+
+    /// Combine this `MemoIter` with another by multiplying the items at each
+    ///     index.
+    pub fn mul_memo<B, TB, U>(self, other: MemoIter<B, TB>) -> PointwiseMemo<A, B, TA, TB, U> where
+        B: Iterator<Item=TB>,
+        TA: Clone + Mul<TB, Output=U>,
+        TB: Clone,
+    {
+        self.combine_with(other, |a, b| a.clone() * b.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_pointwise() {
+        let left = CountingIter::new(0..);
+        let right = CountingIter::new(10..14);
+        let (left_count, right_count) = (left.counter(), right.counter());
+
+        let mut products = MemoIter::new(left).mul_memo(MemoIter::new(right));
+
+        assert_eq!(products.get(2), Some(&24));
+        assert_eq!(left_count.times_advanced(), 3);
+        assert_eq!(right_count.times_advanced(), 3);
+
+        assert_eq!(products.exhaust(), [0, 11, 24, 39]);
+        assert_eq!(left_count.times_advanced(), 5);
+
+        let differences = MemoIter::new(vec![5, 5, 5].into_iter())
+            .sub_memo(MemoIter::new(1..));
+        assert_eq!(differences.collect::<Vec<i32>>(), [4, 3, 2]);
+    }
+}
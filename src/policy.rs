@@ -0,0 +1,132 @@
+//! Policies governing the evaluation of the Iterator within a `MemoIter`.
+
+use std::{
+    ops::ControlFlow,
+    sync::{Arc, atomic::{AtomicBool, Ordering}},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+
+/// A policy consulted by a `MemoIter` before each evaluation of its internal
+///     Iterator. This is implemented for all closures taking the index about
+///     to be evaluated.
+pub trait EvalPolicy {
+    /// Called before the item at index `idx` is evaluated. This may block to
+    ///     delay the evaluation, or return `ControlFlow::Break` to prevent it.
+    fn before_next(&mut self, idx: usize) -> ControlFlow<()>;
+}
+
+
+impl<F> EvalPolicy for F where
+    F: FnMut(usize) -> ControlFlow<()>,
+{
+    #[inline]
+    fn before_next(&mut self, idx: usize) -> ControlFlow<()> {
+        self(idx)
+    }
+}
+
+
+/// A token bucket rate limiter. Each evaluation consumes one token, and tokens
+///     are replenished at a fixed rate, up to a maximum burst size. When no
+///     token is available, evaluation is delayed until one is.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, policy::TokenBucket};
+/// use std::time::{Duration, Instant};
+///
+/// let mut memo = MemoIter::new(0..);
+/// memo.set_policy(TokenBucket::new(100.0, 5));
+///
+/// let start = Instant::now();
+/// memo.get(9);
+///
+/// //  The first five are a burst; the next five are limited to 100 per second.
+/// assert!(start.elapsed() >= Duration::from_millis(40));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    burst: f64,
+    last: Instant,
+    rate: f64,
+    tokens: f64,
+}
+
+
+impl TokenBucket {
+    /// Create a full token bucket, which replenishes `rate` tokens per second
+    ///     and holds no more than `burst` tokens at once.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `rate` is not positive and finite.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        assert!(rate.is_finite() && rate > 0.0, "rate must be positive");
+        let burst: f64 = burst.max(1) as f64;
+
+        Self {
+            burst,
+            last: Instant::now(),
+            rate,
+            tokens: burst,
+        }
+    }
+
+    fn refill(&mut self) {
+        let now: Instant = Instant::now();
+        let elapsed: f64 = now.duration_since(self.last).as_secs_f64();
+
+        self.last = now;
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+    }
+}
+
+
+impl EvalPolicy for TokenBucket {
+    fn before_next(&mut self, _idx: usize) -> ControlFlow<()> {
+        self.refill();
+
+        if self.tokens < 1.0 {
+            sleep(Duration::from_secs_f64((1.0 - self.tokens) / self.rate));
+            self.refill();
+        }
+
+        self.tokens = (self.tokens - 1.0).max(0.0);
+        ControlFlow::Continue(())
+    }
+}
+
+
+/// A shared flag which may be used to cancel evaluation from another thread.
+///     Clones of a token share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+
+impl CancelToken {
+    /// Create a new token, which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel any evaluation checking this token.
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Return `true` if this token has been cancelled.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Clear the cancellation of this token, allowing evaluation to resume.
+    #[inline]
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,230 @@
+//! A keyed family of Memoized Iterators, created lazily as they are needed.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    hash::Hash,
+};
+use crate::{lru::Recency, MemoIter};
+
+
+/// A collection of `MemoIter`s, one for each key, each created from its key by
+///     a factory function the first time it is used. This suits families of
+///     related sequences, such as one row of binomial coefficients per `n`, or
+///     one random stream per seed.
+///
+/// A pool may be given a budget, limiting the number of memos kept at once.
+///     When the budget is exceeded, the least recently used memo is evicted,
+///     and all of its evaluated items are lost; if its key is used again, a new
+///     memo will be created for it by the factory.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::pool::MemoPool;
+///
+/// //  One row of Pascal's Triangle per key.
+/// let mut rows = MemoPool::new(|&n: &u64| {
+///     (0..=n).scan(1u64, move |c, k| {
+///         let value: u64 = *c;
+///         *c = *c * (n - k) / (k + 1);
+///         Some(value)
+///     })
+/// });
+///
+/// assert_eq!(rows.get(&4, 2), Some(&6));
+/// assert_eq!(rows.get(&10, 3), Some(&120));
+/// assert_eq!(rows.get(&4, 5), None);
+/// assert_eq!(rows.len(), 2);
+///
+/// assert_eq!(rows.memo(&4).evaluated(), 5);
+/// assert_eq!(rows.created(), 2);
+/// ```
+pub struct MemoPool<K, F, I, T> where
+    K: Clone + Eq + Hash,
+    F: Fn(&K) -> I,
+    I: Iterator<Item=T>,
+{
+    budget: Option<usize>,
+    created: usize,
+    factory: F,
+    memos: HashMap<K, MemoIter<I, T>>,
+    recency: Recency<K>,
+}
+
+
+impl<K, F, I, T> MemoPool<K, F, I, T> where
+    K: Clone + Eq + Hash,
+    F: Fn(&K) -> I,
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `MemoPool` using a given factory function. Memos will
+    ///     never be evicted.
+    pub fn new(factory: F) -> Self {
+        Self {
+            budget: None,
+            created: 0,
+            factory,
+            memos: HashMap::new(),
+            recency: Recency::new(),
+        }
+    }
+
+    /// Create an empty `MemoPool` using a given factory function, which will
+    ///     keep no more than `budget` memos at once. A budget of zero is
+    ///     treated as a budget of one.
+    pub fn with_budget(budget: usize, factory: F) -> Self {
+        let mut new = Self::new(factory);
+        new.budget = Some(budget.max(1));
+        new
+    }
+
+    /// Return the maximum number of memos kept at once, if there is one.
+    #[inline]
+    pub fn budget(&self) -> Option<usize> {
+        self.budget
+    }
+
+    /// Change the maximum number of memos kept at once. If the new budget is
+    ///     lower than the current number of memos, the least recently used
+    ///     memos are evicted immediately.
+    pub fn set_budget(&mut self, budget: Option<usize>) {
+        self.budget = budget.map(|b| b.max(1));
+        self.enforce_budget();
+    }
+
+    /// Return the number of times the factory function has been called. This
+    ///     includes recreations of evicted memos.
+    #[inline]
+    pub fn created(&self) -> usize {
+        self.created
+    }
+
+    /// Return the number of memos currently held.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.memos.len()
+    }
+
+    /// Return `true` if no memos are currently held.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.memos.is_empty()
+    }
+
+    /// Return `true` if a memo for the given key is currently held.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self.memos.contains_key(key)
+    }
+
+    /// Retrieve the memo for a given key. If it is not currently held, it will
+    ///     be created, possibly evicting another memo.
+    pub fn memo(&mut self, key: &K) -> &mut MemoIter<I, T> {
+        if !self.memos.contains_key(key) {
+            let memo: MemoIter<I, T> = MemoIter::new((self.factory)(key));
+
+            self.created += 1;
+            self.memos.insert(key.clone(), memo);
+        }
+
+        self.recency.touch(key);
+        self.enforce_budget();
+        self.memos.get_mut(key).unwrap()
+    }
+
+    /// Retrieve the memo for a given key, only if it is currently held. This
+    ///     does **not** count as a use of the memo for the purposes of
+    ///     eviction.
+    pub fn peek(&self, key: &K) -> Option<&MemoIter<I, T>> {
+        self.memos.get(key)
+    }
+
+    /// Retrieve, by its index, an item of the sequence for a given key. The
+    ///     memo is created if needed, and evaluated until it contains the item.
+    pub fn get(&mut self, key: &K, idx: usize) -> Option<&T> {
+        self.memo(key).get(idx)
+    }
+
+    /// Retrieve, by its index, an item of the sequence for a given key, only
+    ///     if it has already been evaluated. No memo is created, and this does
+    ///     **not** count as a use of the memo for the purposes of eviction.
+    pub fn recall(&self, key: &K, idx: usize) -> Option<&T> {
+        self.memos.get(key)?.as_ref().get(idx)
+    }
+
+    /// Remove the memo for a given key, returning it if it was present.
+    pub fn evict(&mut self, key: &K) -> Option<MemoIter<I, T>> {
+        self.recency.forget(key);
+        self.memos.remove(key)
+    }
+
+    /// Remove all memos.
+    pub fn clear(&mut self) {
+        self.recency.clear();
+        self.memos.clear();
+    }
+
+    /// Return an Iterator over the keys of the memos currently held, in no
+    ///     particular order.
+    pub fn keys(&self) -> impl Iterator<Item=&K> {
+        self.memos.keys()
+    }
+
+    fn enforce_budget(&mut self) {
+        if let Some(budget) = self.budget {
+            while self.memos.len() > budget {
+                match self.recency.pop_oldest() {
+                    Some(oldest) => { self.memos.remove(&oldest); }
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+
+impl<K, F, I, T> Debug for MemoPool<K, F, I, T> where
+    K: Clone + Debug + Eq + Hash,
+    F: Fn(&K) -> I,
+    I: Iterator<Item=T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoPool")
+            .field("budget", &self.budget)
+            .field("created", &self.created)
+            .field("keys", &self.memos.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction() {
+        let mut pool = MemoPool::with_budget(2, |&step: &usize| (0..).step_by(step));
+
+        assert_eq!(pool.get(&2, 3), Some(&6));
+        assert_eq!(pool.get(&3, 3), Some(&9));
+        assert_eq!(pool.get(&2, 5), Some(&10));
+        assert_eq!(pool.get(&5, 1), Some(&5));
+
+        assert!(pool.contains(&2));
+        assert!(!pool.contains(&3));
+        assert_eq!(pool.recall(&2, 5), Some(&10));
+        assert_eq!(pool.recall(&5, 2), None);
+        assert_eq!(pool.created(), 3);
+
+        assert_eq!(pool.get(&3, 0), Some(&0));
+        assert_eq!(pool.created(), 4);
+        assert!(!pool.contains(&2));
+
+        pool.set_budget(Some(1));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.evict(&3).map(|m| m.evaluated()), Some(1));
+        assert!(pool.is_empty());
+    }
+}
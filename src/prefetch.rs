@@ -0,0 +1,113 @@
+//! Hints of which items will be needed soon, to be evaluated in idle time.
+
+use crate::MemoIter;
+use std::collections::BTreeSet;
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Announce indices which will be needed soon. Nothing is evaluated now;
+    ///     instead, `MemoIter::prefetch()` may be called when there is time to
+    ///     spare, such as while an interactive program waits for input, so
+    ///     that the hinted items are ready before they are demanded.
+    ///
+    /// Indices which have already been evaluated are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((0u64..).map(|n| n * n));
+    /// memo.hint_upcoming(vec![40, 10]);
+    ///
+    /// //  Spend idle time evaluating, 16 items at a time.
+    /// assert_eq!(memo.prefetch(16), 16);
+    /// assert_eq!(memo.pending_hints(), 1);
+    /// assert_eq!(memo.prefetch(16), 16);
+    /// assert_eq!(memo.prefetch(16), 9);
+    /// assert_eq!(memo.prefetch(16), 0);
+    ///
+    /// assert_eq!(memo.evaluated(), 41);
+    /// ```
+    pub fn hint_upcoming<H>(&mut self, indices: H) where
+        H: IntoIterator<Item=usize>,
+    {
+        if !self.exhausted {
+            let len: usize = self.sequence.len();
+            self.hints.extend(indices.into_iter().filter(|&idx| idx >= len));
+        }
+    }
+
+    /// Return the number of hinted indices which have not yet been evaluated.
+    pub fn pending_hints(&self) -> usize {
+        self.hints.range(self.sequence.len()..).count()
+    }
+
+    /// Discard all hints.
+    pub fn clear_hints(&mut self) {
+        self.hints.clear();
+    }
+
+    /// Evaluate at most `budget` items toward the hinted indices, nearest
+    ///     first, returning the number of items evaluated. Returns zero when
+    ///     no hints are pending.
+    ///
+    /// Hints are discarded as they are satisfied, whether by this method or by
+    ///     any other evaluation, and all hints are discarded if the Iterator
+    ///     ends.
+    pub fn prefetch(&mut self, budget: usize) -> usize {
+        let start: usize = self.sequence.len();
+        let limit: usize = start.saturating_add(budget);
+
+        while self.sequence.len() < limit {
+            let len: usize = self.sequence.len();
+            let target: usize = match self.hints.range(len..).next() {
+                Some(&target) => target.min(limit - 1),
+                None => break,
+            };
+
+            self.expand_to_contain(target);
+
+            if self.sequence.len() <= target {
+                break;
+            }
+        }
+
+        if self.exhausted {
+            self.hints.clear();
+        } else {
+            let remaining: BTreeSet<usize> = self.hints.split_off(&self.sequence.len());
+            self.hints = remaining;
+        }
+
+        self.sequence.len() - start
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_prefetch() {
+        let iter = CountingIter::new(0..30);
+        let counter = iter.counter();
+        let mut memo = MemoIter::new(iter);
+
+        memo.hint_upcoming(vec![5, 12, 50]);
+        assert_eq!(memo.get(8), Some(&8));
+        assert_eq!(memo.pending_hints(), 2);
+
+        assert_eq!(memo.prefetch(100), 21);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.pending_hints(), 0);
+        assert_eq!(counter.times_advanced(), 31);
+
+        memo.hint_upcoming(vec![40]);
+        assert_eq!(memo.prefetch(100), 0);
+    }
+}
@@ -0,0 +1,167 @@
+//! Lazy Cartesian products of memoized sequences.
+
+use crate::MemoIter;
+
+
+/// An Iterator over every pair of items from two `MemoIter`s, enumerated in
+///     diagonal order. Wrapping it in a `MemoIter` produces a memoized product
+///     whose parents are evaluated only as far as needed.
+///
+/// Pairs are ordered by the sum of their indices, `i + j`, and then by `i`:
+///     `(0, 0)`, `(0, 1)`, `(1, 0)`, `(0, 2)`, `(1, 1)`, `(2, 0)`, and so on.
+///     Every pair is reached after finitely many steps, even if both parents
+///     are infinite. If either parent is finite, the pairs it cannot complete
+///     are skipped once it is exhausted.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, product::Product};
+///
+/// let letters = MemoIter::new("abc".chars());
+/// let numbers = MemoIter::new(1..);
+///
+/// let mut pairs = MemoIter::new(Product::new(letters, numbers));
+///
+/// assert_eq!(
+///     pairs.get_slice(..6),
+///     [('a', 1), ('a', 2), ('b', 1), ('a', 3), ('b', 2), ('c', 1)],
+/// );
+///
+/// let (_, product) = pairs.consume();
+/// assert_eq!(product.left().evaluated(), 3);
+/// assert_eq!(product.right().evaluated(), 3);
+/// ```
+#[derive(Debug)]
+pub struct Product<A, B, TA, TB> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+{
+    diagonal: usize,
+    i: usize,
+    left: MemoIter<A, TA>,
+    right: MemoIter<B, TB>,
+}
+
+
+impl<A, B, TA, TB> Product<A, B, TA, TB> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+{
+    /// Create the product of two `MemoIter`s. Nothing is evaluated until the
+    ///     first pair is requested.
+    pub fn new(left: MemoIter<A, TA>, right: MemoIter<B, TB>) -> Self {
+        Self { diagonal: 0, i: 0, left, right }
+    }
+
+    /// Return a reference to the `MemoIter` providing the first item of each
+    ///     pair.
+    #[inline]
+    pub fn left(&self) -> &MemoIter<A, TA> {
+        &self.left
+    }
+
+    /// Return a reference to the `MemoIter` providing the second item of each
+    ///     pair.
+    #[inline]
+    pub fn right(&self) -> &MemoIter<B, TB> {
+        &self.right
+    }
+
+    /// Consume self, returning both parent `MemoIter`s.
+    pub fn into_parts(self) -> (MemoIter<A, TA>, MemoIter<B, TB>) {
+        (self.left, self.right)
+    }
+}
+
+
+/// Return the known length of a `MemoIter`, if it has been exhausted.
+#[inline]
+fn known_len<I: Iterator<Item=T>, T>(memo: &MemoIter<I, T>) -> Option<usize> {
+    if memo.is_exhausted() { Some(memo.evaluated()) } else { None }
+}
+
+
+impl<A, B, TA, TB> Iterator for Product<A, B, TA, TB> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+    TA: Clone,
+    TB: Clone,
+{
+    type Item = (TA, TB);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let la: Option<usize> = known_len(&self.left);
+            let lb: Option<usize> = known_len(&self.right);
+
+            if la == Some(0) || lb == Some(0) {
+                return None;
+            }
+
+            let d: usize = self.diagonal;
+            let lo: usize = lb.map_or(0, |lb| d.saturating_sub(lb - 1));
+            let hi: usize = la.map_or(d, |la| d.min(la - 1));
+
+            if let (Some(la), Some(lb)) = (la, lb) {
+                if d > la + lb - 2 {
+                    return None;
+                }
+            }
+
+            if self.i < lo {
+                self.i = lo;
+            }
+
+            if self.i > hi {
+                self.diagonal += 1;
+                self.i = 0;
+                continue;
+            }
+
+            let i: usize = self.i;
+            self.i += 1;
+
+            let a: Option<TA> = self.left.get(i).cloned();
+            let b: Option<TB> = self.right.get(d - i).cloned();
+
+            if let (Some(a), Some(b)) = (a, b) {
+                return Some((a, b));
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finite() {
+        let left = MemoIter::new(0..2);
+        let right = MemoIter::new(10..13);
+        let mut pairs = MemoIter::new(Product::new(left, right));
+
+        assert_eq!(pairs.exhaust(), [
+            (0, 10), (0, 11), (1, 10), (0, 12), (1, 11), (1, 12),
+        ]);
+
+        let empty = MemoIter::new(0..0);
+        let mut pairs = MemoIter::new(Product::new(empty, MemoIter::new(0..)));
+        assert_eq!(pairs.get(0), None);
+    }
+
+    #[test]
+    fn test_skip_finite_side() {
+        let left = MemoIter::new(0..1);
+        let right = MemoIter::new(0..);
+        let mut pairs = MemoIter::new(Product::new(left, right));
+
+        assert_eq!(pairs.get(1_000), Some(&(0, 1_000)));
+
+        let (_, product) = pairs.consume();
+        assert_eq!(product.left().evaluated(), 1);
+        assert_eq!(product.right().evaluated(), 1_001);
+    }
+}
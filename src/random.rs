@@ -0,0 +1,245 @@
+//! Memoization of random number streams, so that the same values can be
+//!     retrieved repeatedly by their position in the stream.
+//!
+//! Two approaches are provided. A `RandomMemo` stores the output of a seeded
+//!     sequential RNG, so that `get(n)` always returns the `n`th value the RNG
+//!     produced. Reaching a high index requires every value before it to be
+//!     generated and stored, as with any other `MemoIter`.
+//!
+//! A `CounterStream` instead uses a counter-based RNG, which can compute the
+//!     `n`th value directly from the seed. This allows sparse random access,
+//!     with nothing to store and no gap to fill.
+
+use rand::{RngCore, SeedableRng};
+use std::iter::FusedIterator;
+use crate::MemoIter;
+
+
+/// A `MemoIter` storing the output of a sequential RNG.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::random::{RandomMemo, SplitMix64};
+///
+/// let mut a: RandomMemo<SplitMix64> = RandomMemo::from_seed(42);
+/// let mut b: RandomMemo<SplitMix64> = RandomMemo::from_seed(42);
+///
+/// let x: u64 = *a.get(1000).unwrap();
+///
+/// assert_eq!(a.get(1000), Some(&x));
+/// assert_eq!(b.get(1000), Some(&x));
+/// ```
+pub type RandomMemo<R> = MemoIter<RngIter<R>, u64>;
+
+
+impl<R: RngCore> MemoIter<RngIter<R>, u64> {
+    /// Create an empty `MemoIter` storing the output of a given RNG.
+    pub fn from_rng(rng: R) -> Self {
+        Self::new(RngIter(rng))
+    }
+}
+
+
+impl<R: RngCore + SeedableRng> MemoIter<RngIter<R>, u64> {
+    /// Create an empty `MemoIter` storing the output of an RNG seeded with a
+    ///     given value.
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_rng(R::seed_from_u64(seed))
+    }
+}
+
+
+/// An infinite Iterator over the output of a sequential RNG.
+#[derive(Clone, Debug)]
+pub struct RngIter<R>(pub R);
+
+
+impl<R: RngCore> Iterator for RngIter<R> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.0.next_u64())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+
+impl<R: RngCore> FusedIterator for RngIter<R> {}
+
+
+/// An RNG which can compute any value in its output stream directly from its
+///     position, without computing the values before it.
+pub trait CounterRng {
+    /// Return the value at a given position in the output stream.
+    fn generate(&self, counter: u64) -> u64;
+}
+
+
+/// A seeded stream of random values which supports random access, without
+///     storing anything.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::random::{CounterStream, SplitMix64};
+///
+/// let stream = CounterStream::new(SplitMix64::new(42));
+/// let far: u64 = stream.get(1 << 40);
+///
+/// assert_eq!(stream.get(1 << 40), far);
+/// assert_eq!(stream.iter().nth(3), Some(stream.get(3)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CounterStream<C> {
+    rng: C,
+}
+
+
+impl<C: CounterRng> CounterStream<C> {
+    /// Create a stream of values from a counter-based RNG.
+    pub fn new(rng: C) -> Self {
+        Self { rng }
+    }
+
+    /// Return the value at a given position in the stream.
+    #[inline]
+    pub fn get(&self, idx: u64) -> u64 {
+        self.rng.generate(idx)
+    }
+
+    /// Return an Iterator over the stream, from the start. Because the values
+    ///     are computed by position, this may be passed into a `MemoIter` to
+    ///     be stored, and will produce the same values as `get()`.
+    pub fn iter(&self) -> CounterIter<C> where C: Clone {
+        CounterIter {
+            counter: 0,
+            rng: self.rng.clone(),
+        }
+    }
+}
+
+
+/// An Iterator over the values of a `CounterStream`, in order.
+#[derive(Clone, Debug)]
+pub struct CounterIter<C> {
+    counter: u64,
+    rng: C,
+}
+
+
+impl<C: CounterRng> Iterator for CounterIter<C> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value: u64 = self.rng.generate(self.counter);
+        self.counter = self.counter.wrapping_add(1);
+        Some(value)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.counter = self.counter.wrapping_add(n as u64);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+
+impl<C: CounterRng> FusedIterator for CounterIter<C> {}
+
+
+/// The SplitMix64 generator. Its output is a hash of a counter, so it may be
+///     used either sequentially, as an `RngCore`, or by position, as a
+///     `CounterRng`.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    seed: u64,
+    state: u64,
+}
+
+
+impl SplitMix64 {
+    const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    /// Create a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: 0 }
+    }
+}
+
+
+impl CounterRng for SplitMix64 {
+    fn generate(&self, counter: u64) -> u64 {
+        let mut z: u64 = self.seed
+            .wrapping_add(counter.wrapping_add(1).wrapping_mul(Self::GAMMA));
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+
+impl RngCore for SplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value: u64 = self.generate(self.state);
+        self.state = self.state.wrapping_add(1);
+        value
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        rand::rand_core::impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+
+    fn seed_from_u64(state: u64) -> Self {
+        Self::new(state)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let mut a: RandomMemo<SplitMix64> = RandomMemo::from_seed(7);
+        let mut b: RandomMemo<SplitMix64> = RandomMemo::from_seed(7);
+        let mut c: RandomMemo<SplitMix64> = RandomMemo::from_seed(8);
+
+        assert_eq!(a.get_slice(..100), b.get_slice(..100));
+        assert_ne!(a.get_slice(..100), c.get_slice(..100));
+
+        //  The sequential and counter-based views of the same generator must
+        //      agree with each other.
+        let stream = CounterStream::new(SplitMix64::new(7));
+        assert_eq!(stream.get(50), a[50]);
+
+        let mut memo = MemoIter::new(stream.iter());
+        assert_eq!(memo.get_slice(..100), &a[..100]);
+    }
+}
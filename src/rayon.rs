@@ -0,0 +1,64 @@
+//! Implementations of `rayon` traits for `MemoIter`, allowing a finite table to
+//!     be precomputed in parallel.
+
+use ::rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend};
+use std::iter::Empty;
+use crate::MemoIter;
+
+
+/// Collect a parallel Iterator into an exhausted `MemoIter`, preserving the
+///     order of its items.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+/// use rayon::prelude::*;
+/// use std::iter::Empty;
+///
+/// let mut table: MemoIter<Empty<u64>, u64> = (0..1000u64)
+///     .into_par_iter()
+///     .map(|n| n * n)
+///     .collect();
+///
+/// assert!(table.is_exhausted());
+/// assert_eq!(table.get(999), Some(&998001));
+/// assert_eq!(table.get(1000), None);
+/// ```
+impl<T: Send> FromParallelIterator<T> for MemoIter<Empty<T>, T> {
+    fn from_par_iter<P>(par_iter: P) -> Self where
+        P: IntoParallelIterator<Item=T>,
+    {
+        Self::from_vec(Vec::from_par_iter(par_iter))
+    }
+}
+
+
+/// Append the items of a parallel Iterator to an exhausted `MemoIter`,
+///     preserving their order.
+impl<T: Send> ParallelExtend<T> for MemoIter<Empty<T>, T> {
+    fn par_extend<P>(&mut self, par_iter: P) where
+        P: IntoParallelIterator<Item=T>,
+    {
+        self.sequence.par_extend(par_iter);
+        self.exhausted = true;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::rayon::prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_par_extend() {
+        let mut memo: MemoIter<Empty<usize>, usize> = (0..10).into_par_iter().collect();
+        memo.par_extend((10..10_000).into_par_iter().map(|n| n * 2));
+
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.evaluated(), 10_000);
+        assert_eq!(memo.get_slice(8..12), [8, 9, 20, 22]);
+        assert_eq!(memo.get(9_999), Some(&19_998));
+    }
+}
@@ -0,0 +1,376 @@
+//! Recording of evaluated items, and replaying of recordings in place of a
+//!     live source.
+
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    marker::PhantomData,
+    panic::{RefUnwindSafe, UnwindSafe},
+    str::FromStr,
+    sync::mpsc::Sender,
+};
+use crate::MemoIter;
+
+
+/// A sink which can store items as they are evaluated.
+pub trait Recorder<T> {
+    /// Store one item. Items are recorded in the order they are evaluated.
+    fn record(&mut self, item: &T) -> io::Result<()>;
+}
+
+
+impl<T: Clone> Recorder<T> for Vec<T> {
+    fn record(&mut self, item: &T) -> io::Result<()> {
+        self.push(item.clone());
+        Ok(())
+    }
+}
+
+
+/// A Recorder which sends a clone of each item through a channel. Recording
+///     fails once the Receiver has been dropped.
+impl<T: Clone> Recorder<T> for Sender<T> {
+    fn record(&mut self, item: &T) -> io::Result<()> {
+        self.send(item.clone()).map_err(|_| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "receiver disconnected")
+        })
+    }
+}
+
+
+/// A Recorder which writes each item to a writer as one line of text, using
+///     its `Display` implementation. A recording written this way can be read
+///     back with `TextRecording`.
+#[derive(Debug)]
+pub struct TextRecorder<W: Write>(pub W);
+
+
+impl<T: Display, W: Write> Recorder<T> for TextRecorder<W> {
+    fn record(&mut self, item: &T) -> io::Result<()> {
+        writeln!(self.0, "{}", item)
+    }
+}
+
+
+/// An Iterator adapter which passes every item of its inner Iterator to a
+///     Recorder before returning it.
+///
+/// If the Recorder returns an error, the error is kept, recording stops, and
+///     the items continue to be returned unrecorded.
+#[derive(Debug)]
+pub struct Record<I, R> {
+    error: Option<io::Error>,
+    iterator: I,
+    recorder: R,
+}
+
+
+impl<I, R> Record<I, R> {
+    /// Wrap an Iterator, recording its items into a Recorder.
+    pub fn new(iterator: I, recorder: R) -> Self {
+        Self { error: None, iterator, recorder }
+    }
+
+    /// Return a reference to the Recorder.
+    #[inline]
+    pub fn recorder(&self) -> &R {
+        &self.recorder
+    }
+
+    /// Return the error that stopped recording, if one has occurred.
+    #[inline]
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Consume self, returning the inner Iterator and the Recorder.
+    pub fn into_parts(self) -> (I, R) {
+        (self.iterator, self.recorder)
+    }
+}
+
+
+impl<I, R, T> Iterator for Record<I, R> where
+    I: Iterator<Item=T>,
+    R: Recorder<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item: T = self.iterator.next()?;
+
+        if self.error.is_none() {
+            if let Err(e) = self.recorder.record(&item) {
+                self.error = Some(e);
+            }
+        }
+
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+
+/// An Iterator over the items of a recording written by `TextRecorder`,
+///     parsing one item from each line. It ends at the first line which cannot
+///     be read or parsed.
+#[derive(Debug)]
+pub struct TextRecording<B, T> {
+    reader: Option<B>,
+    _item: PhantomData<fn() -> T>,
+}
+
+
+impl<B: BufRead, T: FromStr> TextRecording<B, T> {
+    /// Read a recording from a buffered reader.
+    pub fn new(reader: B) -> Self {
+        Self { reader: Some(reader), _item: PhantomData }
+    }
+}
+
+
+impl<B: BufRead, T: FromStr> Iterator for TextRecording<B, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader: &mut B = self.reader.as_mut()?;
+        let mut line: String = String::new();
+
+        let parsed: Option<T> = match reader.read_line(&mut line) {
+            Ok(n) if n > 0 => line.trim_end_matches(&['\n', '\r'][..]).parse().ok(),
+            _ => None,
+        };
+
+        if parsed.is_none() {
+            self.reader = None;
+        }
+
+        parsed
+    }
+}
+
+
+/// An Iterator which returns the items of a recording, and then falls back to
+///     a live Iterator when the recording runs out.
+///
+/// When falling back, the live Iterator is first advanced past as many items
+///     as were replayed, so that every item keeps its position.
+#[derive(Debug)]
+pub struct Replay<R, I> {
+    live: I,
+    recording: Option<R>,
+    replayed: usize,
+}
+
+
+impl<R, I> Replay<R, I> {
+    /// Replay a recording, continuing with a live Iterator afterwards.
+    pub fn new(recording: R, live: I) -> Self {
+        Self { live, recording: Some(recording), replayed: 0 }
+    }
+
+    /// Return the number of items which have been taken from the recording.
+    #[inline]
+    pub fn replayed(&self) -> usize {
+        self.replayed
+    }
+
+    /// Return `true` if items are still being taken from the recording.
+    #[inline]
+    pub fn is_replaying(&self) -> bool {
+        self.recording.is_some()
+    }
+}
+
+
+impl<R, I, T> Iterator for Replay<R, I> where
+    R: Iterator<Item=T>,
+    I: Iterator<Item=T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(recording) = &mut self.recording {
+            if let Some(item) = recording.next() {
+                self.replayed += 1;
+                return Some(item);
+            }
+
+            self.recording = None;
+
+            if self.replayed > 0 {
+                self.live.nth(self.replayed - 1)?;
+            }
+        }
+
+        self.live.next()
+    }
+}
+
+
+impl<I, R, T> MemoIter<Record<I, R>, T> where
+    I: Iterator<Item=T>,
+    R: Recorder<T>,
+{
+    /// Create an empty `MemoIter` wrapping a given Iterator, which records
+    ///     every evaluated item into a Recorder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::recording((1..).map(|n| n * n), Vec::new());
+    /// assert_eq!(memo.get(3), Some(&16));
+    ///
+    /// let (_, source) = memo.consume();
+    /// let (_, recording) = source.into_parts();
+    /// assert_eq!(recording, [1, 4, 9, 16]);
+    /// ```
+    pub fn recording(iterator: I, recorder: R) -> Self {
+        Self::new(Record::new(iterator, recorder))
+    }
+}
+
+
+impl<R, I, T> MemoIter<Replay<R, I>, T> where
+    R: Iterator<Item=T>,
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `MemoIter` which takes its items from a recording, and
+    ///     then from a live Iterator once the recording runs out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, record::TextRecording};
+    /// use std::io::Cursor;
+    ///
+    /// let saved = Cursor::new("1\n4\n9\n");
+    /// let live = (1..).map(|n| n * n);
+    ///
+    /// let mut memo = MemoIter::replaying(TextRecording::new(saved), live);
+    /// assert_eq!(memo.get_slice(..5), [1, 4, 9, 16, 25]);
+    /// ```
+    pub fn replaying<C>(recording: C, live: I) -> Self where
+        C: IntoIterator<IntoIter=R>,
+    {
+        Self::new(Replay::new(recording.into_iter(), live))
+    }
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Pass every item evaluated from now on to a Recorder, such as a channel
+    ///     `Sender` or a `TextRecorder`, so that a downstream pipeline is fed
+    ///     as the memo is filled, without a second pass over it. Items stored
+    ///     by `MemoIter::absorb()` are passed to it as well. Any previous
+    ///     mirror is replaced.
+    ///
+    /// If the Recorder returns an error, it is removed, and the error is kept,
+    ///     to be retrieved with `MemoIter::mirror_error()`. While a mirror is
+    ///     set, items are evaluated one at a time, rather than in bulk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, record::TextRecorder};
+    /// use std::sync::mpsc::channel;
+    ///
+    /// let mut memo = MemoIter::new((1..).map(|n| n * n));
+    /// let (tx, rx) = channel();
+    ///
+    /// memo.set_mirror(tx);
+    /// memo.get(2);
+    /// assert_eq!(rx.try_iter().collect::<Vec<i32>>(), [1, 4, 9]);
+    ///
+    /// memo.set_mirror(TextRecorder(Vec::new()));
+    /// memo.get(4);
+    /// drop(rx);
+    ///
+    /// assert!(memo.mirror_error().is_none());
+    /// ```
+    pub fn set_mirror<R>(&mut self, recorder: R) where
+        R: Recorder<T> + Send + Sync + UnwindSafe + RefUnwindSafe + 'static,
+    {
+        self.mirror = Some(Box::new(recorder));
+        self.mirror_error = None;
+    }
+
+    /// Remove the mirror, if one is set.
+    pub fn clear_mirror(&mut self) {
+        self.mirror = None;
+    }
+
+    /// Return the error which caused the mirror to be removed, if one has
+    ///     occurred since it was set.
+    #[inline]
+    pub fn mirror_error(&self) -> Option<&io::Error> {
+        self.mirror_error.as_ref().map(|e| &e.0)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_text_roundtrip() {
+        let mut memo = MemoIter::recording(-3..7, TextRecorder(Vec::new()));
+        assert_eq!(memo.get(4), Some(&1));
+
+        let (_, source) = memo.consume();
+        assert!(source.error().is_none());
+
+        let (_, TextRecorder(bytes)) = source.into_parts();
+        assert_eq!(bytes, b"-3\n-2\n-1\n0\n1\n");
+
+        let mut replay = MemoIter::replaying(
+            TextRecording::<_, i32>::new(Cursor::new(bytes)),
+            -3..7,
+        );
+        assert_eq!(replay.exhaust(), [-3, -2, -1, 0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut memo = MemoIter::new(0..10);
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        memo.set_mirror(tx);
+        assert_eq!(memo.get(3), Some(&3));
+        assert_eq!(rx.recv(), Ok(0));
+        drop(rx);
+
+        assert_eq!(memo.get(5), Some(&5));
+        assert_eq!(memo.mirror_error().map(io::Error::kind), Some(io::ErrorKind::BrokenPipe));
+        assert_eq!(memo.exhaust().len(), 10);
+    }
+
+    #[test]
+    fn test_replay_fallback() {
+        let live = CountingIter::new(0..10);
+        let counter = live.counter();
+        let mut memo = MemoIter::replaying(vec![0, 1, 2], live);
+
+        assert_eq!(memo.get_slice(..3), [0, 1, 2]);
+        assert_eq!(counter.times_advanced(), 0);
+
+        assert_eq!(memo.get(3), Some(&3));
+        assert_eq!(counter.times_advanced(), 4);
+
+        let bad = TextRecording::<_, u8>::new(Cursor::new("5\nsix\n7\n"));
+        let mut memo = MemoIter::replaying(bad, 0..10);
+
+        assert_eq!(memo.get_slice(..3), [5, 1, 2]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Regular expression search over memoized byte streams, using the DFAs of
+//!     `regex-automata`.
+
+use ::regex_automata::{
+    Anchored,
+    dfa::{Automaton, regex::Regex},
+    Input,
+    MatchError,
+};
+use std::ops::Range;
+use crate::MemoIter;
+
+
+impl<I> MemoIter<I, u8> where
+    I: Iterator<Item=u8>,
+{
+    /// Search for the leftmost-first match of a regular expression, starting
+    ///     at index `start` and ending at or before index `limit`. Items are
+    ///     evaluated only as the search reaches them, and the search stops as
+    ///     soon as a match cannot be extended, so a match near the start does
+    ///     not require evaluating up to the limit.
+    ///
+    /// The match is returned as a range of indices in the memoized sequence.
+    ///     The item at `limit`, if there is one, may be evaluated to decide
+    ///     assertions such as `\b` and `$` at the end of the search.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MatchError` if the DFA gives up, as when it is configured to
+    ///     quit on certain bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    /// use regex_automata::dfa::regex::Regex;
+    ///
+    /// let log = b"INFO boot\nWARN disk 91%\nINFO ok\nWARN disk 97%\n".repeat(1000);
+    /// let mut memo = MemoIter::new(log.into_iter());
+    ///
+    /// let re = Regex::new(r"WARN disk [0-9]+%").unwrap();
+    /// let found = memo.find_regex(&re, 0, usize::MAX).unwrap();
+    ///
+    /// assert_eq!(found, Some(10..23));
+    /// assert_eq!(&memo[10..23], b"WARN disk 91%");
+    ///
+    /// //  Only a little past the first match has been evaluated.
+    /// assert_eq!(memo.evaluated(), 25);
+    /// ```
+    pub fn find_regex<A>(&mut self, re: &Regex<A>, start: usize, limit: usize)
+        -> Result<Option<Range<usize>>, MatchError>
+    where
+        A: Automaton,
+    {
+        let (fwd, rev) = (re.forward(), re.reverse());
+
+        if let Some(before) = start.checked_sub(1) {
+            self.get(before);
+        }
+
+        if start > self.sequence.len() || start > limit {
+            return Ok(None);
+        }
+
+        let input = Input::new(&self.sequence[..start]).range(start..);
+        let mut sid = fwd.start_state_forward(&input)?;
+        let mut end: Option<usize> = None;
+        let mut at: usize = start;
+
+        //  NOTE: DFA matches are delayed by one byte, so the byte at `limit` is
+        //      fed as well, to detect a match ending exactly at the limit.
+        loop {
+            match self.get(at) {
+                Some(&byte) => {
+                    sid = fwd.next_state(sid, byte);
+
+                    if fwd.is_special_state(sid) {
+                        if fwd.is_match_state(sid) {
+                            end = Some(at);
+                        } else if fwd.is_dead_state(sid) {
+                            break;
+                        } else if fwd.is_quit_state(sid) {
+                            return Err(MatchError::quit(byte, at));
+                        }
+                    }
+                }
+                None => {
+                    sid = fwd.next_eoi_state(sid);
+
+                    if fwd.is_match_state(sid) {
+                        end = Some(at);
+                    }
+
+                    break;
+                }
+            }
+
+            if at >= limit {
+                break;
+            }
+
+            at += 1;
+        }
+
+        let end: usize = match end {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+
+        if end == start {
+            return Ok(Some(start..start));
+        }
+
+        let haystack: &[u8] = &self.sequence;
+        let input = Input::new(haystack)
+            .range(start..end)
+            .anchored(Anchored::Yes)
+            .earliest(false);
+
+        match rev.try_search_rev(&input)? {
+            Some(half) => Ok(Some(half.offset()..end)),
+            None => Ok(None),
+        }
+    }
+
+    /// Return an Iterator over the successive non-overlapping matches of a
+    ///     regular expression, ending at or before index `limit`, evaluating
+    ///     the sequence lazily as the matches are requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    /// use regex_automata::dfa::regex::Regex;
+    ///
+    /// let mut memo = MemoIter::new(b"a1 b22 c333".iter().copied());
+    /// let re = Regex::new(r"[0-9]+").unwrap();
+    ///
+    /// let found: Vec<_> = memo.regex_matches(&re, usize::MAX)
+    ///     .map(Result::unwrap)
+    ///     .collect();
+    ///
+    /// assert_eq!(found, [1..2, 4..6, 8..11]);
+    /// ```
+    pub fn regex_matches<'m, 'r, A>(&'m mut self, re: &'r Regex<A>, limit: usize)
+        -> RegexMatches<'m, 'r, I, A>
+    where
+        A: Automaton,
+    {
+        RegexMatches {
+            at: 0,
+            done: false,
+            last_end: None,
+            limit,
+            memo: self,
+            re,
+        }
+    }
+}
+
+
+/// An Iterator over the matches of a regular expression in a `MemoIter`. This
+///     is returned by `MemoIter::regex_matches()`.
+#[derive(Debug)]
+pub struct RegexMatches<'m, 'r, I, A> where
+    I: Iterator<Item=u8>,
+{
+    at: usize,
+    done: bool,
+    last_end: Option<usize>,
+    limit: usize,
+    memo: &'m mut MemoIter<I, u8>,
+    re: &'r Regex<A>,
+}
+
+
+impl<I, A> Iterator for RegexMatches<'_, '_, I, A> where
+    I: Iterator<Item=u8>,
+    A: Automaton,
+{
+    type Item = Result<Range<usize>, MatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let found = match self.memo.find_regex(self.re, self.at, self.limit) {
+                Ok(Some(found)) => found,
+                Ok(None) => break,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            //  NOTE: An empty match directly after the previous match would be
+            //      found again at the same place, so skip past it.
+            if found.is_empty() && Some(found.end) == self.last_end {
+                self.at = found.end + 1;
+                continue;
+            }
+
+            self.at = found.end;
+            self.last_end = Some(found.end);
+            return Some(Ok(found));
+        }
+
+        self.done = true;
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assertions() {
+        let re = Regex::new(r"(?-u:\b)cat(?-u:\b)|^$").unwrap();
+
+        let mut memo = MemoIter::new(b"concat cat catalog cat".iter().copied());
+        let found: Vec<_> = memo.regex_matches(&re, usize::MAX).map(Result::unwrap).collect();
+        assert_eq!(found, [7..10, 19..22]);
+
+        let mut memo = MemoIter::new(b"cats cat".iter().copied());
+        assert_eq!(memo.find_regex(&re, 0, 3).unwrap(), None);
+        assert_eq!(memo.find_regex(&re, 0, 8).unwrap(), Some(5..8));
+        assert_eq!(memo.find_regex(&re, 9, 20).unwrap(), None);
+
+        let mut empty = MemoIter::new(std::iter::empty());
+        assert_eq!(empty.regex_matches(&re, 10).count(), 1);
+    }
+
+    #[test]
+    fn test_empty_matches() {
+        let re = Regex::new(r"a*").unwrap();
+        let mut memo = MemoIter::new(b"baab".iter().copied());
+        let found: Vec<_> = memo.regex_matches(&re, usize::MAX).map(Result::unwrap).collect();
+
+        assert_eq!(found, [0..0, 1..3, 4..4]);
+    }
+}
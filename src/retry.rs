@@ -0,0 +1,182 @@
+//! Retrying of transient failures in fallible sources.
+//!
+//! A fallible source is one whose items are `Result`s. When memoized directly,
+//!     a transient failure is stored as permanently as a success, and every
+//!     caller which retrieves that index will see the error. Wrapping the
+//!     source in a `Retry` before passing it to a `MemoIter` resolves
+//!     transient failures during evaluation, so that only persistent failures
+//!     are stored.
+
+use std::{thread::sleep, time::Duration};
+
+
+/// An Iterator adapter which calls the inner Iterator again when it returns an
+///     error, waiting with exponential backoff between attempts.
+///
+/// The inner Iterator is expected to retry the same item when it is advanced
+///     after returning an error. This is the behavior of most fallible
+///     Iterators over I/O, such as `io::Lines`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, retry::Retry};
+/// use std::time::Duration;
+///
+/// //  A source which fails the first time each item is requested.
+/// let flaky = (0..).flat_map(|n| vec![Err("timeout"), Ok(n)]);
+/// let source = Retry::new(flaky)
+///     .max_attempts(3)
+///     .backoff(Duration::from_millis(1), 2.0);
+///
+/// let mut memo = MemoIter::new(source);
+///
+/// assert_eq!(memo.get(5), Some(&Ok(5)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Retry<I, P> {
+    factor: f64,
+    initial: Duration,
+    iterator: I,
+    max_attempts: u32,
+    max_backoff: Duration,
+    predicate: P,
+}
+
+
+impl<I, T, E> Retry<I, fn(&E) -> bool> where
+    I: Iterator<Item=Result<T, E>>,
+{
+    /// Wrap a fallible Iterator, retrying every error up to three attempts in
+    ///     total, with backoff beginning at 100 milliseconds and doubling after
+    ///     each attempt.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            factor: 2.0,
+            initial: Duration::from_millis(100),
+            iterator,
+            max_attempts: 3,
+            max_backoff: Duration::from_secs(30),
+            predicate: |_| true,
+        }
+    }
+}
+
+
+impl<I, P, T, E> Retry<I, P> where
+    I: Iterator<Item=Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    /// Set the maximum number of attempts to evaluate each item, including the
+    ///     first. An attempt count of zero is treated as one.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry of an item, and the factor by which
+    ///     the delay is multiplied after each subsequent retry. A factor less
+    ///     than one is treated as one.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `factor` is infinite or NaN.
+    pub fn backoff(mut self, initial: Duration, factor: f64) -> Self {
+        assert!(factor.is_finite(), "backoff factor must be finite");
+
+        self.initial = initial;
+        self.factor = factor.max(1.0);
+        self
+    }
+
+    /// Set the longest delay which may be waited between two attempts.
+    pub fn max_backoff(mut self, max: Duration) -> Self {
+        self.max_backoff = max;
+        self
+    }
+
+    /// Only retry errors for which the given predicate returns `true`. Other
+    ///     errors are returned immediately.
+    pub fn when<Q>(self, predicate: Q) -> Retry<I, Q> where
+        Q: FnMut(&E) -> bool,
+    {
+        Retry {
+            factor: self.factor,
+            initial: self.initial,
+            iterator: self.iterator,
+            max_attempts: self.max_attempts,
+            max_backoff: self.max_backoff,
+            predicate,
+        }
+    }
+
+    /// Consume self, returning the inner Iterator.
+    pub fn into_inner(self) -> I {
+        self.iterator
+    }
+}
+
+
+impl<I, P, T, E> Iterator for Retry<I, P> where
+    I: Iterator<Item=Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut delay: Duration = self.initial;
+        let mut attempt: u32 = 1;
+
+        loop {
+            match self.iterator.next()? {
+                Err(e) if attempt < self.max_attempts && (self.predicate)(&e) => {
+                    sleep(delay.min(self.max_backoff));
+                    //  NOTE: The product may be too large for a `Duration`, so
+                    //      it is compared with the limit before converting.
+                    let next: f64 = delay.as_secs_f64() * self.factor;
+
+                    delay = if next < self.max_backoff.as_secs_f64() {
+                        Duration::from_secs_f64(next)
+                    } else {
+                        self.max_backoff
+                    };
+                    attempt += 1;
+                }
+                result => return Some(result),
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry() {
+        let source = vec![
+            Err(1), Ok(10),
+            Err(2), Err(3),
+            Err(-4), Ok(20),
+            Err(5), Ok(30),
+        ];
+
+        let retry = Retry::new(source.into_iter())
+            .max_attempts(2)
+            .backoff(Duration::ZERO, 1.0)
+            .when(|&e: &i32| e > 0);
+
+        let results: Vec<Result<i32, i32>> = retry.collect();
+
+        assert_eq!(results, [Ok(10), Err(3), Err(-4), Ok(20), Ok(30)]);
+
+        //  A delay which grows past what a `Duration` can hold is capped.
+        let mut retry = Retry::new(vec![Err(1), Err(2), Err(3), Ok(4)].into_iter())
+            .max_attempts(4)
+            .backoff(Duration::from_nanos(1), 1e300)
+            .max_backoff(Duration::from_millis(1));
+
+        assert_eq!(retry.next(), Some(Ok(4)));
+    }
+}
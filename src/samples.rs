@@ -0,0 +1,224 @@
+//! Random access into decoded audio, for sample sources which can only be
+//!     decoded forward.
+
+use std::{
+    io::{Error, ErrorKind, Result, SeekFrom},
+    iter::FusedIterator,
+    ops::Range,
+    time::Duration,
+};
+use crate::MemoIter;
+
+
+/// A memoized stream of audio samples, which may be indexed by time as well as
+///     by sample, and which maintains a playback position that can be moved
+///     freely in either direction.
+///
+/// Samples are indexed by their position in the stream. For interleaved
+///     multichannel audio, the sample rate given should be the number of
+///     samples per second across all channels.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::samples::SampleMemo;
+/// use std::{io::SeekFrom, time::Duration};
+///
+/// let ramp = (0..).map(|n| n as f32 / 100.0);
+/// let mut audio = SampleMemo::new(ramp, 100);
+///
+/// assert_eq!(audio.get_at(Duration::from_millis(1500)), Some(&1.5));
+///
+/// audio.seek(SeekFrom::Start(20)).unwrap();
+/// assert_eq!(audio.next(), Some(0.2));
+///
+/// audio.seek(SeekFrom::Current(-11)).unwrap();
+/// assert_eq!(audio.next(), Some(0.1));
+/// ```
+#[derive(Debug)]
+pub struct SampleMemo<I, S> where
+    I: Iterator<Item=S>,
+{
+    memo: MemoIter<I, S>,
+    position: usize,
+    sample_rate: u32,
+}
+
+
+impl<I, S> SampleMemo<I, S> where
+    I: Iterator<Item=S>,
+{
+    /// Wrap a source of samples with a given sample rate, in samples per second.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `sample_rate` is zero.
+    pub fn new(source: I, sample_rate: u32) -> Self {
+        assert_ne!(sample_rate, 0, "sample rate must be nonzero");
+
+        Self {
+            memo: MemoIter::new(source),
+            position: 0,
+            sample_rate,
+        }
+    }
+
+    /// Return the sample rate of the stream, in samples per second.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Return the index of the sample playing at a given time.
+    pub fn index_at(&self, time: Duration) -> usize {
+        let index: u128 = time.as_nanos() * self.sample_rate as u128
+            / 1_000_000_000;
+
+        index.min(usize::MAX as u128) as usize
+    }
+
+    /// Return the time at which a given sample begins playing.
+    pub fn time_of(&self, idx: usize) -> Duration {
+        let nanos: u128 = idx as u128 * 1_000_000_000 / self.sample_rate as u128;
+
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    }
+
+    /// Retrieve the sample playing at a given time, decoding as needed.
+    pub fn get_at(&mut self, time: Duration) -> Option<&S> {
+        let idx: usize = self.index_at(time);
+        self.memo.get(idx)
+    }
+
+    /// Retrieve the samples playing within a given span of time, decoding as
+    ///     needed. The slice will be shorter than expected if the stream ends
+    ///     within the span.
+    pub fn get_range(&mut self, range: Range<Duration>) -> &[S] {
+        let start: usize = self.index_at(range.start);
+        let end: usize = self.index_at(range.end);
+
+        self.memo.get_slice(start..end)
+    }
+
+    /// Return a reference to the underlying `MemoIter`.
+    #[inline]
+    pub fn memo(&mut self) -> &mut MemoIter<I, S> {
+        &mut self.memo
+    }
+
+    /// Consume self, returning the underlying `MemoIter`.
+    pub fn into_memo(self) -> MemoIter<I, S> {
+        self.memo
+    }
+
+    /// Return the index of the next sample to be played.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the time at which the next sample to be played begins.
+    #[inline]
+    pub fn position_time(&self) -> Duration {
+        self.time_of(self.position)
+    }
+
+    /// Move the playback position, in samples, in the manner of `io::Seek`,
+    ///     returning the new position.
+    ///
+    /// Seeking relative to the end of the stream requires the entire stream to
+    ///     be decoded, and will not return if the stream is infinite. Seeking
+    ///     beyond the end of the stream is allowed, and further playback will
+    ///     simply return no samples.
+    ///
+    /// # Errors
+    ///
+    /// An error of the `InvalidInput` kind will be returned if the new position
+    ///     would be before the start of the stream.
+    pub fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, offset): (u64, i64) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::Current(n) => (self.position as u64, n),
+            SeekFrom::End(n) => (self.memo.exhaust().len() as u64, n),
+        };
+
+        let new: u64 = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset as u64)
+        }.ok_or_else(|| Error::new(
+            ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        ))?;
+
+        self.position = new.min(usize::MAX as u64) as usize;
+        Ok(new)
+    }
+
+    /// Move the playback position to the sample playing at a given time.
+    pub fn seek_time(&mut self, time: Duration) {
+        self.position = self.index_at(time);
+    }
+}
+
+
+impl<I, S> Iterator for SampleMemo<I, S> where
+    I: Iterator<Item=S>,
+    S: Copy,
+{
+    type Item = S;
+
+    /// Play the sample at the current position, advancing the position. Samples
+    ///     which have been decoded before are replayed from memory.
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample: S = *self.memo.get(self.position)?;
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+
+impl<I, S> FusedIterator for SampleMemo<I, S> where
+    I: Iterator<Item=S>,
+    S: Copy,
+{}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_scrubbing() {
+        let source = CountingIter::new((0..48_000).map(|n: i32| (n / 10) as i16));
+        let counter = source.counter();
+        let mut audio = SampleMemo::new(source, 48_000);
+
+        assert_eq!(audio.index_at(Duration::from_millis(500)), 24_000);
+        assert_eq!(audio.time_of(12_000), Duration::from_millis(250));
+        assert_eq!(audio.get_at(Duration::from_millis(500)), Some(&2_400));
+
+        let slice = audio.get_range(Duration::from_millis(100)..Duration::from_millis(101));
+        assert_eq!(slice.len(), 48);
+        assert_eq!(slice[0], 480);
+
+        audio.seek_time(Duration::from_millis(250));
+        assert_eq!(audio.next(), Some(1_200));
+        assert_eq!(audio.position_time(), audio.time_of(12_001));
+
+        //  Scrubbing backward replays decoded samples without decoding again.
+        let decoded: usize = counter.times_advanced();
+        audio.seek(SeekFrom::Start(0)).unwrap();
+        assert_eq!(audio.by_ref().take(10).count(), 10);
+        assert_eq!(counter.times_advanced(), decoded);
+
+        assert!(audio.seek(SeekFrom::Current(-11)).is_err());
+        assert_eq!(audio.seek(SeekFrom::End(-1)).unwrap(), 47_999);
+        assert_eq!(audio.next(), Some(4_799));
+        assert_eq!(audio.next(), None);
+    }
+}
@@ -0,0 +1,469 @@
+//! Memoization into segmented storage, which never moves its items, so that
+//!     references to them may be held while more items are evaluated.
+
+use std::{
+    cell::{Cell, RefCell, UnsafeCell},
+    fmt::{self, Debug, Formatter},
+};
+
+
+/// The capacity of the first segment, as a power of two. Each following
+///     segment is twice the size of the one before it.
+pub(crate) const FIRST_SHIFT: u32 = 3;
+
+
+/// Find the segment holding an index, and the offset of the index within it.
+#[inline]
+pub(crate) fn locate(idx: usize) -> (usize, usize) {
+    let n: usize = idx + (1 << FIRST_SHIFT);
+    let bit: u32 = usize::BITS - 1 - n.leading_zeros();
+
+    ((bit - FIRST_SHIFT) as usize, n - (1 << bit))
+}
+
+
+/// Append-only storage made of segments which are allocated once at their full
+///     capacity and never reallocated. Items may be pushed through a shared
+///     reference without invalidating references to earlier items.
+pub(crate) struct Segments<T> {
+    len: Cell<usize>,
+    segments: UnsafeCell<Vec<Vec<T>>>,
+}
+
+
+impl<T> Segments<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            len: Cell::new(0),
+            segments: UnsafeCell::new(Vec::new()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Option<&T> {
+        if idx < self.len.get() {
+            let (seg, off) = locate(idx);
+
+            //  SAFETY: The index is below the length, so the item has been
+            //      written. Segment buffers are never reallocated or removed
+            //      while `self` is alive, and no `&mut` to an item is ever
+            //      created through a shared reference.
+            unsafe {
+                let segments: &Vec<Vec<T>> = &*self.segments.get();
+                Some(&*segments.get_unchecked(seg).as_ptr().add(off))
+            }
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn push(&self, item: T) {
+        let len: usize = self.len.get();
+        let (seg, _) = locate(len);
+
+        //  SAFETY: `Segments` is not `Sync`, and nothing here calls out to user
+        //      code, so no other access to the outer Vec can overlap this one.
+        //      Pushing within capacity writes a new slot without moving the
+        //      existing items, so references to them remain valid.
+        unsafe {
+            let segments: &mut Vec<Vec<T>> = &mut *self.segments.get();
+
+            if seg == segments.len() {
+                segments.push(Vec::with_capacity(1 << (seg as u32 + FIRST_SHIFT)));
+            }
+
+            let segment: &mut Vec<T> = segments.get_unchecked_mut(seg);
+            debug_assert!(segment.len() < segment.capacity());
+            segment.push(item);
+        }
+
+        self.len.set(len + 1);
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        (0..self.len()).filter_map(move |idx| self.get(idx))
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        let segments: Vec<Vec<T>> = self.segments.into_inner();
+        let mut vec: Vec<T> = Vec::with_capacity(self.len.get());
+
+        for segment in segments {
+            vec.extend(segment);
+        }
+
+        vec
+    }
+}
+
+
+impl<T: Debug> Debug for Segments<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+/// A Memoized Iterator which stores its items in segments that are never moved.
+///     Its `EvalGuard` allows references to several items to be held at once
+///     while further items are evaluated, without cloning them.
+///
+/// Because its storage is not contiguous, it cannot be dereferenced to a slice
+///     like a `MemoIter`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::segmented::SegmentedMemoIter;
+///
+/// let mut memo = SegmentedMemoIter::new((1..).map(|n| format!("#{}", n)));
+/// let guard = memo.guard();
+///
+/// let first: &String = guard.get(0).unwrap();
+/// let tenth: &String = guard.get(9).unwrap();
+/// let later: &String = guard.get(999).unwrap();
+///
+/// assert_eq!([first, tenth, later], ["#1", "#10", "#1000"]);
+/// ```
+#[derive(Debug)]
+pub struct SegmentedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    exhausted: bool,
+    iterator: I,
+    segments: Segments<T>,
+}
+
+
+impl<I, T> SegmentedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `SegmentedMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            exhausted: false,
+            iterator,
+            segments: Segments::new(),
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        while !self.exhausted && self.segments.len() <= idx {
+            match self.iterator.next() {
+                Some(item) => self.segments.push(item),
+                None => self.exhausted = true,
+            }
+        }
+
+        self.segments.get(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.segments.get(idx)
+    }
+
+    /// Return an Iterator over references to the values evaluated so far.
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.segments.iter()
+    }
+
+    /// Borrow self mutably, returning a guard through which items may be
+    ///     evaluated and retrieved. References returned by the guard live as
+    ///     long as the guard's borrow, rather than only until the next call.
+    pub fn guard(&mut self) -> EvalGuard<'_, I, T> {
+        EvalGuard {
+            exhausted: Cell::new(self.exhausted),
+            iterator: RefCell::new(&mut self.iterator),
+            original: &mut self.exhausted,
+            segments: &self.segments,
+        }
+    }
+
+    /// Consume self, returning a Vector of the values evaluated so far and the
+    ///     internal Iterator.
+    pub fn consume(self) -> (Vec<T>, I) {
+        let Self { segments, iterator, .. } = self;
+        (segments.into_vec(), iterator)
+    }
+}
+
+
+/// A guard holding a `SegmentedMemoIter` mutably borrowed, which can evaluate
+///     and retrieve its items through a shared reference.
+///
+/// Each reference returned by the guard remains valid for as long as the
+///     `SegmentedMemoIter` is borrowed, even after the guard evaluates more
+///     items or is dropped.
+pub struct EvalGuard<'a, I, T> {
+    exhausted: Cell<bool>,
+    iterator: RefCell<&'a mut I>,
+    original: &'a mut bool,
+    segments: &'a Segments<T>,
+}
+
+
+impl<'a, I, T> EvalGuard<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if called from within the Iterator itself.
+    pub fn get(&self, idx: usize) -> Option<&'a T> {
+        if !self.exhausted.get() && self.segments.len() <= idx {
+            let mut iterator = self.iterator.borrow_mut();
+
+            while self.segments.len() <= idx {
+                match iterator.next() {
+                    Some(item) => self.segments.push(item),
+                    None => {
+                        self.exhausted.set(true);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.segments.get(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&'a T> {
+        self.segments.get(idx)
+    }
+}
+
+
+impl<I, T> Debug for EvalGuard<'_, I, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvalGuard")
+            .field("evaluated", &self.segments.len())
+            .field("exhausted", &self.exhausted.get())
+            .finish_non_exhaustive()
+    }
+}
+
+
+impl<I, T> Drop for EvalGuard<'_, I, T> {
+    fn drop(&mut self) {
+        *self.original = self.exhausted.get();
+    }
+}
+
+
+/// A Memoized Iterator which evaluates and retrieves its items through a
+///     shared reference. It can be held in a struct and read from methods that
+///     take `&self`, without wrapping it in a `RefCell`.
+///
+/// Items are stored in segments that are never moved, so a reference returned
+///     by `get()` stays valid while further items are evaluated. The Iterator
+///     itself is kept in a `RefCell`, so a `CellMemoIter` is not `Sync`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::segmented::CellMemoIter;
+///
+/// struct Primes {
+///     memo: CellMemoIter<Box<dyn Iterator<Item=u32>>, u32>,
+/// }
+///
+/// impl Primes {
+///     fn nth(&self, n: usize) -> u32 {
+///         *self.memo.get(n).unwrap()
+///     }
+/// }
+///
+/// let primes = Primes {
+///     memo: CellMemoIter::new(Box::new((2..).filter(|n| (2..*n).all(|d| n % d != 0)))),
+/// };
+///
+/// let tenth: &u32 = primes.memo.get(9).unwrap();
+/// assert_eq!(primes.nth(99), 541);
+/// assert_eq!(*tenth, 29);
+/// ```
+#[derive(Debug)]
+pub struct CellMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    exhausted: Cell<bool>,
+    iterator: RefCell<I>,
+    segments: Segments<T>,
+}
+
+
+impl<I, T> CellMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `CellMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            exhausted: Cell::new(false),
+            iterator: RefCell::new(iterator),
+            segments: Segments::new(),
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.get()
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if called from within the Iterator itself.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        if !self.exhausted.get() && self.segments.len() <= idx {
+            let mut iterator = self.iterator.borrow_mut();
+
+            while self.segments.len() <= idx {
+                match iterator.next() {
+                    Some(item) => self.segments.push(item),
+                    None => {
+                        self.exhausted.set(true);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.segments.get(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.segments.get(idx)
+    }
+
+    /// Return an Iterator over references to the values evaluated so far.
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.segments.iter()
+    }
+
+    /// Consume self, returning a Vector of the values evaluated so far and the
+    ///     internal Iterator.
+    pub fn consume(self) -> (Vec<T>, I) {
+        let Self { segments, iterator, .. } = self;
+        (segments.into_vec(), iterator.into_inner())
+    }
+}
+
+
+impl<I, T> From<SegmentedMemoIter<I, T>> for CellMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    fn from(memo: SegmentedMemoIter<I, T>) -> Self {
+        Self {
+            exhausted: Cell::new(memo.exhausted),
+            iterator: RefCell::new(memo.iterator),
+            segments: memo.segments,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        assert_eq!(locate(0), (0, 0));
+        assert_eq!(locate(7), (0, 7));
+        assert_eq!(locate(8), (1, 0));
+        assert_eq!(locate(23), (1, 15));
+        assert_eq!(locate(24), (2, 0));
+
+        let (seg, off) = locate(usize::MAX - 8);
+        assert_eq!(seg as u32, usize::BITS - 1 - FIRST_SHIFT);
+        assert_eq!(off, usize::MAX - 8 - ((1 << (usize::BITS - 1)) - 8));
+    }
+
+    #[test]
+    fn test_guard() {
+        let mut memo = SegmentedMemoIter::new((0..100).map(|n| vec![n; 3]));
+
+        let held: Vec<&Vec<i32>> = {
+            let guard = memo.guard();
+            let a = guard.get(5).unwrap();
+            let b = guard.get(50).unwrap();
+            let c = guard.get(500);
+
+            assert_eq!(a, &[5, 5, 5]);
+            assert_eq!(b, &[50, 50, 50]);
+            assert_eq!(c, None);
+            assert_eq!(guard.recall(99), Some(&vec![99; 3]));
+
+            vec![a, b]
+        };
+
+        assert_eq!(held[0][0] + held[1][0], 55);
+        assert!(memo.is_exhausted());
+
+        let (seq, _) = memo.consume();
+        assert_eq!(seq.len(), 100);
+        assert_eq!(seq[42], [42; 3]);
+    }
+
+    #[test]
+    fn test_cell() {
+        let mut segmented = SegmentedMemoIter::new(0..30);
+        segmented.get(3);
+
+        let memo: CellMemoIter<_, i32> = segmented.into();
+        let first: &i32 = memo.get(0).unwrap();
+
+        assert_eq!(memo.evaluated(), 4);
+        assert_eq!(memo.get(20), Some(&20));
+        assert_eq!(memo.recall(21), None);
+        assert_eq!(memo.get(40), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.iter().sum::<i32>(), 435);
+        assert_eq!(*first, 0);
+    }
+}
@@ -0,0 +1,384 @@
+//! Common sequences, ready to be memoized.
+//!
+//! The sequences in this module are computed with checked arithmetic, and end
+//!     at the last value which fits within the integer type, rather than
+//!     panicking or wrapping when the next value would overflow. A `MemoIter`
+//!     over one of them will become exhausted at that point, and requests for
+//!     higher indices will return `None`.
+//!
+//! With the `num-bigint` feature, the `_big` variants compute the same
+//!     sequences with `BigUint`, which never overflows, so that they continue
+//!     for as long as they are evaluated.
+
+use std::iter::FusedIterator;
+#[cfg(feature = "num-bigint")]
+use num_bigint::BigUint;
+use crate::MemoIter;
+
+
+/// An unsigned integer type supporting checked arithmetic.
+pub trait CheckedInt: Copy {
+    /// The additive identity.
+    const ZERO: Self;
+    /// The multiplicative identity.
+    const ONE: Self;
+
+    /// Add two values, returning `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Multiply two values, returning `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+
+macro_rules! impl_checked_int {
+    ($($t:ty),*) => {$(
+        impl CheckedInt for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_checked_int!(u8, u16, u32, u64, u128, usize);
+
+
+/// Create a `MemoIter` over the factorials, `0!, 1!, 2!, ...`, ending at the
+///     largest factorial which fits in `N`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sequences::factorials;
+///
+/// let mut fact = factorials::<u32>();
+///
+/// assert_eq!(fact.get(12), Some(&479_001_600));
+/// assert_eq!(fact.get(13), None);
+/// assert!(fact.is_exhausted());
+/// ```
+pub fn factorials<N: CheckedInt>() -> MemoIter<Factorials<N>, N> {
+    MemoIter::new(Factorials {
+        acc: Some(N::ONE),
+        n: N::ZERO,
+    })
+}
+
+
+/// Create a `MemoIter` over the Fibonacci sequence, `0, 1, 1, 2, 3, ...`,
+///     ending at the largest Fibonacci number which fits in `N`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sequences::fibonacci;
+///
+/// let mut fib = fibonacci::<u8>();
+///
+/// assert_eq!(fib.exhaust().last(), Some(&233));
+/// assert_eq!(fib.len(), 14);
+/// ```
+pub fn fibonacci<N: CheckedInt>() -> MemoIter<Fibonacci<N>, N> {
+    MemoIter::new(Fibonacci {
+        current: Some(N::ZERO),
+        next: Some(N::ONE),
+    })
+}
+
+
+/// Create a `MemoIter` over the powers of a base, `1, base, base², ...`, ending
+///     at the largest power which fits in `N`. If the base is zero or one, the
+///     sequence is infinite.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sequences::powers;
+///
+/// let mut pow = powers(10u16);
+///
+/// assert_eq!(pow.get(4), Some(&10_000));
+/// assert_eq!(pow.get(5), None);
+/// ```
+pub fn powers<N: CheckedInt>(base: N) -> MemoIter<Powers<N>, N> {
+    MemoIter::new(Powers {
+        base,
+        current: Some(N::ONE),
+    })
+}
+
+
+/// An Iterator over the factorials which fit in `N`.
+#[derive(Clone, Debug)]
+pub struct Factorials<N> {
+    acc: Option<N>,
+    n: N,
+}
+
+
+impl<N: CheckedInt> Iterator for Factorials<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value: N = self.acc?;
+
+        self.acc = self.n.checked_add(N::ONE).and_then(|n| {
+            self.n = n;
+            value.checked_mul(n)
+        });
+
+        Some(value)
+    }
+}
+
+
+impl<N: CheckedInt> FusedIterator for Factorials<N> {}
+
+
+/// An Iterator over the Fibonacci numbers which fit in `N`.
+#[derive(Clone, Debug)]
+pub struct Fibonacci<N> {
+    current: Option<N>,
+    next: Option<N>,
+}
+
+
+impl<N: CheckedInt> Iterator for Fibonacci<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value: N = self.current?;
+        let after: Option<N> = self.next.and_then(|next| next.checked_add(value));
+
+        self.current = self.next;
+        self.next = after;
+
+        Some(value)
+    }
+}
+
+
+impl<N: CheckedInt> FusedIterator for Fibonacci<N> {}
+
+
+/// An Iterator over the powers of a base which fit in `N`.
+#[derive(Clone, Debug)]
+pub struct Powers<N> {
+    base: N,
+    current: Option<N>,
+}
+
+
+impl<N: CheckedInt> Iterator for Powers<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value: N = self.current?;
+        self.current = value.checked_mul(self.base);
+        Some(value)
+    }
+}
+
+
+impl<N: CheckedInt> FusedIterator for Powers<N> {}
+
+
+/// Create a `MemoIter` over the factorials, `0!, 1!, 2!, ...`, as `BigUint`s.
+///     Unlike `factorials()`, the sequence is infinite.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sequences::factorials_big;
+///
+/// let mut fact = factorials_big();
+///
+/// let digits: String = fact.get(1000).unwrap().to_string();
+/// assert_eq!(digits.len(), 2568);
+/// assert!(digits.starts_with("402387260077"));
+/// ```
+#[cfg(feature = "num-bigint")]
+pub fn factorials_big() -> MemoIter<FactorialsBig, BigUint> {
+    MemoIter::new(FactorialsBig {
+        acc: BigUint::from(1u8),
+        n: 0,
+    })
+}
+
+
+/// Create a `MemoIter` over the Fibonacci sequence, `0, 1, 1, 2, 3, ...`, as
+///     `BigUint`s. Unlike `fibonacci()`, the sequence is infinite.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sequences::fibonacci_big;
+///
+/// let mut fib = fibonacci_big();
+///
+/// assert_eq!(fib.get(100).unwrap().to_string(), "354224848179261915075");
+/// ```
+#[cfg(feature = "num-bigint")]
+pub fn fibonacci_big() -> MemoIter<FibonacciBig, BigUint> {
+    MemoIter::new(FibonacciBig {
+        current: BigUint::from(0u8),
+        next: BigUint::from(1u8),
+    })
+}
+
+
+/// Create a `MemoIter` over row `n` of Pascal's triangle, the binomial
+///     coefficients `C(n, 0), C(n, 1), ..., C(n, n)`, as `BigUint`s. The row
+///     ends after its `n + 1` items.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sequences::binomial_row_big;
+///
+/// let mut row = binomial_row_big(100);
+///
+/// assert_eq!(row.get(50).unwrap().to_string(), "100891344545564193334812497256");
+/// assert_eq!(row.exhaust().len(), 101);
+/// ```
+#[cfg(feature = "num-bigint")]
+pub fn binomial_row_big(n: u64) -> MemoIter<BinomialRowBig, BigUint> {
+    MemoIter::new(BinomialRowBig {
+        current: Some(BigUint::from(1u8)),
+        k: 0,
+        n,
+    })
+}
+
+
+/// An Iterator over the factorials, as `BigUint`s.
+#[cfg(feature = "num-bigint")]
+#[derive(Clone, Debug)]
+pub struct FactorialsBig {
+    acc: BigUint,
+    n: u64,
+}
+
+
+#[cfg(feature = "num-bigint")]
+impl Iterator for FactorialsBig {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value: BigUint = self.acc.clone();
+
+        self.n += 1;
+        self.acc *= self.n;
+
+        Some(value)
+    }
+}
+
+
+#[cfg(feature = "num-bigint")]
+impl FusedIterator for FactorialsBig {}
+
+
+/// An Iterator over the Fibonacci numbers, as `BigUint`s.
+#[cfg(feature = "num-bigint")]
+#[derive(Clone, Debug)]
+pub struct FibonacciBig {
+    current: BigUint,
+    next: BigUint,
+}
+
+
+#[cfg(feature = "num-bigint")]
+impl Iterator for FibonacciBig {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let after: BigUint = &self.current + &self.next;
+        let value: BigUint = std::mem::replace(&mut self.current, std::mem::replace(&mut self.next, after));
+
+        Some(value)
+    }
+}
+
+
+#[cfg(feature = "num-bigint")]
+impl FusedIterator for FibonacciBig {}
+
+
+/// An Iterator over one row of Pascal's triangle, as `BigUint`s.
+#[cfg(feature = "num-bigint")]
+#[derive(Clone, Debug)]
+pub struct BinomialRowBig {
+    current: Option<BigUint>,
+    k: u64,
+    n: u64,
+}
+
+
+#[cfg(feature = "num-bigint")]
+impl Iterator for BinomialRowBig {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value: BigUint = self.current.take()?;
+
+        if self.k < self.n {
+            //  NOTE: `C(n, k) * (n - k)` is always divisible by `k + 1`, so
+            //      this division is exact.
+            self.current = Some(&value * (self.n - self.k) / (self.k + 1));
+            self.k += 1;
+        }
+
+        Some(value)
+    }
+}
+
+
+#[cfg(feature = "num-bigint")]
+impl FusedIterator for BinomialRowBig {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(factorials::<u8>().exhaust(), [1, 1, 2, 6, 24, 120]);
+        assert_eq!(factorials::<u64>().exhaust().len(), 21);
+        assert_eq!(factorials::<u128>().exhaust().len(), 35);
+
+        assert_eq!(fibonacci::<u64>().exhaust().len(), 94);
+        assert_eq!(fibonacci::<u64>().exhaust()[93], 12_200_160_415_121_876_738);
+
+        assert_eq!(powers(2u32).exhaust().len(), 32);
+        assert_eq!(powers(0u8).get_slice(..3), [1, 0, 0]);
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_big() {
+        let mut fact = factorials_big();
+        let mut small = factorials::<u128>();
+        let small: &[u128] = small.exhaust();
+
+        assert_eq!(fact.get(34).unwrap(), &BigUint::from(small[34]));
+        assert_eq!(fact.get(35).unwrap(), &(BigUint::from(small[34]) * 35u8));
+
+        let mut fib = fibonacci_big();
+        assert_eq!(fib.get(93).unwrap(), &BigUint::from(12_200_160_415_121_876_738u64));
+
+        let row: Vec<BigUint> = binomial_row_big(4).exhaust().to_vec();
+        assert_eq!(row, [1u8, 4, 6, 4, 1].iter().map(|&n| BigUint::from(n)).collect::<Vec<_>>());
+        assert_eq!(binomial_row_big(0).exhaust().len(), 1);
+    }
+}
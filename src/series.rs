@@ -0,0 +1,166 @@
+//! Operations on memoized sequences as the coefficients of power series.
+
+use std::ops::{Add, Mul};
+use crate::MemoIter;
+
+
+/// An Iterator over the Cauchy product, or discrete convolution, of two
+///     `MemoIter`s. Its item at index `n` is the sum of `a[k] * b[n - k]` for
+///     every `k` from zero to `n`. If the parents hold the coefficients of two
+///     power series, this produces the coefficients of their product.
+///
+/// Wrapping it in a `MemoIter` stores every coefficient once it is computed.
+///     The parents are `MemoIter`s as well, so each of their items is evaluated
+///     only once, however many coefficients of the product use it, and items
+///     are evaluated only as far as needed: the coefficient at `n` requires
+///     the first `n + 1` items of each parent.
+///
+/// If a parent is finite, the missing items are treated as zero. The product
+///     ends once no pair of items is left to multiply, which is after
+///     `a.len() + b.len() - 1` coefficients if both parents are finite. It is
+///     not fused, because a parent marked with `set_fused(false)` may return
+///     more items after ending, and so may the product.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::MemoIter;
+/// use std::iter::repeat;
+///
+/// //  Ways to pay `n` with coins of 1 and 2: the product of
+/// //      `1 / (1 - x)` and `1 / (1 - x²)`.
+/// let ones = MemoIter::new(repeat(1u64));
+/// let twos = MemoIter::new((0..).map(|n| (n % 2 == 0) as u64));
+///
+/// let mut ways = ones.cauchy_product(twos);
+///
+/// assert_eq!(ways.get_slice(..8), [1, 1, 2, 2, 3, 3, 4, 4]);
+/// ```
+#[derive(Debug)]
+pub struct Cauchy<A, B, TA, TB> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+{
+    left: MemoIter<A, TA>,
+    n: usize,
+    right: MemoIter<B, TB>,
+}
+
+
+impl<A, B, TA, TB> Cauchy<A, B, TA, TB> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+{
+    /// Create the Cauchy product of two `MemoIter`s. Nothing is evaluated
+    ///     until the first coefficient is requested.
+    pub fn new(left: MemoIter<A, TA>, right: MemoIter<B, TB>) -> Self {
+        Self { left, n: 0, right }
+    }
+
+    /// Return a reference to the `MemoIter` providing the first factor of each
+    ///     term.
+    #[inline]
+    pub fn left(&self) -> &MemoIter<A, TA> {
+        &self.left
+    }
+
+    /// Return a reference to the `MemoIter` providing the second factor of
+    ///     each term.
+    #[inline]
+    pub fn right(&self) -> &MemoIter<B, TB> {
+        &self.right
+    }
+
+    /// Consume self, returning both parent `MemoIter`s.
+    pub fn into_parts(self) -> (MemoIter<A, TA>, MemoIter<B, TB>) {
+        (self.left, self.right)
+    }
+}
+
+
+impl<A, B, TA, TB, U> Iterator for Cauchy<A, B, TA, TB> where
+    A: Iterator<Item=TA>,
+    B: Iterator<Item=TB>,
+    TA: Clone + Mul<TB, Output=U>,
+    TB: Clone,
+    U: Add<Output=U>,
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n: usize = self.n;
+        let mut sum: Option<U> = None;
+
+        //  NOTE: Evaluating `b[n]` first means that the whole of `b` needed for
+        //      this coefficient is evaluated in one step, rather than one item
+        //      at a time as `k` decreases.
+        self.right.get(n);
+
+        for k in 0..=n {
+            let a: &TA = match self.left.get(k) {
+                Some(a) => a,
+                None => break,
+            };
+
+            if let Some(b) = self.right.recall(n - k) {
+                let term: U = a.clone() * b.clone();
+
+                sum = Some(match sum {
+                    Some(sum) => sum + term,
+                    None => term,
+                });
+            }
+        }
+
+        if sum.is_some() {
+            self.n += 1;
+        }
+
+        sum
+    }
+}
+
+
+impl<A, TA> MemoIter<A, TA> where
+    A: Iterator<Item=TA>,
+{
+    /// Produce the Cauchy product of this `MemoIter` and another, as a
+    ///     `MemoIter` of coefficients computed on demand. See `Cauchy` for
+    ///     details.
+    pub fn cauchy_product<B, TB, U>(self, other: MemoIter<B, TB>) -> MemoIter<Cauchy<A, B, TA, TB>, U> where
+        B: Iterator<Item=TB>,
+        TA: Clone + Mul<TB, Output=U>,
+        TB: Clone,
+        U: Add<Output=U>,
+    {
+        MemoIter::new(Cauchy::new(self, other))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_cauchy() {
+        //  (1 + 2x + 3x²)(4 + 5x) = 4 + 13x + 22x² + 15x³
+        let mut finite = MemoIter::new(vec![1, 2, 3].into_iter())
+            .cauchy_product(MemoIter::new(vec![4, 5].into_iter()));
+
+        assert_eq!(finite.exhaust(), [4, 13, 22, 15]);
+
+        let left = CountingIter::new(1i64..);
+        let right = CountingIter::new(std::iter::repeat(-1i64));
+        let (left_count, right_count) = (left.counter(), right.counter());
+
+        let mut sums = MemoIter::new(left).cauchy_product(MemoIter::new(right));
+
+        assert_eq!(sums.get(3), Some(&-10));
+        assert_eq!(sums.get(1), Some(&-3));
+        assert_eq!(sums.get(99), Some(&-5050));
+        assert_eq!(left_count.times_advanced(), 100);
+        assert_eq!(right_count.times_advanced(), 100);
+    }
+}
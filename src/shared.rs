@@ -0,0 +1,424 @@
+//! Memoization shared between threads, through cheaply cloned handles.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+        Arc,
+        Mutex,
+    },
+};
+use crate::{
+    MemoIter,
+    segmented::{FIRST_SHIFT, locate},
+};
+
+
+/// The greatest number of segments needed to hold `usize::MAX` items.
+const SEGMENTS: usize = (usize::BITS - FIRST_SHIFT) as usize;
+
+
+/// Return the capacity of a segment.
+#[inline]
+fn capacity(seg: usize) -> usize {
+    1 << (seg as u32 + FIRST_SHIFT)
+}
+
+
+/// Append-only storage made of segments which are never moved, like the
+///     `Segments` of a `SegmentedMemoIter`, but whose length and segments are
+///     atomic, so that it may be read from any number of threads without
+///     locking while one thread appends to it.
+pub(crate) struct AtomicSegments<T> {
+    len: AtomicUsize,
+    segments: [AtomicPtr<T>; SEGMENTS],
+    _items: PhantomData<T>,
+}
+
+
+//  SAFETY: Items are written by one thread and read by others, so they must be
+//      both `Send` and `Sync`. Segments are owned, and freed only by `Drop`.
+unsafe impl<T: Send + Sync> Sync for AtomicSegments<T> {}
+unsafe impl<T: Send> Send for AtomicSegments<T> {}
+
+
+impl<T> AtomicSegments<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            len: AtomicUsize::new(0),
+            segments: std::array::from_fn(|_| AtomicPtr::new(ptr::null_mut())),
+            _items: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Option<&T> {
+        if idx < self.len() {
+            let (seg, off) = locate(idx);
+
+            //  SAFETY: The length is stored, with `Release`, only after the
+            //      item and its segment are written, so loading it with
+            //      `Acquire` makes both visible here. Written items are never
+            //      moved or mutated while `self` is alive.
+            unsafe {
+                Some(&*self.segments[seg].load(Ordering::Acquire).add(off))
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Append an item.
+    ///
+    /// # Safety
+    ///
+    /// This must not be called from more than one thread at once.
+    pub(crate) unsafe fn push(&self, item: T) {
+        let len: usize = self.len.load(Ordering::Relaxed);
+        let (seg, off) = locate(len);
+        let mut segment: *mut T = self.segments[seg].load(Ordering::Relaxed);
+
+        if segment.is_null() {
+            let mut slots: Vec<MaybeUninit<T>> = Vec::with_capacity(capacity(seg));
+            slots.resize_with(capacity(seg), MaybeUninit::uninit);
+
+            segment = Box::into_raw(slots.into_boxed_slice()) as *mut T;
+            self.segments[seg].store(segment, Ordering::Release);
+        }
+
+        //  SAFETY: The slot at the length has not been written, and is not yet
+        //      visible to readers. The caller ensures that no other push can
+        //      write to it at the same time.
+        unsafe {
+            segment.add(off).write(item);
+        }
+
+        self.len.store(len + 1, Ordering::Release);
+    }
+
+    pub(crate) fn into_vec(mut self) -> Vec<T> {
+        let len: usize = *self.len.get_mut();
+        let mut vec: Vec<T> = Vec::with_capacity(len);
+
+        for idx in 0..len {
+            let (seg, off) = locate(idx);
+
+            //  SAFETY: Every item below the length has been written. The length
+            //      is cleared afterwards, so that `Drop` does not drop them
+            //      again.
+            vec.push(unsafe { self.segments[seg].get_mut().add(off).read() });
+        }
+
+        *self.len.get_mut() = 0;
+        vec
+    }
+}
+
+
+impl<T> Drop for AtomicSegments<T> {
+    fn drop(&mut self) {
+        let len: usize = *self.len.get_mut();
+
+        for (seg, segment) in self.segments.iter_mut().enumerate() {
+            let segment: *mut T = *segment.get_mut();
+
+            if segment.is_null() {
+                break;
+            }
+
+            //  NOTE: Each segment holds as many items as every segment before
+            //      it, plus the capacity of the first.
+            let cap: usize = capacity(seg);
+            let init: usize = len.saturating_sub(cap - capacity(0)).min(cap);
+
+            //  SAFETY: The first `init` slots of the segment have been written,
+            //      and the segment was allocated as a boxed slice of `cap`.
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(segment, init));
+                drop(Box::from_raw(ptr::slice_from_raw_parts_mut(segment as *mut MaybeUninit<T>, cap)));
+            }
+        }
+    }
+}
+
+
+/// The state shared by every handle to a `SharedMemoIter`.
+struct Shared<I, T> {
+    exhausted: AtomicBool,
+    items: AtomicSegments<T>,
+    iterator: Mutex<I>,
+}
+
+
+/// A handle to a Memoized Iterator which may be shared between threads. Cloning
+///     a handle is cheap, and every clone refers to the same sequence, so an
+///     item evaluated through one handle can be retrieved through any other.
+///
+/// Retrieving an item already evaluated is wait-free: items are stored in
+///     segments that are never moved, and are found through an atomic length,
+///     without taking any lock. Only evaluating new items locks the Iterator,
+///     so one thread evaluates at a time, while readers of earlier items are
+///     never blocked. Items are returned by reference, and each reference stays
+///     valid for as long as the handle it came from.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::shared::SharedMemoIter;
+/// use std::thread;
+///
+/// let squares = SharedMemoIter::new((0u64..).map(|n| n * n));
+///
+/// let workers: Vec<_> = (0..4).map(|t| {
+///     let squares = squares.clone();
+///     thread::spawn(move || squares.get_cloned(t * 10).unwrap())
+/// }).collect();
+///
+/// let results: Vec<u64> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+/// assert_eq!(results, [0, 100, 400, 900]);
+///
+/// //  Every item up to the highest requested was evaluated exactly once.
+/// assert_eq!(squares.evaluated(), 31);
+/// assert_eq!(squares.get(12), Some(&144));
+/// ```
+pub struct SharedMemoIter<I, T> {
+    shared: Arc<Shared<I, T>>,
+}
+
+
+impl<I, T> SharedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `SharedMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self::from_parts(Vec::new(), iterator, false)
+    }
+
+    fn from_parts(sequence: Vec<T>, iterator: I, exhausted: bool) -> Self {
+        let items: AtomicSegments<T> = AtomicSegments::new();
+
+        for item in sequence {
+            //  SAFETY: The storage has not been shared yet.
+            unsafe { items.push(item); }
+        }
+
+        Self {
+            shared: Arc::new(Shared {
+                exhausted: AtomicBool::new(exhausted),
+                items,
+                iterator: Mutex::new(iterator),
+            }),
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.shared.items.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.shared.exhausted.load(Ordering::Acquire)
+    }
+
+    /// Return the number of handles to this sequence, including this one.
+    #[inline]
+    pub fn handles(&self) -> usize {
+        Arc::strong_count(&self.shared)
+    }
+
+    fn expand_to_contain(&self, idx: usize) {
+        if self.is_exhausted() || idx < self.evaluated() {
+            return;
+        }
+
+        let mut iterator = self.shared.iterator.lock().unwrap_or_else(|e| e.into_inner());
+
+        //  NOTE: Another thread may have evaluated the item while this one was
+        //      waiting for the Iterator, so this is checked again.
+        if self.is_exhausted() {
+            return;
+        }
+
+        while self.evaluated() <= idx {
+            match iterator.next() {
+                //  SAFETY: Items are only pushed while holding the Iterator.
+                Some(item) => unsafe { self.shared.items.push(item) },
+                None => {
+                    //  NOTE: This is set after every item is appended, so that
+                    //      a reader who sees it set will also see every item.
+                    self.shared.exhausted.store(true, Ordering::Release);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has,
+    ///     and any other thread evaluating this sequence will wait for it.
+    ///
+    /// # Panics
+    ///
+    /// This method may panic or deadlock if called from within the Iterator
+    ///     itself.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned. This never waits.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.shared.items.get(idx)
+    }
+
+    /// Call a function with a reference to an item, evaluating it if needed.
+    ///     Returns `None` if the Iterator ends first.
+    pub fn with<R, F>(&self, idx: usize, f: F) -> Option<R> where
+        F: FnOnce(&T) -> R,
+    {
+        self.get(idx).map(f)
+    }
+
+    /// Call a function with a reference to an item, if it has been evaluated.
+    ///     Nothing is evaluated.
+    pub fn recall_with<R, F>(&self, idx: usize, f: F) -> Option<R> where
+        F: FnOnce(&T) -> R,
+    {
+        self.recall(idx).map(f)
+    }
+
+    /// Retrieve a clone of an item, evaluating it if needed.
+    pub fn get_cloned(&self, idx: usize) -> Option<T> where
+        T: Clone,
+    {
+        self.get(idx).cloned()
+    }
+
+    /// Retrieve a clone of an item, if it has been evaluated. Nothing is
+    ///     evaluated.
+    pub fn recall_cloned(&self, idx: usize) -> Option<T> where
+        T: Clone,
+    {
+        self.recall(idx).cloned()
+    }
+
+    /// Consume this handle, returning a Vector of the values evaluated so far
+    ///     and the internal Iterator, if it is the only handle. Otherwise, the
+    ///     handle is returned unchanged.
+    pub fn try_consume(self) -> Result<(Vec<T>, I), Self> {
+        match Arc::try_unwrap(self.shared) {
+            Ok(shared) => Ok((
+                shared.items.into_vec(),
+                shared.iterator.into_inner().unwrap_or_else(|e| e.into_inner()),
+            )),
+            Err(shared) => Err(Self { shared }),
+        }
+    }
+}
+
+
+impl<I, T> Clone for SharedMemoIter<I, T> {
+    fn clone(&self) -> Self {
+        Self { shared: self.shared.clone() }
+    }
+}
+
+
+impl<I, T> Debug for SharedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedMemoIter")
+            .field("evaluated", &self.evaluated())
+            .field("exhausted", &self.is_exhausted())
+            .field("handles", &self.handles())
+            .finish_non_exhaustive()
+    }
+}
+
+
+impl<I, T> From<MemoIter<I, T>> for SharedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    fn from(memo: MemoIter<I, T>) -> Self {
+        let exhausted: bool = memo.is_exhausted();
+        let (sequence, iterator) = memo.consume();
+
+        Self::from_parts(sequence, iterator, exhausted)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_threads() {
+        let iter = CountingIter::new(0..1000);
+        let counter = iter.counter();
+        let memo = SharedMemoIter::new(iter);
+
+        let workers: Vec<_> = (0..8).map(|t| {
+            let memo = memo.clone();
+            thread::spawn(move || {
+                (0..1000).rev().step_by(8 - t).map(|idx| memo.get_cloned(idx).unwrap()).sum::<i32>()
+            })
+        }).collect();
+
+        for worker in workers {
+            assert!(worker.join().unwrap() > 0);
+        }
+
+        assert_eq!(counter.times_advanced(), 1000);
+        assert_eq!(memo.get_cloned(1000), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.recall_cloned(999), Some(999));
+
+        let other = memo.clone();
+        let memo = memo.try_consume().unwrap_err();
+        drop(other);
+
+        let (seq, _) = memo.try_consume().unwrap();
+        assert_eq!(seq.len(), 1000);
+    }
+
+    #[test]
+    fn test_drop() {
+        let token: Arc<()> = Arc::new(());
+        let memo = SharedMemoIter::new(std::iter::repeat(token.clone()));
+
+        //  Thirty items fill the segments of 8 and 16, and part of the next.
+        let held: &Arc<()> = memo.get(0).unwrap();
+        assert_eq!(memo.get(29), Some(held));
+        assert_eq!(Arc::strong_count(&token), 30 + 2);
+
+        drop(memo);
+        assert_eq!(Arc::strong_count(&token), 1);
+
+        let memo: SharedMemoIter<_, Arc<()>> = MemoIter::new(std::iter::repeat(token.clone())).into();
+        memo.get(40);
+
+        let (seq, iter) = memo.try_consume().unwrap();
+        assert_eq!(seq.len(), 41);
+        assert_eq!(Arc::strong_count(&token), 41 + 2);
+
+        drop((seq, iter));
+        assert_eq!(Arc::strong_count(&token), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! Memoization of tuples in struct-of-arrays form, with each field of the tuple
+//!     stored in its own Vector.
+
+use std::fmt::{self, Debug, Formatter};
+
+
+/// A tuple type which can be stored as a tuple of Vectors, one for each field.
+///     This is implemented for tuples of two, three and four fields.
+pub trait SoaItem: Sized {
+    /// The Vectors holding the fields of stored items.
+    type Columns: Default;
+    /// A tuple of references to the fields of one stored item.
+    type Ref<'a> where Self: 'a;
+
+    /// Split an item into its fields, and push each onto its column.
+    fn push(columns: &mut Self::Columns, item: Self);
+    /// Return references to the fields of the item at an index, which must be
+    ///     in bounds.
+    fn read(columns: &Self::Columns, idx: usize) -> Self::Ref<'_>;
+}
+
+
+/// A tuple type whose field at position `N` can be stored as a contiguous
+///     column.
+pub trait Column<const N: usize>: SoaItem {
+    /// The type of the field at position `N`.
+    type Field;
+
+    /// Return the column holding the field at position `N` of every item.
+    fn column(columns: &Self::Columns) -> &[Self::Field];
+}
+
+
+macro_rules! impl_soa_item {
+    ($(($($n:tt $t:ident),+);)*) => {$(
+        impl<$($t),+> SoaItem for ($($t,)+) {
+            type Columns = ($(Vec<$t>,)+);
+            type Ref<'a> = ($(&'a $t,)+) where Self: 'a;
+
+            #[inline]
+            fn push(columns: &mut Self::Columns, item: Self) {
+                $(columns.$n.push(item.$n);)+
+            }
+
+            #[inline]
+            fn read(columns: &Self::Columns, idx: usize) -> Self::Ref<'_> {
+                ($(&columns.$n[idx],)+)
+            }
+        }
+    )*};
+}
+
+impl_soa_item! {
+    (0 A, 1 B);
+    (0 A, 1 B, 2 C);
+    (0 A, 1 B, 2 C, 3 D);
+}
+
+
+macro_rules! impl_column {
+    ($($n:tt $field:ident in ($($t:ident),+);)*) => {$(
+        impl<$($t),+> Column<$n> for ($($t,)+) {
+            type Field = $field;
+
+            #[inline]
+            fn column(columns: &Self::Columns) -> &[$field] {
+                &columns.$n
+            }
+        }
+    )*};
+}
+
+impl_column! {
+    0 A in (A, B);
+    1 B in (A, B);
+    0 A in (A, B, C);
+    1 B in (A, B, C);
+    2 C in (A, B, C);
+    0 A in (A, B, C, D);
+    1 B in (A, B, C, D);
+    2 C in (A, B, C, D);
+    3 D in (A, B, C, D);
+}
+
+
+/// A Memoized Iterator over tuples, which stores each field of its items in a
+///     separate Vector. Every value of one field is then contiguous in memory,
+///     so that a computation over one field, such as a sum, does not need to
+///     load the other fields as well.
+///
+/// Items are retrieved as tuples of references to their fields. A whole column
+///     can be retrieved as a slice with `column()`, naming the position of its
+///     field as a constant parameter.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::soa::SoaMemoIter;
+///
+/// let mut memo = SoaMemoIter::new((1..).map(|n: u64| (n, 1.0 / n as f64)));
+///
+/// assert_eq!(memo.get(3), Some((&4, &0.25)));
+/// assert_eq!(memo.column::<0>(), [1, 2, 3, 4]);
+///
+/// let harmonic: f64 = memo.column::<1>().iter().sum();
+/// assert!((harmonic - 2.083).abs() < 0.001);
+/// ```
+pub struct SoaMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: SoaItem,
+{
+    columns: T::Columns,
+    exhausted: bool,
+    iterator: I,
+    len: usize,
+}
+
+
+impl<I, T> SoaMemoIter<I, T> where
+    I: Iterator<Item=T>,
+    T: SoaItem,
+{
+    /// Create an empty `SoaMemoIter` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            columns: T::Columns::default(),
+            exhausted: false,
+            iterator,
+            len: 0,
+        }
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.len <= idx {
+            match self.iterator.next() {
+                Some(item) => {
+                    T::push(&mut self.columns, item);
+                    self.len += 1;
+                }
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    /// Retrieve, by its index, the fields of an item returned by the Iterator.
+    ///     If the item has not yet been evaluated, the Iterator will be run
+    ///     until it has.
+    pub fn get(&mut self, idx: usize) -> Option<T::Ref<'_>> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, the fields of an item returned by the Iterator.
+    ///     If the item has not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<T::Ref<'_>> {
+        if idx < self.len {
+            Some(T::read(&self.columns, idx))
+        } else {
+            None
+        }
+    }
+
+    /// Return the field at position `N` of every item evaluated so far, as a
+    ///     contiguous slice.
+    #[inline]
+    pub fn column<const N: usize>(&self) -> &[<T as Column<N>>::Field] where
+        T: Column<N>,
+    {
+        <T as Column<N>>::column(&self.columns)
+    }
+
+    /// Evaluate the Iterator until the item at an index is stored, and return
+    ///     the field at position `N` of every item up to and including it. If
+    ///     the sequence ends first, every item is included.
+    pub fn get_column<const N: usize>(&mut self, idx: usize) -> &[<T as Column<N>>::Field] where
+        T: Column<N>,
+    {
+        self.expand_to_contain(idx);
+
+        let column: &[<T as Column<N>>::Field] = <T as Column<N>>::column(&self.columns);
+        &column[..column.len().min(idx.saturating_add(1))]
+    }
+
+    /// Return the columns of every item evaluated so far.
+    #[inline]
+    pub fn columns(&self) -> &T::Columns {
+        &self.columns
+    }
+
+    /// Consume self, returning the columns of every item evaluated so far, and
+    ///     the internal Iterator.
+    pub fn consume(self) -> (T::Columns, I) {
+        (self.columns, self.iterator)
+    }
+}
+
+
+impl<I, T> Debug for SoaMemoIter<I, T> where
+    I: Debug + Iterator<Item=T>,
+    T: SoaItem,
+    T::Columns: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoaMemoIter")
+            .field("columns", &self.columns)
+            .field("exhausted", &self.exhausted)
+            .field("iterator", &self.iterator)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        let mut memo = SoaMemoIter::new((0..5u8).map(|n| (n, n as i32 * -2, char::from(b'a' + n))));
+
+        assert_eq!(memo.get(2), Some((&2, &-4, &'c')));
+        assert_eq!(memo.recall(3), None);
+        assert_eq!(memo.column::<2>(), ['a', 'b', 'c']);
+        assert_eq!(memo.get_column::<1>(1), [0, -2]);
+
+        assert_eq!(memo.get_column::<0>(9), [0, 1, 2, 3, 4]);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.get(5), None);
+
+        let ((bytes, ints, chars), _) = memo.consume();
+        assert_eq!(bytes.len(), 5);
+        assert_eq!(ints[4], -8);
+        assert_eq!(chars.into_iter().collect::<String>(), "abcde");
+    }
+}
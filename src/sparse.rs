@@ -0,0 +1,479 @@
+//! Memoization of Iterators which can skip ahead cheaply, storing only the items
+//!     which are actually requested.
+
+use std::{collections::BTreeMap, ops::Range};
+
+
+/// A Memoized Iterator which may leave gaps in its stored sequence.
+///
+/// When an index is requested which is no more than `max_gap` items beyond
+///     what has been evaluated, every item up to it is evaluated and stored,
+///     as with a `MemoIter`. When an index is requested further ahead, the
+///     intermediate items are skipped with `Iterator::nth()`, and are never
+///     stored. Skipped items are recorded as gaps, and can not be retrieved
+///     later, because the Iterator cannot go back.
+///
+/// This is suited to sources where skipping is cheap, such as ranges, counter
+///     based generators, or readers, and where the intermediate values are not
+///     needed.
+///
+/// Values which are already known, such as those published in a reference
+///     table, may be inserted at any index with `insert_known()`. They will be
+///     returned without evaluating anything, and may fill gaps. If the
+///     Iterator later reaches a known value, the evaluated item is discarded;
+///     with `verify_known()`, it is first compared against the known value.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::sparse::SparseMemoIter;
+///
+/// let mut memo = SparseMemoIter::new((0..).map(|n| n * 2), 4);
+///
+/// assert_eq!(memo.get(3), Some(&6));
+/// assert_eq!(memo.get(1_000_000), Some(&2_000_000));
+///
+/// //  The items between these two requests were skipped.
+/// assert_eq!(memo.evaluated(), 5);
+/// assert!(memo.is_gap(500_000));
+/// assert_eq!(memo.get(500_000), None);
+/// assert_eq!(memo.gaps(), [4..1_000_000]);
+/// ```
+#[derive(Debug)]
+pub struct SparseMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    dense: Vec<T>,
+    exhausted: bool,
+    iterator: I,
+    max_gap: usize,
+    mismatches: Vec<usize>,
+    position: usize,
+    sparse: BTreeMap<usize, T>,
+    verify: Option<fn(&T, &T) -> bool>,
+}
+
+
+impl<I, T> SparseMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `SparseMemoIter` wrapping a given Iterator. Requests
+    ///     more than `max_gap` items beyond the position of the Iterator will
+    ///     skip the intermediate items.
+    pub fn new(iterator: I, max_gap: usize) -> Self {
+        Self {
+            dense: Vec::new(),
+            exhausted: false,
+            iterator,
+            max_gap,
+            mismatches: Vec::new(),
+            position: 0,
+            sparse: BTreeMap::new(),
+            verify: None,
+        }
+    }
+}
+
+
+impl<I, T> SparseMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the number of items stored, including known values.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.dense.len() + self.sparse.len()
+    }
+
+    /// Return the index of the next item the internal Iterator will return.
+    ///     Every index below this is either stored or a gap.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the largest number of items which will be evaluated, rather than
+    ///     skipped, to reach a requested index.
+    #[inline]
+    pub fn max_gap(&self) -> usize {
+        self.max_gap
+    }
+
+    /// Change the largest number of items which will be evaluated, rather than
+    ///     skipped, to reach a requested index.
+    #[inline]
+    pub fn set_max_gap(&mut self, max_gap: usize) {
+        self.max_gap = max_gap;
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Return `true` if the item at a given index was skipped, and can not be
+    ///     retrieved.
+    pub fn is_gap(&self, idx: usize) -> bool {
+        idx >= self.dense.len()
+            && idx < self.position
+            && !self.sparse.contains_key(&idx)
+    }
+
+    /// Return the ranges of indices which were skipped, in order.
+    pub fn gaps(&self) -> Vec<Range<usize>> {
+        let mut gaps: Vec<Range<usize>> = Vec::new();
+        let mut next: usize = self.dense.len();
+        let reached = self.sparse.range(..self.position).map(|(idx, _)| idx);
+
+        for &idx in reached.chain(Some(&self.position)) {
+            if idx > next {
+                gaps.push(next..idx);
+            }
+
+            next = next.max(idx + 1);
+        }
+
+        gaps
+    }
+
+    /// Return the contiguous prefix of the stored sequence, before any gap.
+    #[inline]
+    pub fn dense_prefix(&self) -> &[T] {
+        &self.dense
+    }
+
+    /// Insert a known value at a given index, which has not yet been reached or
+    ///     which is a gap. If a value is already stored at the index, the new
+    ///     value is returned in an `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::sparse::SparseMemoIter;
+    ///
+    /// let mut memo = SparseMemoIter::new((0..).map(|n| n * n), 0);
+    ///
+    /// memo.insert_known(1_000, 1_000_000).unwrap();
+    /// assert_eq!(memo.get(1_000), Some(&1_000_000));
+    /// assert_eq!(memo.position(), 0);
+    ///
+    /// memo.get(10);
+    /// assert!(memo.is_gap(5));
+    /// memo.insert_known(5, 25).unwrap();
+    /// assert_eq!(memo.get(5), Some(&25));
+    /// assert_eq!(memo.insert_known(5, 26), Err(26));
+    /// ```
+    pub fn insert_known(&mut self, idx: usize, value: T) -> Result<(), T> {
+        if self.recall(idx).is_some() {
+            Err(value)
+        } else {
+            self.sparse.insert(idx, value);
+            self.compact();
+            Ok(())
+        }
+    }
+
+    /// Compare items evaluated by the Iterator against any known values at
+    ///     their indices. When they differ, the evaluated item replaces the
+    ///     known value, and the index is recorded in `mismatches()`.
+    pub fn verify_known(&mut self, verify: bool) where
+        T: PartialEq,
+    {
+        self.verify = if verify { Some(T::eq) } else { None };
+    }
+
+    /// Return the indices at which a known value was found to differ from the
+    ///     item evaluated by the Iterator, while verification was enabled.
+    #[inline]
+    pub fn mismatches(&self) -> &[usize] {
+        &self.mismatches
+    }
+
+    /// Store an item evaluated by the Iterator, which must already have been
+    ///     advanced past its index.
+    fn store(&mut self, idx: usize, item: T) {
+        let item: T = match self.sparse.remove(&idx) {
+            None => item,
+            Some(known) => match self.verify {
+                Some(eq) if !eq(&known, &item) => {
+                    self.mismatches.push(idx);
+                    item
+                }
+                _ => known,
+            }
+        };
+
+        if idx == self.dense.len() {
+            self.dense.push(item);
+            self.compact();
+        } else {
+            self.sparse.insert(idx, item);
+        }
+    }
+
+    /// Move any reached values which directly follow the dense prefix into it.
+    fn compact(&mut self) {
+        while self.dense.len() < self.position {
+            match self.sparse.remove(&self.dense.len()) {
+                Some(item) => self.dense.push(item),
+                None => break,
+            }
+        }
+    }
+
+    fn mark_exhausted(&mut self) {
+        self.exhausted = true;
+        self.dense.shrink_to_fit();
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        if self.exhausted || idx < self.position {
+            return;
+        }
+
+        //  NOTE: The position after `usize::MAX` cannot be represented, so that
+        //      index is never reached, and the Iterator is left untouched.
+        let end: usize = match idx.checked_add(1) {
+            Some(end) => end,
+            None => return,
+        };
+        let gap: usize = idx - self.position;
+
+        if gap > self.max_gap {
+            match self.iterator.nth(gap) {
+                Some(item) => {
+                    self.position = end;
+                    self.store(idx, item);
+                }
+                None => self.mark_exhausted(),
+            }
+        } else {
+            while self.position <= idx {
+                match self.iterator.next() {
+                    Some(item) => {
+                        self.position += 1;
+                        self.store(self.position - 1, item);
+                    }
+                    None => {
+                        self.mark_exhausted();
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     at the index given has not yet been reached, it will be, either by
+    ///     evaluating or by skipping the items before it. Returns `None` if the
+    ///     index is a gap, or if the Iterator terminates before reaching it.
+    ///     An index of `usize::MAX` is never reached, and returns `None`
+    ///     unless a known value was inserted there.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        if self.recall(idx).is_none() {
+            self.expand_to_contain(idx);
+        }
+
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     at the index given has not yet been reached, it will **NOT** be
+    ///     reached now, and this method will return `None`.
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        match self.dense.get(idx) {
+            Some(item) => Some(item),
+            None => self.sparse.get(&idx),
+        }
+    }
+
+    fn recall_mut(&mut self, idx: usize) -> Option<&mut T> {
+        match self.dense.get_mut(idx) {
+            Some(item) => Some(item),
+            None => self.sparse.get_mut(&idx),
+        }
+    }
+
+    /// Return a `SparseEntry` for the value at a given index. Nothing is
+    ///     evaluated until a method of the `SparseEntry` requires it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::sparse::SparseMemoIter;
+    ///
+    /// let mut memo = SparseMemoIter::new((0..).map(|n| n * 3), 0);
+    ///
+    /// assert_eq!(memo.entry(10).or_eval(), Some(&mut 30));
+    /// assert_eq!(memo.entry(5).or_eval(), None);
+    /// assert_eq!(memo.entry(5).or_insert(15), &mut 15);
+    ///
+    /// memo.entry(10).and_modify(|n| *n += 1);
+    /// assert_eq!(memo.get(10), Some(&31));
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, idx: usize) -> SparseEntry<'_, I, T> {
+        SparseEntry { idx, memo: self }
+    }
+
+    /// Consume self, returning the stored items with their indices, in order,
+    ///     including known values, and the original Iterator.
+    pub fn consume(self) -> (Vec<(usize, T)>, I) {
+        let Self { dense, iterator, sparse, .. } = self;
+
+        let items: Vec<(usize, T)> = dense.into_iter()
+            .enumerate()
+            .chain(sparse)
+            .collect();
+
+        (items, iterator)
+    }
+}
+
+
+/// A view into a single index of a `SparseMemoIter`, which may or may not hold
+///     a value yet. Returned by `SparseMemoIter::entry()`.
+#[derive(Debug)]
+pub struct SparseEntry<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    idx: usize,
+    memo: &'a mut SparseMemoIter<I, T>,
+}
+
+
+impl<'a, I, T> SparseEntry<'a, I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the index of this entry.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// Return `true` if a value is stored at this entry.
+    #[inline]
+    pub fn is_stored(&self) -> bool {
+        self.memo.recall(self.idx).is_some()
+    }
+
+    /// Call a function on the value of this entry, if one is stored. Nothing is
+    ///     evaluated.
+    pub fn and_modify<F>(self, func: F) -> Self where
+        F: FnOnce(&mut T),
+    {
+        if let Some(item) = self.memo.recall_mut(self.idx) {
+            func(item);
+        }
+
+        self
+    }
+
+    /// Return the value of this entry, reaching it with the Iterator if needed,
+    ///     as with `SparseMemoIter::get()`. Returns `None` if the index is a
+    ///     gap, or if the Iterator ends first.
+    pub fn or_eval(self) -> Option<&'a mut T> {
+        self.memo.get(self.idx);
+        self.memo.recall_mut(self.idx)
+    }
+
+    /// Return the value of this entry, inserting a given value as a known value
+    ///     if none is stored. Nothing is evaluated.
+    pub fn or_insert(self, value: T) -> &'a mut T {
+        self.or_insert_with(|| value)
+    }
+
+    /// Return the value of this entry, inserting the result of a function as a
+    ///     known value if none is stored, as with
+    ///     `SparseMemoIter::insert_known()`. Nothing is evaluated.
+    pub fn or_insert_with<F>(self, func: F) -> &'a mut T where
+        F: FnOnce() -> T,
+    {
+        if self.memo.recall(self.idx).is_none() {
+            self.memo.sparse.insert(self.idx, func());
+            self.memo.compact();
+        }
+
+        self.memo.recall_mut(self.idx).unwrap()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CountingIter;
+
+    #[test]
+    fn test_sparse() {
+        let iter = CountingIter::new(0..100);
+        let counter = iter.counter();
+        let mut memo = SparseMemoIter::new(iter, 3);
+
+        assert_eq!(memo.get(2), Some(&2));
+        assert_eq!(memo.get(6), Some(&6));
+        assert_eq!(memo.dense_prefix(), [0, 1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(memo.get(20), Some(&20));
+        assert_eq!(memo.get(22), Some(&22));
+        assert_eq!(memo.get(30), Some(&30));
+
+        assert!(memo.is_gap(10));
+        assert!(!memo.is_gap(21));
+        assert_eq!(memo.get(21), Some(&21));
+        assert_eq!(memo.get(10), None);
+        assert_eq!(memo.gaps(), [7..20, 23..30]);
+        assert_eq!(memo.evaluated(), 7 + 3 + 1);
+
+        //  The skipped items were still advanced over, but are not stored.
+        assert_eq!(counter.times_advanced(), 31);
+
+        assert_eq!(memo.get(usize::MAX), None);
+        assert_eq!(counter.times_advanced(), 31);
+
+        assert_eq!(memo.get(500), None);
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.get(99), None);
+        assert_eq!(memo.get(30), Some(&30));
+
+        let (items, _) = memo.consume();
+        assert_eq!(items.len(), 11);
+        assert_eq!(items.last(), Some(&(30, 30)));
+    }
+
+    #[test]
+    fn test_known() {
+        let mut memo = SparseMemoIter::new(0..100, 5);
+        memo.verify_known(true);
+
+        memo.insert_known(3, 3).unwrap();
+        memo.insert_known(4, 40).unwrap();
+        memo.insert_known(50, 50).unwrap();
+        assert_eq!(memo.get(50), Some(&50));
+        assert_eq!(memo.position(), 0);
+
+        //  Reaching the known values reconciles them against the source.
+        assert_eq!(memo.get(5), Some(&5));
+        assert_eq!(memo.get(4), Some(&4));
+        assert_eq!(memo.mismatches(), [4]);
+        assert_eq!(memo.dense_prefix(), [0, 1, 2, 3, 4, 5]);
+
+        //  Skipping past a known value leaves it in place.
+        assert_eq!(memo.get(60), Some(&60));
+        assert_eq!(memo.gaps(), [6..50, 51..60]);
+
+        //  Filling a gap adjacent to the dense prefix extends it.
+        memo.insert_known(6, 6).unwrap();
+        memo.insert_known(7, 7).unwrap();
+        assert_eq!(memo.dense_prefix(), [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(memo.gaps(), [8..50, 51..60]);
+        assert_eq!(memo.insert_known(60, 0), Err(0));
+
+        memo.verify_known(false);
+        memo.insert_known(70, 700).unwrap();
+        assert_eq!(memo.get(75), Some(&75));
+        assert_eq!(memo.get(70), Some(&700));
+        assert_eq!(memo.mismatches(), [4]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Statistical summaries of the items evaluated by a `MemoIter`.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    iter::{Product, Sum},
+};
+use crate::MemoIter;
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the item at quantile `q` of the items evaluated so far, where `q`
+    ///     is between zero and one. Nothing new is evaluated.
+    ///
+    /// The item returned is the one which would be at index `⌊q × (n - 1)⌋` if
+    ///     the `n` evaluated items were sorted, so that a `q` of zero gives the
+    ///     minimum and a `q` of one gives the maximum. It is found by selection
+    ///     rather than by sorting, in linear time on average.
+    ///
+    /// Returns `None` if nothing has been evaluated, or if `q` is not between
+    ///     zero and one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut memo = MemoIter::new((0..).map(|n: i32| (n * 37) % 101));
+    /// memo.get(99);
+    ///
+    /// assert_eq!(memo.quantile(0.0), Some(&0));
+    /// assert_eq!(memo.quantile(0.9), Some(&90));
+    /// assert_eq!(memo.quantile(1.0), Some(&100));
+    /// assert_eq!(memo.quantile(1.5), None);
+    /// ```
+    pub fn quantile(&self, q: f64) -> Option<&T> where
+        T: Ord,
+    {
+        if self.sequence.is_empty() || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+
+        let rank: usize = (q * (self.sequence.len() - 1) as f64) as usize;
+        let mut items: Vec<&T> = self.sequence.iter().collect();
+        let (_, &mut item, _) = items.select_nth_unstable(rank);
+
+        Some(item)
+    }
+
+    /// Return the median of the items evaluated so far, or `None` if nothing
+    ///     has been evaluated. Of an even number of items, the lower of the
+    ///     two middle items is returned.
+    #[inline]
+    pub fn median_evaluated(&self) -> Option<&T> where
+        T: Ord,
+    {
+        self.quantile(0.5)
+    }
+
+    /// Count the occurrences of each distinct item evaluated so far. Nothing
+    ///     new is evaluated. The keys of the map borrow from the `MemoIter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut rolls = MemoIter::new((0u32..).map(|n| n * 7 % 6 + 1));
+    /// rolls.get(59);
+    ///
+    /// let counts = rolls.frequencies();
+    /// assert_eq!(counts.len(), 6);
+    /// assert!(counts.values().all(|&n| n == 10));
+    /// assert_eq!(counts[&3], 10);
+    /// ```
+    pub fn frequencies(&self) -> HashMap<&T, usize> where
+        T: Eq + Hash,
+    {
+        let mut counts: HashMap<&T, usize> = HashMap::new();
+
+        for item in &self.sequence {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Return the sum of the items evaluated so far. Nothing new is evaluated.
+    #[inline]
+    pub fn sum_evaluated<'a, S>(&'a self) -> S where
+        S: Sum<&'a T>,
+    {
+        self.sequence.iter().sum()
+    }
+
+    /// Return the product of the items evaluated so far. Nothing new is
+    ///     evaluated.
+    #[inline]
+    pub fn product_evaluated<'a, S>(&'a self) -> S where
+        S: Product<&'a T>,
+    {
+        self.sequence.iter().product()
+    }
+
+    /// Return the sum of the first `n` items, evaluating them if needed. If the
+    ///     Iterator ends before `n` items, the sum of all of them is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::MemoIter;
+    ///
+    /// let mut odds = MemoIter::new((0u64..).map(|n| 2 * n + 1));
+    ///
+    /// assert_eq!(odds.sum_to::<u64>(10), 100);
+    /// assert_eq!(odds.evaluated(), 10);
+    /// assert_eq!(odds.sum_evaluated::<u64>(), 100);
+    /// assert_eq!(odds.product_to::<u64>(4), 105);
+    /// ```
+    pub fn sum_to<'a, S>(&'a mut self, n: usize) -> S where
+        S: Sum<&'a T>,
+    {
+        self.get_slice(..n).iter().sum()
+    }
+
+    /// Return the product of the first `n` items, evaluating them if needed. If
+    ///     the Iterator ends before `n` items, the product of all of them is
+    ///     returned.
+    pub fn product_to<'a, S>(&'a mut self, n: usize) -> S where
+        S: Product<&'a T>,
+    {
+        self.get_slice(..n).iter().product()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile() {
+        let mut memo = MemoIter::new(vec![5, 1, 4, 2, 3, 6].into_iter());
+
+        assert_eq!(memo.median_evaluated(), None);
+        assert_eq!(memo.quantile(f64::NAN), None);
+
+        memo.get(4);
+        assert_eq!(memo.median_evaluated(), Some(&3));
+
+        memo.exhaust();
+        assert_eq!(memo.median_evaluated(), Some(&3));
+        assert_eq!(memo.quantile(0.2), Some(&2));
+        assert_eq!(&memo[..], [5, 1, 4, 2, 3, 6]);
+    }
+
+    #[test]
+    fn test_frequencies() {
+        let mut memo = MemoIter::new("mississippi".chars());
+        assert!(memo.frequencies().is_empty());
+
+        memo.exhaust();
+        let counts = memo.frequencies();
+
+        assert_eq!(counts[&'s'], 4);
+        assert_eq!(counts[&'m'], 1);
+        assert_eq!(counts.get(&'x'), None);
+    }
+
+    #[test]
+    fn test_sums() {
+        let mut memo = MemoIter::new(1..=5);
+
+        assert_eq!(memo.sum_evaluated::<i32>(), 0);
+        assert_eq!(memo.product_evaluated::<i32>(), 1);
+        assert_eq!(memo.sum_to::<i32>(0), 0);
+        assert_eq!(memo.product_to::<i32>(3), 6);
+        assert_eq!(memo.sum_to::<i32>(100), 15);
+        assert_eq!(memo.product_evaluated::<i32>(), 120);
+    }
+}
@@ -0,0 +1,166 @@
+//! Strategies for generating `MemoIter`s with `proptest`.
+//!
+//! Each strategy produces a `MemoIter` over a Vector-backed source, in one of
+//!     the states a `MemoIter` can reach through normal use. The states are
+//!     reached by calling the same methods a user would, rather than by
+//!     constructing the internals directly, so that property tests exercise
+//!     the real paths through the state machine.
+//!
+//! ```
+//! use memoiter::strategy;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn evaluation_is_idempotent(mut memo in strategy::any_state(any::<u8>(), 0..32)) {
+//!         let before: Vec<u8> = memo.get_slice(..).to_vec();
+//!         let total: usize = memo.total_len();
+//!
+//!         prop_assert_eq!(&memo.get_slice(..total)[..before.len()], &before[..]);
+//!     }
+//! }
+//! # evaluation_is_idempotent();
+//! ```
+
+use proptest::{collection::{SizeRange, vec}, prelude::*, sample::Index};
+use std::{fmt::Debug, vec::IntoIter};
+use crate::MemoIter;
+
+
+/// A `MemoIter` over an owned Vector, as produced by the strategies in this
+///     module.
+pub type VecMemoIter<T> = MemoIter<IntoIter<T>, T>;
+
+
+/// Generate a `MemoIter` which has not yet evaluated anything.
+pub fn empty<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value=VecMemoIter<S::Value>> where
+    S: Strategy,
+    S::Value: Debug,
+{
+    vec(element, size).prop_map(|v| MemoIter::new(v.into_iter()))
+}
+
+
+/// Generate a `MemoIter` which has evaluated some prefix of its sequence, but
+///     which has not been exhausted.
+pub fn partial<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value=VecMemoIter<S::Value>> where
+    S: Strategy,
+    S::Value: Debug,
+{
+    split(element, size).prop_map(|(v, n)| {
+        let mut memo = MemoIter::new(v.into_iter());
+
+        if let Some(last) = n.checked_sub(1) {
+            memo.get(last);
+        }
+
+        memo
+    })
+}
+
+
+/// Generate a `MemoIter` which has evaluated its entire sequence, and found
+///     the end of its Iterator.
+pub fn exhausted<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value=VecMemoIter<S::Value>> where
+    S: Strategy,
+    S::Value: Debug,
+{
+    vec(element, size).prop_map(|v| {
+        let len: usize = v.len();
+        let mut memo = MemoIter::new(v.into_iter());
+        memo.get(len);
+        memo
+    })
+}
+
+
+/// Generate a `MemoIter` which was constructed with `MemoIter::with_vec()`,
+///     with some prefix of its sequence supplied up front.
+pub fn seeded<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value=VecMemoIter<S::Value>> where
+    S: Strategy,
+    S::Value: Debug,
+{
+    split(element, size).prop_map(|(mut v, n)| {
+        let rest: Vec<S::Value> = v.split_off(n);
+        MemoIter::with_vec(rest.into_iter(), v)
+    })
+}
+
+
+/// Generate a `MemoIter` in any of the states produced by the other strategies
+///     in this module.
+pub fn any_state<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value=VecMemoIter<S::Value>> where
+    S: Clone + Strategy + 'static,
+    S::Value: Debug,
+{
+    let size: SizeRange = size.into();
+
+    prop_oneof![
+        empty(element.clone(), size.clone()),
+        partial(element.clone(), size.clone()),
+        exhausted(element.clone(), size.clone()),
+        seeded(element, size),
+    ]
+}
+
+
+/// Generate a Vector, along with an index at which it may be split.
+fn split<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value=(Vec<S::Value>, usize)> where
+    S: Strategy,
+    S::Value: Debug,
+{
+    (vec(element, size), any::<Index>()).prop_map(|(v, idx)| {
+        let n: usize = idx.index(v.len() + 1);
+        (v, n)
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use proptest::{strategy::ValueTree, test_runner::TestRunner};
+    use super::*;
+
+    #[test]
+    fn test_empty_source() {
+        let mut runner: TestRunner = TestRunner::deterministic();
+        let mut generate = |strategy: BoxedStrategy<VecMemoIter<u8>>| {
+            strategy.new_tree(&mut runner).unwrap().current()
+        };
+
+        //  With nothing to evaluate, only `exhausted()` may find the end.
+        assert!(!generate(empty(any::<u8>(), 0..1).boxed()).is_exhausted());
+        assert!(!generate(partial(any::<u8>(), 0..1).boxed()).is_exhausted());
+        assert!(!generate(seeded(any::<u8>(), 0..1).boxed()).is_exhausted());
+        assert!(generate(exhausted(any::<u8>(), 0..1).boxed()).is_exhausted());
+    }
+
+    proptest! {
+        #[test]
+        fn test_states(
+            mut e in empty(any::<u8>(), 0..16),
+            mut x in exhausted(any::<u8>(), 0..16),
+            mut p in partial(any::<u8>(), 0..16),
+            mut s in seeded(any::<u8>(), 0..16),
+        ) {
+            prop_assert_eq!(e.evaluated(), 0);
+            prop_assert!(!e.is_exhausted());
+
+            prop_assert!(x.is_exhausted());
+            prop_assert_eq!(x.evaluated(), x.total_len());
+
+            prop_assert!(!p.is_exhausted());
+            prop_assert!(!s.is_exhausted());
+
+            for memo in [&mut e, &mut x, &mut p, &mut s].iter_mut() {
+                let total: usize = memo.total_len();
+                prop_assert_eq!(memo.get_slice(..total).len(), total);
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Conversion of a `MemoIter` into an asynchronous `Stream`.
+
+use futures_core::Stream;
+use std::{
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    panic::resume_unwind,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::task::{JoinError, JoinHandle, spawn_blocking};
+use crate::MemoIter;
+
+
+/// The result of evaluating one item on a blocking thread.
+type Evaluation<I, T> = (Box<MemoIter<I, T>>, Option<T>);
+
+
+enum State<I, T> where
+    I: Iterator<Item=T>,
+{
+    Idle(Box<MemoIter<I, T>>),
+    Evaluating(JoinHandle<Evaluation<I, T>>),
+    Done,
+}
+
+
+/// A `Stream` over the items of a `MemoIter`, returned by
+///     `MemoIter::into_stream()`.
+///
+/// Items which are already stored are yielded immediately. Each new item is
+///     evaluated by a task on the blocking thread pool of `tokio`, so that an
+///     expensive Iterator does not stall the executor. It must therefore be
+///     polled within a `tokio` runtime.
+pub struct MemoStream<I, T> where
+    I: Iterator<Item=T>,
+{
+    next: usize,
+    state: State<I, T>,
+}
+
+
+impl<I, T> MemoStream<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Return the index of the next item to be yielded.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.next
+    }
+
+    /// Consume self, returning the `MemoIter`, unless an item is being
+    ///     evaluated, or the Stream has ended because an evaluation task was
+    ///     cancelled.
+    pub fn into_inner(self) -> Option<MemoIter<I, T>> {
+        match self.state {
+            State::Idle(memo) => Some(*memo),
+            _ => None,
+        }
+    }
+}
+
+
+impl<I, T> Debug for MemoStream<I, T> where
+    I: Iterator<Item=T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state: &str = match self.state {
+            State::Idle(_) => "Idle",
+            State::Evaluating(_) => "Evaluating",
+            State::Done => "Done",
+        };
+
+        f.debug_struct("MemoStream")
+            .field("next", &self.next)
+            .field("state", &state)
+            .finish()
+    }
+}
+
+
+impl<I, T> Stream for MemoStream<I, T> where
+    I: Iterator<Item=T> + Send + 'static,
+    T: Clone + Send + 'static,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this: &mut Self = self.get_mut();
+
+        loop {
+            match &mut this.state {
+                State::Idle(memo) => {
+                    if let Some(item) = memo.sequence.get(this.next) {
+                        this.next += 1;
+                        return Poll::Ready(Some(item.clone()));
+                    } else if memo.is_exhausted() {
+                        return Poll::Ready(None);
+                    }
+
+                    let idx: usize = this.next;
+                    let mut memo: Box<MemoIter<I, T>> = match std::mem::replace(&mut this.state, State::Done) {
+                        State::Idle(memo) => memo,
+                        _ => unreachable!(),
+                    };
+
+                    this.state = State::Evaluating(spawn_blocking(move || {
+                        let item: Option<T> = memo.get(idx).cloned();
+                        (memo, item)
+                    }));
+                }
+                State::Evaluating(handle) => {
+                    let result: Result<Evaluation<I, T>, JoinError> = match Pin::new(handle).poll(cx) {
+                        Poll::Ready(result) => result,
+                        Poll::Pending => return Poll::Pending,
+                    };
+
+                    match result {
+                        Ok((memo, item)) => {
+                            this.state = State::Idle(memo);
+
+                            if item.is_none() {
+                                return Poll::Ready(None);
+                            }
+                        }
+                        Err(e) => {
+                            this.state = State::Done;
+
+                            if e.is_panic() {
+                                resume_unwind(e.into_panic());
+                            }
+
+                            return Poll::Ready(None);
+                        }
+                    }
+                }
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+
+impl<I, T> MemoIter<I, T> where
+    I: Iterator<Item=T> + Send + 'static,
+    T: Clone + Send + 'static,
+{
+    /// Convert this `MemoIter` into a `Stream` of clones of its items, starting
+    ///     from the first, for use from asynchronous code. Stored items are
+    ///     yielded at once, and new items are evaluated on the blocking thread
+    ///     pool of `tokio`, so the Stream must be polled within a `tokio`
+    ///     runtime.
+    ///
+    /// The `MemoIter`, with every item evaluated for the Stream, can be taken
+    ///     back with `MemoStream::into_inner()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_core::Stream;
+    /// use memoiter::MemoIter;
+    /// use std::{future::poll_fn, pin::Pin};
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    ///
+    /// let mut memo = MemoIter::new((1u64..=5).map(|n| n.pow(3)));
+    /// memo.get(1);
+    /// let mut stream = memo.into_stream();
+    ///
+    /// let items: Vec<u64> = runtime.block_on(async {
+    ///     let mut items = Vec::new();
+    ///
+    ///     while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+    ///         items.push(item);
+    ///     }
+    ///
+    ///     items
+    /// });
+    ///
+    /// assert_eq!(items, [1, 8, 27, 64, 125]);
+    /// assert!(stream.into_inner().unwrap().is_exhausted());
+    /// ```
+    pub fn into_stream(self) -> MemoStream<I, T> {
+        MemoStream {
+            next: 0,
+            state: State::Idle(Box::new(self)),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::future::poll_fn;
+    use tokio::runtime::Builder;
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_stream() {
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let source = CountingIter::new(0..4);
+        let counter = source.counter();
+
+        let mut memo = MemoIter::new(source);
+        memo.get(2);
+
+        let mut stream = memo.into_stream();
+        let mut next = || runtime.block_on(poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)));
+
+        assert_eq!(next(), Some(0));
+        assert_eq!(next(), Some(1));
+        assert_eq!(next(), Some(2));
+        assert_eq!(counter.times_advanced(), 3);
+
+        assert_eq!(next(), Some(3));
+        assert_eq!(next(), None);
+        assert_eq!(next(), None);
+        assert_eq!(counter.times_advanced(), 5);
+    }
+}
@@ -0,0 +1,276 @@
+//! Utilities for testing code which uses `MemoIter`s.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    sync::{Arc, atomic::{AtomicUsize, Ordering}},
+    thread::sleep,
+    time::Duration,
+};
+
+
+/// An Iterator wrapper which counts the number of times its inner Iterator has
+///     been advanced. This can be used to assert that a `MemoIter` has not
+///     evaluated an item more than once.
+///
+/// Because a `MemoIter` takes ownership of its Iterator, the count is shared
+///     with any `Counter` handles retrieved from `CountingIter::counter()`,
+///     which remain readable after the `CountingIter` has been moved. Clones of
+///     a `CountingIter` also share the same count.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, testing::CountingIter};
+///
+/// let iter = CountingIter::new(0..100);
+/// let counter = iter.counter();
+/// let mut memo = MemoIter::new(iter);
+///
+/// assert_eq!(memo.get(9), Some(&9));
+/// assert_eq!(counter.times_advanced(), 10);
+///
+/// assert_eq!(memo.get(5), Some(&5));
+/// assert_eq!(counter.times_advanced(), 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CountingIter<I> {
+    count: Counter,
+    iterator: I,
+}
+
+
+impl<I: Iterator> CountingIter<I> {
+    /// Wrap an Iterator, beginning with a count of zero.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            count: Counter::default(),
+            iterator,
+        }
+    }
+
+    /// Return a handle to the count of this Iterator.
+    pub fn counter(&self) -> Counter {
+        self.count.clone()
+    }
+
+    /// Return the number of times `next()` has been called on this Iterator.
+    #[inline]
+    pub fn times_advanced(&self) -> usize {
+        self.count.times_advanced()
+    }
+}
+
+
+impl<I: Iterator> Iterator for CountingIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.count.0.fetch_add(1, Ordering::Relaxed);
+        self.iterator.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+
+impl<I: ExactSizeIterator> ExactSizeIterator for CountingIter<I> {}
+
+
+/// A shared handle to the count of a `CountingIter`.
+#[derive(Clone, Debug, Default)]
+pub struct Counter(Arc<AtomicUsize>);
+
+
+impl Counter {
+    /// Return the number of times `next()` has been called on the associated
+    ///     `CountingIter`.
+    #[inline]
+    pub fn times_advanced(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+
+/// The error returned by a `FailingIter` in place of an item.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct InjectedError {
+    /// The position of the item which was not returned.
+    pub index: usize,
+    /// The number of failed attempts to return the item, including this one.
+    pub attempt: usize,
+}
+
+
+impl Display for InjectedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "injected failure of item {} (attempt {})", self.index, self.attempt)
+    }
+}
+
+
+impl Error for InjectedError {}
+
+
+#[derive(Clone, Copy, Debug)]
+enum Fault {
+    Error(usize),
+    Panic,
+    Stall(Duration),
+}
+
+
+/// An Iterator wrapper which fails in chosen ways at chosen positions, so that
+///     the handling of failures by code using `MemoIter`s can be tested
+///     deterministically.
+///
+/// Items are returned as `Ok`, or replaced with an `InjectedError` where one
+///     has been configured. A failed item is not skipped: The inner Iterator is
+///     only advanced when an item is returned successfully, so the next call
+///     tries the same position again, as is expected by `retry::Retry`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, retry::Retry, testing::FailingIter};
+/// use std::time::Duration;
+///
+/// let source = FailingIter::new(0..)
+///     .error_at(2, 1)
+///     .error_from(5);
+///
+/// let retrying = Retry::new(source).backoff(Duration::ZERO, 1.0);
+/// let mut memo = MemoIter::new(retrying);
+///
+/// assert_eq!(memo.get(2), Some(&Ok(2)));
+/// assert!(memo.get(5).unwrap().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct FailingIter<I> {
+    attempts: usize,
+    end: Option<usize>,
+    error_from: Option<usize>,
+    faults: BTreeMap<usize, Fault>,
+    index: usize,
+    iterator: I,
+}
+
+
+impl<I: Iterator> FailingIter<I> {
+    /// Wrap an Iterator, with no failures configured.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            attempts: 0,
+            end: None,
+            error_from: None,
+            faults: BTreeMap::new(),
+            index: 0,
+            iterator,
+        }
+    }
+
+    /// Return an error instead of the item at `idx`, the first `times` times it
+    ///     is requested. It is returned normally after that.
+    pub fn error_at(mut self, idx: usize, times: usize) -> Self {
+        self.faults.insert(idx, Fault::Error(times));
+        self
+    }
+
+    /// Return an error every time an item at `idx` or later is requested. The
+    ///     Iterator never gets past `idx`.
+    pub fn error_from(mut self, idx: usize) -> Self {
+        self.error_from = Some(idx);
+        self
+    }
+
+    /// Panic when the item at `idx` is requested.
+    pub fn panic_at(mut self, idx: usize) -> Self {
+        self.faults.insert(idx, Fault::Panic);
+        self
+    }
+
+    /// Wait for `delay` before returning the item at `idx`, each time it is
+    ///     requested.
+    pub fn stall_at(mut self, idx: usize, delay: Duration) -> Self {
+        self.faults.insert(idx, Fault::Stall(delay));
+        self
+    }
+
+    /// End the sequence at `idx`, returning `None` instead of the item at `idx`
+    ///     and every item after it.
+    pub fn end_at(mut self, idx: usize) -> Self {
+        self.end = Some(idx);
+        self
+    }
+
+    /// Return the position of the next item to be requested.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    fn fail(&mut self) -> InjectedError {
+        self.attempts += 1;
+        InjectedError { index: self.index, attempt: self.attempts }
+    }
+}
+
+
+impl<I: Iterator> Iterator for FailingIter<I> {
+    type Item = Result<I::Item, InjectedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx: usize = self.index;
+
+        if self.end.is_some_and(|end| idx >= end) {
+            return None;
+        }
+
+        if self.error_from.is_some_and(|from| idx >= from) {
+            return Some(Err(self.fail()));
+        }
+
+        match self.faults.get(&idx) {
+            Some(Fault::Error(times)) if self.attempts < *times => {
+                return Some(Err(self.fail()));
+            }
+            Some(Fault::Panic) => panic!("injected panic at item {}", idx),
+            Some(&Fault::Stall(delay)) => sleep(delay),
+            _ => {}
+        }
+
+        let item: I::Item = self.iterator.next()?;
+
+        self.attempts = 0;
+        self.index += 1;
+        Some(Ok(item))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{MemoIter, checked::{AccessError, CheckedMemoIter}};
+    use super::*;
+
+    #[test]
+    fn test_failures() {
+        let mut memo = MemoIter::new(FailingIter::new("abcdef".chars()).error_at(1, 2).end_at(4));
+
+        assert_eq!(memo.get(0), Some(&Ok('a')));
+        assert_eq!(memo.get(1), Some(&Err(InjectedError { index: 1, attempt: 1 })));
+        assert_eq!(memo.get(3), Some(&Ok('b')));
+        assert_eq!(memo.exhaust().len(), 6);
+
+        let failing = FailingIter::new(0..10).panic_at(3);
+        let mut checked = CheckedMemoIter::new(MemoIter::new(failing));
+
+        assert_eq!(checked.get(2), Ok(&Ok(2)));
+        assert_eq!(checked.get(3), Err(AccessError::Poisoned));
+        assert!(checked.is_poisoned());
+    }
+}
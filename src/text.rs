@@ -0,0 +1,239 @@
+//! Memoization of `char` sequences as strings.
+
+use std::{
+    collections::Bound,
+    iter::FusedIterator,
+    ops::RangeBounds,
+};
+
+
+/// The number of characters between each stored byte offset.
+const STRIDE: usize = 64;
+
+
+/// A Memoized Iterator over `char`s, which stores its items as a `String`.
+///     Ranges of evaluated characters can be retrieved as `&str`, rather than
+///     as the `&[char]` that a `MemoIter` would give.
+///
+/// Characters are indexed by their position in the sequence, not by byte. The
+///     byte offset of every 64th character is remembered, so that any other
+///     can be found by decoding at most 63 characters.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::text::MemoString;
+///
+/// let mut memo = MemoString::new("Grüße, Jürgen! ❤".chars());
+///
+/// assert_eq!(memo.get(3), Some('ß'));
+/// assert_eq!(memo.get_str(7..13), "Jürgen");
+/// assert_eq!(memo.byte_offset(7), Some(9));
+/// assert_eq!(memo.as_str(), "Grüße, Jürgen");
+///
+/// assert_eq!(memo.exhaust(), "Grüße, Jürgen! ❤");
+/// ```
+#[derive(Debug)]
+pub struct MemoString<I> where
+    I: Iterator<Item=char>,
+{
+    chars: usize,
+    exhausted: bool,
+    iterator: I,
+    marks: Vec<usize>,
+    text: String,
+}
+
+
+impl<I> MemoString<I> where
+    I: Iterator<Item=char>,
+{
+    /// Create an empty `MemoString` wrapping a given Iterator.
+    pub fn new(iterator: I) -> Self {
+        Self {
+            chars: 0,
+            exhausted: false,
+            iterator,
+            marks: Vec::new(),
+            text: String::new(),
+        }
+    }
+
+    /// Return the number of characters evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.chars
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Return the text evaluated so far.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.chars <= idx {
+            match self.iterator.next() {
+                Some(c) => {
+                    if self.chars % STRIDE == 0 {
+                        self.marks.push(self.text.len());
+                    }
+
+                    self.text.push(c);
+                    self.chars += 1;
+                }
+                None => {
+                    self.exhausted = true;
+                    self.text.shrink_to_fit();
+                }
+            }
+        }
+    }
+
+    /// Return the byte offset in `as_str()` at which the character with a given
+    ///     index begins. The index one past the last evaluated character gives
+    ///     the length of the text. Returns `None` for any greater index.
+    pub fn byte_offset(&self, idx: usize) -> Option<usize> {
+        if idx < self.chars {
+            let start: usize = self.marks[idx / STRIDE];
+
+            self.text[start..].char_indices()
+                .nth(idx % STRIDE)
+                .map(|(offset, _)| start + offset)
+        } else if idx == self.chars {
+            Some(self.text.len())
+        } else {
+            None
+        }
+    }
+
+    /// Retrieve, by its index, a character returned by the Iterator. If the
+    ///     character has not yet been evaluated, the Iterator will be run until
+    ///     it has.
+    pub fn get(&mut self, idx: usize) -> Option<char> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a character returned by the Iterator. If the
+    ///     character has not yet been evaluated, `None` is returned.
+    pub fn recall(&self, idx: usize) -> Option<char> {
+        let offset: usize = self.byte_offset(idx)?;
+        self.text[offset..].chars().next()
+    }
+
+    /// Retrieve a range of characters returned by the Iterator, as a string
+    ///     slice. If the characters in the range have not yet been evaluated,
+    ///     they will be.
+    ///
+    /// As with `MemoIter::get_slice()`, a range with an unbounded end does no
+    ///     new evaluation, and a range extending past the end of the sequence
+    ///     is shortened rather than causing a panic.
+    pub fn get_str<R>(&mut self, range: R) -> &str where
+        R: RangeBounds<usize>,
+    {
+        let first: usize = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.saturating_add(1),
+        };
+
+        let end: usize = match range.end_bound() {
+            Bound::Unbounded => self.chars,
+            Bound::Included(&i) => {
+                self.expand_to_contain(i);
+                self.chars.min(i.saturating_add(1))
+            }
+            Bound::Excluded(&i) => {
+                if let Some(last) = i.checked_sub(1) {
+                    self.expand_to_contain(last);
+                }
+
+                self.chars.min(i)
+            }
+        };
+
+        if first < end {
+            match (self.byte_offset(first), self.byte_offset(end)) {
+                (Some(a), Some(b)) => &self.text[a..b],
+                _ => "",
+            }
+        } else {
+            ""
+        }
+    }
+
+    /// Evaluate the internal Iterator until it ends, returning the entire text.
+    ///
+    /// This method will not return if the Iterator is infinite.
+    pub fn exhaust(&mut self) -> &str {
+        self.expand_to_contain(usize::MAX);
+        &self.text
+    }
+
+    /// Consume self, returning the text evaluated so far and the internal
+    ///     Iterator.
+    pub fn consume(self) -> (String, I) {
+        (self.text, self.iterator)
+    }
+}
+
+
+impl<I> Iterator for MemoString<I> where
+    I: Iterator<Item=char>,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let idx: usize = self.chars;
+        self.get(idx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.exhausted {
+            (0, Some(0))
+        } else {
+            self.iterator.size_hint()
+        }
+    }
+}
+
+
+impl<I: Iterator<Item=char>> FusedIterator for MemoString<I> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_offsets() {
+        let source: String = (0..300).map(|n: usize| if n % 5 == 0 { 'é' } else { 'e' }).collect();
+        let mut memo = MemoString::new(source.chars());
+
+        assert_eq!(memo.get(200), Some('é'));
+        assert_eq!(memo.get_str(198..=201), "eeée");
+        assert_eq!(memo.byte_offset(200), Some(240));
+        assert_eq!(memo.byte_offset(202), Some(243));
+        assert_eq!(memo.byte_offset(203), None);
+
+        assert_eq!(memo.get_str(299..400), "e");
+        assert!(memo.is_exhausted());
+        assert_eq!(memo.get_str(400..), "");
+        assert_eq!(memo.get_str(10..5), "");
+        assert_eq!(memo.exhaust(), source);
+    }
+}
@@ -0,0 +1,191 @@
+//! Memoization which records when each item was evaluated.
+
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+
+/// A Memoized Iterator which stores, alongside each item, the moment it was
+///     evaluated. Items can then be looked up by the time elapsed since the
+///     `TimedMemoIter` was created, as well as by index, so that a memoized
+///     stream of events can be replayed or sliced by wall-clock time.
+///
+/// Timestamps are taken when the internal Iterator returns each item, and so
+///     never decrease. Because of this, every item stamped at or before a
+///     moment which has already passed must already have been evaluated, and
+///     time-based lookups never need to run the Iterator.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::timed::TimedMemoIter;
+/// use std::{thread::sleep, time::Duration};
+///
+/// let mut events = TimedMemoIter::new("abcdef".chars());
+///
+/// events.get(2);
+/// let checkpoint: Duration = events.origin().elapsed();
+/// sleep(Duration::from_millis(20));
+/// events.get(4);
+///
+/// assert_eq!(events.get_at_or_before(checkpoint), Some((2, &'c')));
+/// assert_eq!(events.indices_between(checkpoint..events.origin().elapsed()), 3..5);
+/// assert!(events.elapsed(4).unwrap() >= checkpoint + Duration::from_millis(20));
+/// ```
+#[derive(Debug)]
+pub struct TimedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    exhausted: bool,
+    iterator: I,
+    origin: Instant,
+    sequence: Vec<(Instant, T)>,
+}
+
+
+impl<I, T> TimedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    /// Create an empty `TimedMemoIter` wrapping a given Iterator, measuring
+    ///     time from now.
+    pub fn new(iterator: I) -> Self {
+        Self::with_origin(iterator, Instant::now())
+    }
+
+    /// Create an empty `TimedMemoIter` wrapping a given Iterator, measuring
+    ///     time from `origin`.
+    pub fn with_origin(iterator: I, origin: Instant) -> Self {
+        Self {
+            exhausted: false,
+            iterator,
+            origin,
+            sequence: Vec::new(),
+        }
+    }
+
+    /// Return the moment from which elapsed times are measured.
+    #[inline]
+    pub fn origin(&self) -> Instant {
+        self.origin
+    }
+
+    /// Return the number of items evaluated.
+    #[inline]
+    pub fn evaluated(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn expand_to_contain(&mut self, idx: usize) {
+        while !self.exhausted && self.sequence.len() <= idx {
+            match self.iterator.next() {
+                Some(item) => self.sequence.push((Instant::now(), item)),
+                None => {
+                    self.exhausted = true;
+                    self.sequence.shrink_to_fit();
+                }
+            }
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, the Iterator will be run until it has.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        self.expand_to_contain(idx);
+        self.recall(idx)
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: usize) -> Option<&T> {
+        self.sequence.get(idx).map(|(_, item)| item)
+    }
+
+    /// Return the moment at which the item at an index was evaluated, if it
+    ///     has been evaluated.
+    #[inline]
+    pub fn timestamp(&self, idx: usize) -> Option<Instant> {
+        self.sequence.get(idx).map(|&(time, _)| time)
+    }
+
+    /// Return the time between the origin and the evaluation of the item at an
+    ///     index, if it has been evaluated.
+    #[inline]
+    pub fn elapsed(&self, idx: usize) -> Option<Duration> {
+        self.timestamp(idx).map(|time| time.saturating_duration_since(self.origin))
+    }
+
+    /// Return the index of the first item evaluated after `elapsed` had passed
+    ///     since the origin, or the number of items evaluated if there is none.
+    fn partition(&self, elapsed: Duration) -> usize {
+        self.sequence.partition_point(|&(time, _)| {
+            time.saturating_duration_since(self.origin) <= elapsed
+        })
+    }
+
+    /// Return the last item evaluated at or before `elapsed` had passed since
+    ///     the origin, along with its index. Returns `None` if no item had yet
+    ///     been evaluated at that time.
+    pub fn get_at_or_before(&self, elapsed: Duration) -> Option<(usize, &T)> {
+        let idx: usize = self.partition(elapsed).checked_sub(1)?;
+        Some((idx, &self.sequence[idx].1))
+    }
+
+    /// Return the range of indices of the items evaluated after `range.start`
+    ///     and at or before `range.end` had passed since the origin.
+    pub fn indices_between(&self, range: Range<Duration>) -> Range<usize> {
+        let start: usize = self.partition(range.start);
+        start..self.partition(range.end).max(start)
+    }
+
+    /// Return a slice of the timestamped items evaluated so far.
+    #[inline]
+    pub fn as_slice(&self) -> &[(Instant, T)] {
+        &self.sequence
+    }
+
+    /// Return an Iterator over references to the values evaluated so far.
+    pub fn iter(&self) -> impl Iterator<Item=&T> + '_ {
+        self.sequence.iter().map(|(_, item)| item)
+    }
+
+    /// Consume self, returning the timestamped items evaluated so far, and the
+    ///     internal Iterator.
+    #[inline]
+    pub fn consume(self) -> (Vec<(Instant, T)>, I) {
+        (self.sequence, self.iterator)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timed() {
+        let origin: Instant = Instant::now();
+        let mut memo = TimedMemoIter::with_origin(0..10, origin);
+
+        assert_eq!(memo.get_at_or_before(Duration::from_secs(60)), None);
+        assert_eq!(memo.get(3), Some(&3));
+        assert_eq!(memo.get(20), None);
+        assert!(memo.is_exhausted());
+
+        let stamps: Vec<Duration> = (0..10).map(|idx| memo.elapsed(idx).unwrap()).collect();
+        assert!(stamps.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        assert_eq!(memo.get_at_or_before(stamps[9]), Some((9, &9)));
+        assert_eq!(memo.indices_between(Duration::ZERO..Duration::from_secs(60)), 0..10);
+        assert_eq!(memo.indices_between(stamps[9]..stamps[0]), 10..10);
+        assert_eq!(memo.timestamp(10), None);
+    }
+}
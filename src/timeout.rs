@@ -0,0 +1,197 @@
+//! Evaluation of Iterators with a time limit on each item.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    iter::FusedIterator,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+
+/// An error indicating that an item was not produced within the time limit.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TimedOut {
+    /// The position of the item which was not produced.
+    pub index: usize,
+    /// The time limit which was exceeded.
+    pub limit: Duration,
+}
+
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "item {} was not produced within {:?}", self.index, self.limit)
+    }
+}
+
+
+impl Error for TimedOut {}
+
+
+/// An Iterator adapter which advances its inner Iterator on a helper thread,
+///     and which gives up on any item that takes too long to produce. This
+///     turns a source which may hang into a fallible source, suitable for a
+///     `MemoIter` of `Result`s.
+///
+/// When an item times out, a `TimedOut` error takes its place, and the item is
+///     discarded if it is produced later, so that every later item keeps its
+///     position. The helper thread cannot be interrupted, so the following
+///     item cannot be produced until the hung one is; if the hung item takes
+///     longer than another full time limit to finish, the following item will
+///     also time out.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, timeout::TimeoutIter};
+/// use std::{sync::mpsc::channel, time::Duration};
+///
+/// //  The second item hangs until it is released.
+/// let (release, gate) = channel::<()>();
+/// let slow = (0..4).inspect(move |&n| if n == 1 {
+///     gate.recv().unwrap();
+/// });
+///
+/// let mut memo = MemoIter::new(TimeoutIter::new(slow, Duration::from_millis(100)));
+///
+/// assert_eq!(memo.get(0), Some(&Ok(0)));
+/// assert!(memo.get(1).unwrap().is_err());
+///
+/// release.send(()).unwrap();
+/// assert_eq!(memo.get(2), Some(&Ok(2)));
+/// ```
+#[derive(Debug)]
+pub struct TimeoutIter<T> {
+    done: bool,
+    index: usize,
+    limit: Duration,
+    requests: Sender<()>,
+    responses: Receiver<(usize, Option<T>)>,
+}
+
+
+impl<T: Send + 'static> TimeoutIter<T> {
+    /// Move an Iterator onto a helper thread, and wait no longer than `limit`
+    ///     for each of its items.
+    pub fn new<I>(mut iterator: I, limit: Duration) -> Self where
+        I: Iterator<Item=T> + Send + 'static,
+    {
+        let (requests, requested) = channel::<()>();
+        let (respond, responses) = channel();
+
+        thread::spawn(move || {
+            for index in 0.. {
+                if requested.recv().is_err() {
+                    break;
+                }
+
+                let item: Option<T> = iterator.next();
+                let end: bool = item.is_none();
+
+                if respond.send((index, item)).is_err() || end {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            done: false,
+            index: 0,
+            limit,
+            requests,
+            responses,
+        }
+    }
+
+    /// Return the time limit for each item.
+    #[inline]
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    /// Change the time limit for each item.
+    #[inline]
+    pub fn set_limit(&mut self, limit: Duration) {
+        self.limit = limit;
+    }
+}
+
+
+impl<T: Send + 'static> Iterator for TimeoutIter<T> {
+    type Item = Result<T, TimedOut>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.requests.send(()).is_err() {
+            self.done = true;
+            return None;
+        }
+
+        let index: usize = self.index;
+        self.index += 1;
+
+        //  NOTE: The limit applies to the item as a whole, so that time spent
+        //      discarding late responses counts against it.
+        let deadline: Option<Instant> = Instant::now().checked_add(self.limit);
+
+        loop {
+            let response = match deadline {
+                Some(deadline) => self.responses.recv_timeout(
+                    deadline.saturating_duration_since(Instant::now()),
+                ),
+                None => self.responses.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match response {
+                //  NOTE: Late responses to requests which already timed out
+                //      are discarded here.
+                Ok((i, _)) if i < index => continue,
+                Ok((_, Some(item))) => break Some(Ok(item)),
+                Ok((_, None)) | Err(RecvTimeoutError::Disconnected) => {
+                    self.done = true;
+                    break None;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    break Some(Err(TimedOut { index, limit: self.limit }));
+                }
+            }
+        }
+    }
+}
+
+
+impl<T: Send + 'static> FusedIterator for TimeoutIter<T> {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeout() {
+        let (release, gate) = channel::<()>();
+        let source = (0..5).inspect(move |&n| if n == 2 {
+            gate.recv().unwrap();
+        });
+
+        //  NOTE: The long limit is never reached, and the short one is only
+        //      reached by the item held back by the gate.
+        let long: Duration = Duration::from_secs(60);
+        let short: Duration = Duration::from_millis(10);
+        let mut iter = TimeoutIter::new(source, long);
+
+        assert_eq!(iter.next(), Some(Ok(0)));
+        assert_eq!(iter.next(), Some(Ok(1)));
+
+        iter.set_limit(short);
+        assert_eq!(iter.next(), Some(Err(TimedOut { index: 2, limit: short })));
+
+        iter.set_limit(long);
+        release.send(()).unwrap();
+
+        assert_eq!(iter.next(), Some(Ok(3)));
+        assert_eq!(iter.next(), Some(Ok(4)));
+        assert_eq!(iter.next(), None);
+    }
+}
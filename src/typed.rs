@@ -0,0 +1,190 @@
+//! Access to `MemoIter`s through strongly-typed indices.
+
+use std::{
+    collections::Bound,
+    marker::PhantomData,
+    ops::RangeBounds,
+};
+use crate::MemoIter;
+
+
+/// A type which can be used to index a `TypedMemoIter`. Implementing this for a
+///     newtype, such as `TokenId` or `Frame`, keeps indices into one sequence
+///     from being used by mistake on another.
+pub trait MemoIndex: Copy {
+    /// Convert a position in the sequence into an index.
+    fn from_position(position: usize) -> Self;
+
+    /// Convert this index into a position in the sequence.
+    fn position(self) -> usize;
+}
+
+
+impl MemoIndex for usize {
+    #[inline]
+    fn from_position(position: usize) -> Self {
+        position
+    }
+
+    #[inline]
+    fn position(self) -> usize {
+        self
+    }
+}
+
+
+/// A wrapper for a `MemoIter` whose accessors take and return indices of a
+///     specific type, rather than `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use memoiter::{MemoIter, typed::{MemoIndex, TypedMemoIter}};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Frame(u32);
+///
+/// impl MemoIndex for Frame {
+///     fn from_position(position: usize) -> Self { Frame(position as u32) }
+///     fn position(self) -> usize { self.0 as usize }
+/// }
+///
+/// let mut frames: TypedMemoIter<Frame, _, _> = MemoIter::new((0..).map(|n| n * 16)).into();
+///
+/// assert_eq!(frames.get(Frame(3)), Some(&48));
+/// assert_eq!(frames.get_slice(Frame(1)..Frame(3)), [16, 32]);
+/// assert_eq!(frames.end(), Frame(4));
+/// assert_eq!(frames.position_of(&32), Some(Frame(2)));
+/// ```
+#[derive(Debug)]
+pub struct TypedMemoIter<Ix, I, T> where
+    I: Iterator<Item=T>,
+{
+    memo: MemoIter<I, T>,
+    _index: PhantomData<fn(Ix) -> Ix>,
+}
+
+
+impl<Ix, I, T> TypedMemoIter<Ix, I, T> where
+    Ix: MemoIndex,
+    I: Iterator<Item=T>,
+{
+    /// Wrap a `MemoIter`.
+    pub fn new(memo: MemoIter<I, T>) -> Self {
+        Self { memo, _index: PhantomData }
+    }
+
+    /// Return a reference to the wrapped `MemoIter`.
+    #[inline]
+    pub fn memo(&self) -> &MemoIter<I, T> {
+        &self.memo
+    }
+
+    /// Consume self, returning the wrapped `MemoIter`.
+    #[inline]
+    pub fn into_inner(self) -> MemoIter<I, T> {
+        self.memo
+    }
+
+    /// Return the index after the last item evaluated, which is also the number
+    ///     of items evaluated.
+    #[inline]
+    pub fn end(&self) -> Ix {
+        Ix::from_position(self.memo.evaluated())
+    }
+
+    /// Return `true` if the internal Iterator has been exhausted and is done
+    ///     returning new values.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.memo.is_exhausted()
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator, evaluating it
+    ///     if needed, as with `MemoIter::get()`.
+    #[inline]
+    pub fn get(&mut self, idx: Ix) -> Option<&T> {
+        self.memo.get(idx.position())
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator. If the value
+    ///     has not yet been evaluated, `None` is returned.
+    #[inline]
+    pub fn recall(&self, idx: Ix) -> Option<&T> {
+        self.memo.sequence.get(idx.position())
+    }
+
+    /// Retrieve a slice of values returned by the Iterator, evaluating them if
+    ///     needed, as with `MemoIter::get_slice()`.
+    pub fn get_slice<R>(&mut self, range: R) -> &[T] where
+        R: RangeBounds<Ix>,
+    {
+        let start: Bound<usize> = map_bound(range.start_bound());
+        let end: Bound<usize> = map_bound(range.end_bound());
+
+        self.memo.get_slice((start, end))
+    }
+
+    /// Return the index of the first evaluated item equal to `item`, if there
+    ///     is one. Nothing new is evaluated.
+    pub fn position_of(&self, item: &T) -> Option<Ix> where
+        T: PartialEq,
+    {
+        self.memo.sequence.iter().position(|x| x == item).map(Ix::from_position)
+    }
+
+    /// Return an Iterator over the items evaluated so far, with their indices.
+    pub fn indexed(&self) -> impl Iterator<Item=(Ix, &T)> + '_ {
+        self.memo.sequence.iter()
+            .enumerate()
+            .map(|(position, item)| (Ix::from_position(position), item))
+    }
+}
+
+
+impl<Ix, I, T> From<MemoIter<I, T>> for TypedMemoIter<Ix, I, T> where
+    Ix: MemoIndex,
+    I: Iterator<Item=T>,
+{
+    fn from(memo: MemoIter<I, T>) -> Self {
+        Self::new(memo)
+    }
+}
+
+
+fn map_bound<Ix: MemoIndex>(bound: Bound<&Ix>) -> Bound<usize> {
+    match bound {
+        Bound::Included(idx) => Bound::Included(idx.position()),
+        Bound::Excluded(idx) => Bound::Excluded(idx.position()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct TokenId(usize);
+
+    impl MemoIndex for TokenId {
+        fn from_position(position: usize) -> Self { TokenId(position) }
+        fn position(self) -> usize { self.0 }
+    }
+
+    #[test]
+    fn test_typed() {
+        let mut tokens: TypedMemoIter<TokenId, _, _> = MemoIter::new("a b c d".split(' ')).into();
+
+        assert_eq!(tokens.get(TokenId(2)), Some(&"c"));
+        assert_eq!(tokens.get_slice(TokenId(1)..=TokenId(9)), ["b", "c", "d"]);
+        assert_eq!(tokens.recall(TokenId(4)), None);
+
+        let indexed: Vec<(TokenId, &&str)> = tokens.indexed().skip(2).collect();
+        assert_eq!(indexed, [(TokenId(2), &"c"), (TokenId(3), &"d")]);
+
+        let mut plain: TypedMemoIter<usize, _, _> = tokens.into_inner().into();
+        assert_eq!(plain.get(0), Some(&"a"));
+    }
+}
@@ -0,0 +1,55 @@
+//! An Iterator driven by a closure over explicit mutable state.
+
+use std::fmt::{self, Debug, Formatter};
+
+
+/// An Iterator which produces each item by calling a closure with mutable
+///     access to a state value. This gives a nameable type to stateful
+///     generators, without needing a custom Iterator struct for each.
+pub struct Unfold<S, F> {
+    func: F,
+    state: S,
+}
+
+
+impl<S, F, T> Unfold<S, F> where
+    F: FnMut(&mut S) -> Option<T>,
+{
+    /// Create an Iterator over the values returned by `func`, starting from a
+    ///     given initial state.
+    pub fn new(state: S, func: F) -> Self {
+        Self { func, state }
+    }
+
+    /// Return a reference to the current state.
+    #[inline]
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Consume self, returning the current state.
+    pub fn into_state(self) -> S {
+        self.state
+    }
+}
+
+
+impl<S: Debug, F> Debug for Unfold<S, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Unfold")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+
+impl<S, F, T> Iterator for Unfold<S, F> where
+    F: FnMut(&mut S) -> Option<T>,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.func)(&mut self.state)
+    }
+}
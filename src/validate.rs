@@ -0,0 +1,254 @@
+//! Detection of nondeterministic sources, which would make memoization
+//!     semantically wrong.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::{Deref, RangeBounds},
+    slice::SliceIndex,
+};
+use crate::MemoIter;
+
+
+/// An error indicating that two sequences expected to be equal differ at some
+///     index, either because their items differ or because only one of them
+///     ends there. This is returned when a source produces a different item
+///     when evaluated a second time, and by comparisons such as
+///     `MemoIter::eq_iter()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Divergence {
+    /// The first index at which the sequences differ.
+    pub index: usize,
+}
+
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "sequences diverge at index {}", self.index)
+    }
+}
+
+
+impl Error for Divergence {}
+
+
+/// A comparison of the items two caches of the same sequence have evaluated,
+///     as returned by `MemoIter::diff()`. This is mostly useful for checking
+///     that a persisted or replayed cache matches a live one.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CacheDiff {
+    /// The first index evaluated by both caches at which their items differ,
+    ///     if there is one.
+    pub divergence: Option<Divergence>,
+    /// The number of items evaluated by the first cache.
+    pub left: usize,
+    /// The number of items evaluated by the second cache.
+    pub right: usize,
+}
+
+
+impl CacheDiff {
+    /// Return `true` if the caches agree on every item which both have
+    ///     evaluated, meaning that one is a prefix of the other.
+    #[inline]
+    pub fn is_consistent(&self) -> bool {
+        self.divergence.is_none()
+    }
+
+    /// Return `true` if the caches have evaluated exactly the same items.
+    #[inline]
+    pub fn is_identical(&self) -> bool {
+        self.is_consistent() && self.left == self.right
+    }
+}
+
+
+/// A debugging wrapper for a `MemoIter` over a cloneable source, which checks
+///     that the source is deterministic.
+///
+/// A pristine clone of the source is kept from construction. Each time a given
+///     number of new items has been evaluated, a sample of the stored items
+///     is chosen, and a fresh clone of the pristine source is run to re-evaluate
+///     them. If any differs from the stored item, this wrapper panics.
+///
+/// Each check evaluates the source again up to the highest sampled index, so
+///     this should be used in tests and debugging rather than in production.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use memoiter::validate::ValidatedMemoIter;
+/// use std::{cell::Cell, rc::Rc};
+///
+/// //  A source whose clones share hidden state, and so are not independent.
+/// let shared = Rc::new(Cell::new(0));
+/// let source = (0..).map(move |n| {
+///     shared.set(shared.get() + 1);
+///     n + shared.get()
+/// });
+///
+/// let mut memo = ValidatedMemoIter::new(source, 10, 4);
+/// memo.get(100);
+/// ```
+#[derive(Debug)]
+pub struct ValidatedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    interval: usize,
+    memo: MemoIter<I, T>,
+    next_check: usize,
+    pristine: I,
+    samples: usize,
+    state: u64,
+}
+
+
+impl<I, T> ValidatedMemoIter<I, T> where
+    I: Clone + Iterator<Item=T>,
+    T: PartialEq,
+{
+    /// Create an empty `ValidatedMemoIter` wrapping a given Iterator. After
+    ///     every `interval` new items, `samples` stored items are re-evaluated.
+    pub fn new(iterator: I, interval: usize, samples: usize) -> Self {
+        let interval: usize = interval.max(1);
+
+        Self {
+            interval,
+            memo: MemoIter::new(iterator.clone()),
+            next_check: interval,
+            pristine: iterator,
+            samples,
+            state: 0x2545_F491_4F6C_DD1D,
+        }
+    }
+
+    /// Retrieve, by its index, a value returned by the Iterator, as with
+    ///     `MemoIter::get()`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if a check finds the source to be
+    ///     nondeterministic.
+    pub fn get(&mut self, idx: usize) -> Option<&T> {
+        self.memo.get(idx);
+        self.check_due();
+        self.memo.recall(idx)
+    }
+
+    /// Retrieve a slice of values returned by the Iterator, as with
+    ///     `MemoIter::get_slice()`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if a check finds the source to be
+    ///     nondeterministic.
+    pub fn get_slice<R>(&mut self, range: R) -> &[T] where
+        R: Clone + RangeBounds<usize> + SliceIndex<[T], Output=[T]>,
+    {
+        self.memo.get_slice(range.clone());
+        self.check_due();
+        self.memo.get_slice(range)
+    }
+
+    /// Consume self, returning the underlying `MemoIter`.
+    pub fn into_inner(self) -> MemoIter<I, T> {
+        self.memo
+    }
+
+    /// Re-evaluate every stored item from a fresh clone of the source, and
+    ///     return the first index at which they differ, if any.
+    pub fn validate_all(&self) -> Result<(), Divergence> {
+        let indices: Vec<usize> = (0..self.memo.evaluated()).collect();
+        self.validate(&indices)
+    }
+
+    /// Re-evaluate the items at a sorted list of indices from a fresh clone of
+    ///     the source, and compare them to the stored items.
+    fn validate(&self, indices: &[usize]) -> Result<(), Divergence> {
+        let mut fresh: I = self.pristine.clone();
+        let mut pos: usize = 0;
+
+        for &index in indices {
+            let item: Option<T> = fresh.nth(index - pos);
+            pos = index + 1;
+
+            if item.as_ref() != self.memo[..].get(index) {
+                return Err(Divergence { index });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_due(&mut self) {
+        let evaluated: usize = self.memo.evaluated();
+
+        if evaluated >= self.next_check {
+            self.next_check = evaluated.saturating_add(self.interval);
+
+            let mut indices: Vec<usize> = (0..self.samples)
+                .map(|_| (self.random() % evaluated as u64) as usize)
+                .collect();
+
+            indices.sort_unstable();
+            indices.dedup();
+
+            if let Err(e) = self.validate(&indices) {
+                panic!("source is nondeterministic: {}", e);
+            }
+        }
+    }
+
+    /// Advance an xorshift generator, to choose samples.
+    fn random(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+
+impl<I, T> Deref for ValidatedMemoIter<I, T> where
+    I: Iterator<Item=T>,
+{
+    type Target = MemoIter<I, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.memo
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut memo = ValidatedMemoIter::new((0..).map(|n| n * 3), 16, 8);
+
+        assert_eq!(memo.get(200), Some(&600));
+        assert_eq!(memo.get_slice(5..8), [15, 18, 21]);
+        assert_eq!(memo.validate_all(), Ok(()));
+        assert_eq!(memo.evaluated(), 201);
+    }
+
+    #[test]
+    fn test_nondeterministic() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let source = (0..).map(move |n| {
+            counter.set(counter.get() + 1);
+            if counter.get() == 50 { -1 } else { n }
+        });
+
+        let mut memo = ValidatedMemoIter::new(source, usize::MAX, 0);
+        memo.get(99);
+
+        assert_eq!(memo.validate_all(), Err(Divergence { index: 49 }));
+        assert_eq!(calls.get(), 150);
+    }
+}
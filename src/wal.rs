@@ -0,0 +1,282 @@
+//! Persistence of evaluated items in an append-only log file, so that long
+//!     computations survive crashes.
+
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    iter::Skip,
+    path::Path,
+    str::FromStr,
+};
+use crate::{MemoIter, record::{Record, Recorder}};
+
+
+/// Escape backslashes and line breaks in the text of an item, so that it is
+///     written as exactly one line.
+fn escape(text: &str) -> Cow<'_, str> {
+    if text.contains(&['\\', '\n', '\r'][..]) {
+        let mut escaped: String = String::with_capacity(text.len() + 2);
+
+        for c in text.chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                c => escaped.push(c),
+            }
+        }
+
+        Cow::Owned(escaped)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+
+/// Reverse `escape()`, returning `None` if the text holds an unknown escape.
+fn unescape(line: &str) -> Option<Cow<'_, str>> {
+    if !line.contains('\\') {
+        return Some(Cow::Borrowed(line));
+    }
+
+    let mut text: String = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        text.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+
+    Some(Cow::Owned(text))
+}
+
+
+/// How often a `LogRecorder` forces its writes through to the storage device.
+///
+/// Every item is handed to the operating system as soon as it is evaluated, so
+///     it survives a crash of the process under any policy. Syncing also makes
+///     it survive a crash of the whole system, at some cost in speed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SyncPolicy {
+    /// Never sync explicitly, leaving it to the operating system.
+    Never,
+    /// Sync after every item.
+    Always,
+    /// Sync after every `n` items.
+    Every(usize),
+}
+
+
+/// A Recorder which appends each item to a log file as one line of text, using
+///     its `Display` implementation, and syncs the file according to a
+///     `SyncPolicy`. Backslashes and line breaks in the text are escaped with
+///     backslashes, so that every item takes exactly one line.
+#[derive(Debug)]
+pub struct LogRecorder {
+    file: File,
+    policy: SyncPolicy,
+    unsynced: usize,
+}
+
+
+impl LogRecorder {
+    /// Append to an open file, syncing according to `policy`.
+    pub fn new(file: File, policy: SyncPolicy) -> Self {
+        Self { file, policy, unsynced: 0 }
+    }
+
+    /// Return the policy for syncing the file.
+    #[inline]
+    pub fn policy(&self) -> SyncPolicy {
+        self.policy
+    }
+
+    /// Sync any items written since the last sync.
+    pub fn sync(&mut self) -> io::Result<()> {
+        if self.unsynced > 0 {
+            self.file.sync_data()?;
+            self.unsynced = 0;
+        }
+
+        Ok(())
+    }
+}
+
+
+impl<T: Display> Recorder<T> for LogRecorder {
+    fn record(&mut self, item: &T) -> io::Result<()> {
+        //  NOTE: Each line is written whole, in a single call, so that a crash
+        //      can only ever leave a partial line at the very end of the log.
+        self.file.write_all(format!("{}\n", escape(&item.to_string())).as_bytes())?;
+        self.unsynced += 1;
+
+        match self.policy {
+            SyncPolicy::Never => Ok(()),
+            SyncPolicy::Always => self.sync(),
+            SyncPolicy::Every(n) if self.unsynced >= n => self.sync(),
+            SyncPolicy::Every(_) => Ok(()),
+        }
+    }
+}
+
+
+/// A `MemoIter` whose evaluated items are appended to a log file.
+pub type LoggedMemoIter<I, T> = MemoIter<Record<Skip<I>, LogRecorder>, T>;
+
+
+impl<I, T> MemoIter<Record<Skip<I>, LogRecorder>, T> where
+    I: Iterator<Item=T>,
+    T: Display + FromStr,
+{
+    /// Create a `MemoIter` which appends every evaluated item to the log file
+    ///     at `path`, first restoring every item already in the log. Evaluation
+    ///     continues after the restored items, which the Iterator will skip
+    ///     when it is first advanced.
+    ///
+    /// The file is created if it does not exist. If the last line is not
+    ///     terminated, as may be left by a crash during a write, it is removed,
+    ///     so that new items follow on from the last complete one.
+    ///
+    /// Returns an error of kind `InvalidData`, and leaves the file unchanged, if
+    ///     any complete line cannot be parsed as a `T`, such as when the log
+    ///     was written for items of a different type.
+    ///
+    /// If writing to the log fails, the error is kept by the `Record` adapter,
+    ///     and evaluation continues without logging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use memoiter::{MemoIter, wal::SyncPolicy};
+    ///
+    /// # let dir = std::env::temp_dir().join(format!("memoiter-doc-{}", std::process::id()));
+    /// # std::fs::create_dir_all(&dir).unwrap();
+    /// let path = dir.join("squares.log");
+    /// # let _ = std::fs::remove_file(&path);
+    ///
+    /// let mut memo = MemoIter::logged(&path, (0u64..).map(|n| n * n), SyncPolicy::Never)?;
+    /// assert_eq!(memo.get(9), Some(&81));
+    /// drop(memo);
+    ///
+    /// //  Later, or after a crash, the log restores the evaluated items.
+    /// let mut memo = MemoIter::logged(&path, (0u64..).map(|n| n * n), SyncPolicy::Never)?;
+    /// assert_eq!(memo.evaluated(), 10);
+    /// assert_eq!(memo.get(10), Some(&100));
+    /// # std::fs::remove_dir_all(&dir).unwrap();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn logged<P>(path: P, iterator: I, policy: SyncPolicy) -> io::Result<Self> where
+        P: AsRef<Path>,
+    {
+        let mut file: File = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut reader: BufReader<&File> = BufReader::new(&file);
+        let mut sequence: Vec<T> = Vec::new();
+        let mut valid: u64 = 0;
+        let mut line: Vec<u8> = Vec::new();
+
+        loop {
+            line.clear();
+            let n: usize = reader.read_until(b'\n', &mut line)?;
+
+            //  NOTE: Only an unterminated final line can have been torn by a
+            //      crash. Anything else that fails to parse is real data, which
+            //      must not be discarded.
+            if line.pop() != Some(b'\n') {
+                break;
+            }
+
+            let item: Option<T> = std::str::from_utf8(&line).ok()
+                .and_then(unescape)
+                .and_then(|text| text.parse().ok());
+
+            match item {
+                Some(item) => {
+                    sequence.push(item);
+                    valid += n as u64;
+                }
+                None => return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {} of the log cannot be parsed", sequence.len() + 1),
+                )),
+            }
+        }
+
+        drop(reader);
+
+        if file.seek(SeekFrom::End(0))? > valid {
+            file.set_len(valid)?;
+            file.sync_data()?;
+        }
+
+        let skip: usize = sequence.len();
+        let recorder: LogRecorder = LogRecorder::new(file, policy);
+
+        Ok(Self::with_vec(Record::new(iterator.skip(skip), recorder), sequence))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+    use crate::testing::CountingIter;
+    use super::*;
+
+    #[test]
+    fn test_torn_log() {
+        let dir: PathBuf = std::env::temp_dir()
+            .join(format!("memoiter-test-{}", std::process::id()));
+        let path: PathBuf = dir.join("torn.log");
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(&path, "0\n1\n2\n3").unwrap();
+
+        let source = CountingIter::new(0..10u8);
+        let counter = source.counter();
+        let mut memo = MemoIter::logged(&path, source, SyncPolicy::Every(2)).unwrap();
+
+        assert_eq!(memo.evaluated(), 3);
+        assert_eq!(memo.get(4), Some(&4));
+        assert_eq!(counter.times_advanced(), 5);
+        drop(memo);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "0\n1\n2\n3\n4\n");
+
+        let mut memo = MemoIter::logged(&path, 0..10u8, SyncPolicy::Always).unwrap();
+        assert_eq!(memo.exhaust(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        drop(memo);
+
+        //  A complete line of the wrong type is an error, and nothing is lost.
+        let before: String = fs::read_to_string(&path).unwrap();
+        let other = MemoIter::<_, bool>::logged(&path, std::iter::empty(), SyncPolicy::Never);
+        assert_eq!(other.map(|_| ()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+
+        //  Line breaks within an item are escaped, and restored intact.
+        let text: PathBuf = dir.join("text.log");
+        let lines = vec![String::from("a\nb"), String::from("c\\n"), String::new()];
+
+        let mut memo = MemoIter::logged(&text, lines.clone().into_iter(), SyncPolicy::Never).unwrap();
+        assert_eq!(memo.exhaust(), &lines[..]);
+        drop(memo);
+
+        let mut memo = MemoIter::logged(&text, std::iter::empty::<String>(), SyncPolicy::Never).unwrap();
+        assert_eq!(memo.exhaust(), &lines[..]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+//! Wrappers exposing `MemoIter`s of common item types to JavaScript through
+//!     `wasm-bindgen`.
+//!
+//! The sequence itself is defined in Rust, by wrapping any Iterator of the
+//!     right item type, and the wrapper is then returned from an exported
+//!     function. JavaScript can then read the sequence by index, evaluating
+//!     only as much of it as is needed:
+//!
+//! ```
+//! use memoiter::wasm::MemoF64;
+//! use wasm_bindgen::prelude::*;
+//!
+//! #[wasm_bindgen]
+//! pub fn harmonic() -> MemoF64 {
+//!     MemoF64::new((1..).scan(0.0, |sum, n| {
+//!         *sum += 1.0 / n as f64;
+//!         Some(*sum)
+//!     }))
+//! }
+//! ```
+//!
+//! In JavaScript, `harmonic().getSlice(0, 100)` then returns a `Float64Array`
+//!     of the first hundred harmonic numbers.
+
+use wasm_bindgen::prelude::*;
+use crate::MemoIter;
+
+
+macro_rules! impl_wasm_memo {
+    ($($name:ident($t:ty);)*) => {$(
+        #[doc = concat!("A `MemoIter` over `", stringify!($t), "` items, exported to JavaScript.")]
+        #[wasm_bindgen]
+        pub struct $name {
+            memo: MemoIter<Box<dyn Iterator<Item=$t>>, $t>,
+        }
+
+
+        impl $name {
+            /// Create a wrapper around an empty `MemoIter` over a given
+            ///     Iterator.
+            pub fn new<I>(iterator: I) -> Self where
+                I: Iterator<Item=$t> + 'static,
+            {
+                Self { memo: MemoIter::new(Box::new(iterator)) }
+            }
+
+            /// Return the wrapped `MemoIter`.
+            #[inline]
+            pub fn memo(&mut self) -> &mut MemoIter<Box<dyn Iterator<Item=$t>>, $t> {
+                &mut self.memo
+            }
+        }
+
+
+        #[wasm_bindgen]
+        impl $name {
+            /// Retrieve an item by its index, evaluating the sequence up to it
+            ///     if needed. Returns `undefined` if the sequence ends first.
+            pub fn get(&mut self, idx: usize) -> Option<$t> {
+                self.memo.get(idx).cloned()
+            }
+
+            /// Retrieve the items from `start` up to, but not including, `end`,
+            ///     evaluating the sequence up to them if needed. Fewer items are
+            ///     returned if the sequence ends first.
+            #[wasm_bindgen(js_name = getSlice)]
+            pub fn get_slice(&mut self, start: usize, end: usize) -> Vec<$t> {
+                self.memo.get_slice(start..end).to_vec()
+            }
+
+            /// Return the number of items evaluated.
+            pub fn evaluated(&self) -> usize {
+                self.memo.evaluated()
+            }
+
+            /// Return `true` if the sequence has ended.
+            #[wasm_bindgen(js_name = isExhausted)]
+            pub fn is_exhausted(&self) -> bool {
+                self.memo.is_exhausted()
+            }
+        }
+    )*};
+}
+
+impl_wasm_memo! {
+    MemoF64(f64);
+    MemoI32(i32);
+    MemoU32(u32);
+    MemoStrings(String);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrappers() {
+        let mut halves = MemoF64::new((0..4).map(|n| n as f64 / 2.0));
+
+        assert_eq!(halves.get(2), Some(1.0));
+        assert_eq!(halves.get_slice(1, 10), [0.5, 1.0, 1.5]);
+        assert_eq!(halves.get(4), None);
+        assert!(halves.is_exhausted());
+
+        let mut words = MemoStrings::new("to be or not".split(' ').map(String::from));
+
+        assert_eq!(words.get_slice(2, 4), ["or", "not"]);
+        assert_eq!(words.evaluated(), 4);
+        assert_eq!(words.memo().recall(0).map(String::as_str), Some("to"));
+    }
+}